@@ -230,6 +230,114 @@ class Layer:
         ...
 
 
+class ColorFormat:
+    Hex: ColorFormat = ...
+    Rgb: ColorFormat = ...
+    Hsl: ColorFormat = ...
+    X: ColorFormat = ...
+    Css: ColorFormat = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class ColorFormatErrorKind:
+    UnknownFormat: ColorFormatErrorKind = ...
+    UnexpectedCharacters: ColorFormatErrorKind = ...
+    NoOpeningParenthesis: ColorFormatErrorKind = ...
+    NoClosingParenthesis: ColorFormatErrorKind = ...
+    UnknownColorSpace: ColorFormatErrorKind = ...
+    MissingCoordinate: ColorFormatErrorKind = ...
+    OversizedCoordinate: ColorFormatErrorKind = ...
+    MalformedHex: ColorFormatErrorKind = ...
+    MalformedFloat: ColorFormatErrorKind = ...
+    TooManyCoordinates: ColorFormatErrorKind = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class ColorFormatError(ValueError):
+    @property
+    def kind(self) -> ColorFormatErrorKind:
+        ...
+    @property
+    def input(self) -> str:
+        ...
+    @property
+    def span(self) -> tuple[int, int]:
+        ...
+    @property
+    def coordinate(self) -> None | int:
+        ...
+    @property
+    def expected(self) -> None | str:
+        ...
+    @property
+    def found(self) -> str:
+        ...
+    def __repr__(self) -> str:
+        ...
+    def __str__(self) -> str:
+        ...
+
+
+class GamutMapping_Css(GamutMapping):
+    def __new__(cls, jnd: float) -> Self:
+        ...
+    @property
+    def jnd(self) -> float:
+        ...
+
+
+class GamutMapping_Raytrace(GamutMapping):
+    def __new__(cls) -> Self:
+        ...
+
+
+class GamutMapping_ChromaCompression(GamutMapping):
+    def __new__(cls) -> Self:
+        ...
+
+
+class GamutMapping_HuePreservingClip(GamutMapping):
+    def __new__(cls) -> Self:
+        ...
+
+
+class GamutMapping:
+    Css = GamutMapping_Css
+    Raytrace = GamutMapping_Raytrace
+    ChromaCompression = GamutMapping_ChromaCompression
+    HuePreservingClip = GamutMapping_HuePreservingClip
+
+    CSS: GamutMapping = ...
+
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
+class GamutBoundary:
+    def space(self) -> ColorSpace:
+        ...
+    def max_chroma(self, lightness: float, hue: float) -> float:
+        ...
+    def cusp(self, hue: float) -> list[float]:
+        ...
+
+
 class ColorSpace:
     Srgb: ColorSpace = ...
     LinearSrgb: ColorSpace = ...
@@ -237,20 +345,51 @@ class ColorSpace:
     LinearDisplayP3: ColorSpace = ...
     Rec2020: ColorSpace = ...
     LinearRec2020: ColorSpace = ...
+    Rec2100Pq: ColorSpace = ...
+    Rec2100Hlg: ColorSpace = ...
+    A98Rgb: ColorSpace = ...
+    LinearA98Rgb: ColorSpace = ...
+    ProPhotoRgb: ColorSpace = ...
+    LinearProPhotoRgb: ColorSpace = ...
+    Hsl: ColorSpace = ...
+    Hsv: ColorSpace = ...
+    Hwb: ColorSpace = ...
     Oklab: ColorSpace = ...
     Oklch: ColorSpace = ...
     Oklrab: ColorSpace = ...
     Oklrch: ColorSpace = ...
+    Okhsv: ColorSpace = ...
+    Okhsl: ColorSpace = ...
+    Lab: ColorSpace = ...
+    Lch: ColorSpace = ...
+    Cam16Ucs: ColorSpace = ...
+    Hct: ColorSpace = ...
+    Ictcp: ColorSpace = ...
+    Jzazbz: ColorSpace = ...
+    Jzczhz: ColorSpace = ...
     Xyz: ColorSpace = ...
+    XyzD50: ColorSpace = ...
 
     def is_rgb(self) -> bool:
         ...
     def is_polar(self) -> bool:
         ...
+    def hue_index(self) -> None | int:
+        ...
     def is_ok(self) -> bool:
         ...
+    def is_lab(self) -> bool:
+        ...
+    def is_cylindrical(self) -> bool:
+        ...
     def is_bounded(self) -> bool:
         ...
+    def max_chroma(self, lightness: float, hue: float) -> None | float:
+        ...
+    def cusp(self, hue: float) -> None | list[float]:
+        ...
+    def gamut_boundary(self) -> None | GamutBoundary:
+        ...
     def __hash__(self) -> int:
         ...
     def __eq__(self, other: object) -> bool:
@@ -291,12 +430,209 @@ class OkVersion:
         ...
 
 
+class Adjustment_RotateHue(Adjustment):
+    def __new__(cls, degrees: float) -> Self:
+        ...
+    @property
+    def degrees(self) -> float:
+        ...
+
+
+class Adjustment_Saturate(Adjustment):
+    def __new__(cls, amount: float) -> Self:
+        ...
+    @property
+    def amount(self) -> float:
+        ...
+
+
+class Adjustment_Desaturate(Adjustment):
+    def __new__(cls, amount: float) -> Self:
+        ...
+    @property
+    def amount(self) -> float:
+        ...
+
+
+class Adjustment_ScaleChroma(Adjustment):
+    def __new__(cls, factor: float) -> Self:
+        ...
+    @property
+    def factor(self) -> float:
+        ...
+
+
+class Adjustment_SetLightness(Adjustment):
+    def __new__(cls, lightness: float) -> Self:
+        ...
+    @property
+    def lightness(self) -> float:
+        ...
+
+
+class Adjustment_SetChroma(Adjustment):
+    def __new__(cls, chroma: float) -> Self:
+        ...
+    @property
+    def chroma(self) -> float:
+        ...
+
+
+class Adjustment_Grayscale(Adjustment):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Adjustment_Invert(Adjustment):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Adjustment_Tint(Adjustment):
+    def __new__(cls, fraction: float) -> Self:
+        ...
+    @property
+    def fraction(self) -> float:
+        ...
+
+
+class Adjustment_Shade(Adjustment):
+    def __new__(cls, fraction: float) -> Self:
+        ...
+    @property
+    def fraction(self) -> float:
+        ...
+
+
+class Adjustment:
+    RotateHue = Adjustment_RotateHue
+    Saturate = Adjustment_Saturate
+    Desaturate = Adjustment_Desaturate
+    ScaleChroma = Adjustment_ScaleChroma
+    SetLightness = Adjustment_SetLightness
+    SetChroma = Adjustment_SetChroma
+    Grayscale = Adjustment_Grayscale
+    Invert = Adjustment_Invert
+    Tint = Adjustment_Tint
+    Shade = Adjustment_Shade
+
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
+class Harmony:
+    Complementary: Harmony = ...
+    Analogous: Harmony = ...
+    Triadic: Harmony = ...
+    Tetradic: Harmony = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class ColorVisionDeficiency:
+    Protanopia: ColorVisionDeficiency = ...
+    Deuteranopia: ColorVisionDeficiency = ...
+    Tritanopia: ColorVisionDeficiency = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class CvdMethod:
+    Machado: CvdMethod = ...
+    Brettel: CvdMethod = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class DeltaE_Ok(DeltaE):
+    def __new__(cls, _0: OkVersion) -> Self:
+        ...
+    @property
+    def _0(self) -> OkVersion:
+        ...
+
+
+class DeltaE_Ciede2000(DeltaE):
+    def __new__(cls) -> Self:
+        ...
+
+
+class DeltaE_Cie94(DeltaE):
+    def __new__(cls) -> Self:
+        ...
+
+
+class DeltaE_Cmc(DeltaE):
+    def __new__(cls, lightness: float, chroma: float) -> Self:
+        ...
+    @property
+    def lightness(self) -> float:
+        ...
+    @property
+    def chroma(self) -> float:
+        ...
+
+
+class DeltaE_HyAb(DeltaE):
+    def __new__(cls) -> Self:
+        ...
+
+
+class DeltaE_Itp(DeltaE):
+    def __new__(cls) -> Self:
+        ...
+
+
+class DeltaE:
+    Ok = DeltaE_Ok
+    Ciede2000 = DeltaE_Ciede2000
+    Cie94 = DeltaE_Cie94
+    Cmc = DeltaE_Cmc
+    HyAb = DeltaE_HyAb
+    Itp = DeltaE_Itp
+
+    CMC: DeltaE = ...
+
+    def space(self) -> ColorSpace:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
 class Color:
     def __new__(
         cls, space: ColorSpace, coordinates: tuple[float, float, float]
     ) -> Self:
         ...
     @staticmethod
+    def from_rgb_space(
+        space: RgbSpace, coordinates: tuple[float, float, float]
+    ) -> Color:
+        ...
+    @staticmethod
     def parse(s: str) -> Color:
         ...
     @staticmethod
@@ -318,16 +654,38 @@ class Color:
     def oklrch(lr: float, c: float, h: float) -> Color:
         ...
     @staticmethod
+    def hct(h: float, c: float, t: float) -> Color:
+        ...
+    @staticmethod
     def from_24bit(r: int, g: int, b: int) -> Color:
         ...
+    @staticmethod
+    def from_x11_name(name: str) -> None | Color:
+        ...
     def to_24bit(self) -> list[int]:
         ...
     def to_hex_format(self) -> str:
         ...
+    def to_format(self, format: ColorFormat) -> str:
+        ...
+    def css_name(self) -> None | str:
+        ...
+    def x11_name(self) -> None | str:
+        ...
     def is_default(self) -> bool:
         ...
     def space(self) -> ColorSpace:
         ...
+    def alpha(self) -> float:
+        ...
+    def rgb_space(self) -> None | RgbSpace:
+        ...
+    def with_alpha(self, alpha: float) -> Self:
+        ...
+    def is_opaque(self) -> bool:
+        ...
+    def over(self, background: Self) -> Self:
+        ...
     def coordinates(self) -> list[float]:
         ...
     def normalize(self) -> Self:
@@ -340,7 +698,21 @@ class Color:
         ...
     def to_gamut(self) -> Self:
         ...
-    def distance(self, other: Self, version: OkVersion) -> float:
+    def to_gamut_with(self, mapping: GamutMapping) -> Self:
+        ...
+    def to_gamut_hct(self) -> Self:
+        ...
+    def to_rgb_space(self, space: RgbSpace) -> Self:
+        ...
+    def rgb_space_coordinates(self) -> None | list[float]:
+        ...
+    def to_cam16(self, conditions: ViewingConditions) -> Cam16:
+        ...
+    def distance(self, other: Self, metric: DeltaE) -> float:
+        ...
+    def delta_e_itp(self, other: Self) -> float:
+        ...
+    def delta_e_z(self, other: Self) -> float:
         ...
     def interpolate(
         self,
@@ -349,10 +721,39 @@ class Color:
         interpolation_strategy: HueInterpolation,
     ) -> Interpolator:
         ...
+    def mix(
+        self,
+        weight: None | float,
+        other: Self,
+        other_weight: None | float,
+        interpolation_space: ColorSpace,
+        interpolation_strategy: HueInterpolation,
+    ) -> None | Self:
+        ...
     def lighten(self, factor: float) -> Self:
         ...
     def darken(self, factor: float) -> Self:
         ...
+    def adjust(
+        self, adjustment: Adjustment, target: None | ColorSpace = None
+    ) -> Self:
+        ...
+    def harmony(self, harmony: Harmony, target: ColorSpace) -> list[Color]:
+        ...
+    def tints(self, count: int, target: ColorSpace) -> list[Color]:
+        ...
+    def shades(self, count: int, target: ColorSpace) -> list[Color]:
+        ...
+    def scale(self, target: ColorSpace) -> list[Color]:
+        ...
+    def simulate_cvd(
+        self, deficiency: ColorVisionDeficiency, method: CvdMethod, severity: float
+    ) -> Self:
+        ...
+    def daltonize(
+        self, deficiency: ColorVisionDeficiency, method: CvdMethod, severity: float
+    ) -> Self:
+        ...
     def contrast_against(self, background: Self) -> float:
         ...
     def use_black_text(self) -> bool:
@@ -371,6 +772,196 @@ class Color:
         ...
     def __str__(self) -> str:
         ...
+    def __format__(self, spec: str) -> str:
+        ...
+
+
+class TransferCurve:
+    ...
+
+
+class TransferFunction_Piecewise(TransferFunction):
+    def __new__(
+        cls, gamma: float, a: float, b: float, c: float, d: float
+    ) -> Self:
+        ...
+    @property
+    def gamma(self) -> float:
+        ...
+    @property
+    def a(self) -> float:
+        ...
+    @property
+    def b(self) -> float:
+        ...
+    @property
+    def c(self) -> float:
+        ...
+    @property
+    def d(self) -> float:
+        ...
+
+
+class TransferFunction_Gamma(TransferFunction):
+    def __new__(cls, gamma: float) -> Self:
+        ...
+    @property
+    def gamma(self) -> float:
+        ...
+
+
+class TransferFunction_Pq(TransferFunction):
+    def __new__(cls) -> Self:
+        ...
+
+
+class TransferFunction_Hlg(TransferFunction):
+    def __new__(cls) -> Self:
+        ...
+
+
+class TransferFunction_Custom(TransferFunction):
+    def __new__(
+        cls, to_linear: TransferCurve, from_linear: TransferCurve
+    ) -> Self:
+        ...
+    @property
+    def to_linear(self) -> TransferCurve:
+        ...
+    @property
+    def from_linear(self) -> TransferCurve:
+        ...
+
+
+class TransferFunction:
+    Piecewise = TransferFunction_Piecewise
+    Gamma = TransferFunction_Gamma
+    Pq = TransferFunction_Pq
+    Hlg = TransferFunction_Hlg
+    Custom = TransferFunction_Custom
+
+    SRGB: TransferFunction = ...
+    REC709: TransferFunction = ...
+    PQ: TransferFunction = ...
+    HLG: TransferFunction = ...
+
+    def to_linear(self, value: float) -> float:
+        ...
+    def from_linear(self, value: float) -> float:
+        ...
+
+
+class RgbSpace:
+    def __new__(
+        cls,
+        primaries: tuple[
+            tuple[float, float], tuple[float, float], tuple[float, float]
+        ],
+        white_point: tuple[float, float],
+        transfer: TransferFunction,
+    ) -> Self:
+        ...
+    def primaries(self) -> list[list[float]]:
+        ...
+    def white_point(self) -> list[float]:
+        ...
+    def transfer(self) -> TransferFunction:
+        ...
+    def to_linear(
+        self, coordinates: tuple[float, float, float]
+    ) -> list[float]:
+        ...
+    def from_linear(
+        self, coordinates: tuple[float, float, float]
+    ) -> list[float]:
+        ...
+    def to_xyz(self, coordinates: tuple[float, float, float]) -> list[float]:
+        ...
+    def from_xyz(self, coordinates: tuple[float, float, float]) -> list[float]:
+        ...
+    def in_gamut(self, coordinates: tuple[float, float, float]) -> bool:
+        ...
+    def clip(self, coordinates: tuple[float, float, float]) -> list[float]:
+        ...
+    def to_gamut(self, coordinates: tuple[float, float, float]) -> list[float]:
+        ...
+    def to_contrast_luminance(
+        self, coordinates: tuple[float, float, float]
+    ) -> float:
+        ...
+
+
+class Surround:
+    Average: Surround = ...
+    Dim: Surround = ...
+    Dark: Surround = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class Cam16:
+    @property
+    def lightness(self) -> float:
+        ...
+    @property
+    def chroma(self) -> float:
+        ...
+    @property
+    def hue(self) -> float:
+        ...
+    @property
+    def colorfulness(self) -> float:
+        ...
+    @property
+    def saturation(self) -> float:
+        ...
+    @property
+    def brightness(self) -> float:
+        ...
+
+
+class ViewingConditions:
+    def __new__(
+        cls,
+        white_point: tuple[float, float, float],
+        adapting_luminance: float,
+        background_luminance: float,
+        surround: Surround,
+        discounting: bool,
+    ) -> Self:
+        ...
+    @staticmethod
+    def with_surround(surround: Surround) -> ViewingConditions:
+        ...
+    def white_point(self) -> list[float]:
+        ...
+    def adapting_luminance(self) -> float:
+        ...
+    def background_luminance(self) -> float:
+        ...
+    def surround(self) -> Surround:
+        ...
+    def discounting(self) -> bool:
+        ...
+    def to_cam16(self, coordinates: tuple[float, float, float]) -> Cam16:
+        ...
+    def from_cam16(
+        self, coordinates: tuple[float, float, float]
+    ) -> list[float]:
+        ...
+    def to_cam16_ucs(
+        self, coordinates: tuple[float, float, float]
+    ) -> list[float]:
+        ...
+    def from_cam16_ucs(
+        self, coordinates: tuple[float, float, float]
+    ) -> list[float]:
+        ...
 
 
 class Interpolator:
@@ -386,6 +977,155 @@ class Interpolator:
         ...
 
 
+class Easing_Linear(Easing):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Easing_Ease(Easing):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Easing_EaseIn(Easing):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Easing_EaseOut(Easing):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Easing_EaseInOut(Easing):
+    def __new__(cls) -> Self:
+        ...
+
+
+class Easing_CubicBezier(Easing):
+    def __new__(cls, x1: float, y1: float, x2: float, y2: float) -> Self:
+        ...
+    @property
+    def x1(self) -> float:
+        ...
+    @property
+    def y1(self) -> float:
+        ...
+    @property
+    def x2(self) -> float:
+        ...
+    @property
+    def y2(self) -> float:
+        ...
+
+
+class Easing_Midpoint(Easing):
+    def __new__(cls, hint: float) -> Self:
+        ...
+    @property
+    def hint(self) -> float:
+        ...
+
+
+class Easing:
+    Linear = Easing_Linear
+    Ease = Easing_Ease
+    EaseIn = Easing_EaseIn
+    EaseOut = Easing_EaseOut
+    EaseInOut = Easing_EaseInOut
+    CubicBezier = Easing_CubicBezier
+    Midpoint = Easing_Midpoint
+
+    def apply(self, progress: float) -> float:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
+class Gradient:
+    def __new__(
+        cls,
+        colors: list[Color],
+        space: ColorSpace,
+        strategy: HueInterpolation
+    ) -> Self:
+        ...
+    @staticmethod
+    def with_positions(
+        colors: list[Color],
+        positions: list[float],
+        space: ColorSpace,
+        strategy: HueInterpolation
+    ) -> Gradient:
+        ...
+    def stop_count(self) -> int:
+        ...
+    def start(self) -> float:
+        ...
+    def end(self) -> float:
+        ...
+    def set_interpolation(
+        self, index: int, space: ColorSpace, strategy: HueInterpolation
+    ) -> None:
+        ...
+    def set_easing(self, index: int, easing: Easing) -> None:
+        ...
+    def at(self, position: float) -> Color:
+        ...
+    def sample(self, count: int) -> GradientIterator:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
+class GradientIterator:
+    def __iter__(self) -> Self:
+        ...
+    def __next__(self) -> Color:
+        ...
+
+
+class SplineKind:
+    CatmullRom: SplineKind = ...
+    Natural: SplineKind = ...
+
+    def __hash__(self) -> int:
+        ...
+    def __eq__(self, other: object) -> bool:
+        ...
+    def __ne__(self, other: object) -> bool:
+        ...
+
+
+class Spline:
+    def __new__(
+        cls,
+        colors: list[Color],
+        space: ColorSpace,
+        strategy: HueInterpolation,
+        kind: SplineKind
+    ) -> Self:
+        ...
+    def space(self) -> ColorSpace:
+        ...
+    def kind(self) -> SplineKind:
+        ...
+    def is_arc_length(self) -> bool:
+        ...
+    def with_arc_length(self) -> Spline:
+        ...
+    def at(self, fraction: float) -> Color:
+        ...
+    def sample(self, count: int) -> list[Color]:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
 class ThemeEntry:
     Foreground: ThemeEntry = ...
     Background: ThemeEntry = ...
@@ -441,17 +1181,38 @@ class Theme:
         ...
     def __repr__(self) -> str:
         ...
+    def simulate_cvd(
+        self, deficiency: ColorVisionDeficiency, method: CvdMethod, severity: float
+    ) -> Theme:
+        ...
+    def daltonize(
+        self, deficiency: ColorVisionDeficiency, method: CvdMethod, severity: float
+    ) -> Theme:
+        ...
+    def adjust(
+        self, adjustment: Adjustment, target: None | ColorSpace = None
+    ) -> Theme:
+        ...
+    def parse(self, s: str) -> Color:
+        ...
 
 
 class Sampler:
     def __new__(cls, theme: Theme, ok_version: OkVersion) -> Self:
         ...
+    @staticmethod
+    def with_conditions(theme: Theme, conditions: ViewingConditions) -> Sampler:
+        ...
+    def conditions(self) -> None | ViewingConditions:
+        ...
     def to_high_res_8bit(self, color: int) -> Color:
         ...
     def try_high_res(self, color: TerminalColor) -> None | Color:
         ...
     def to_high_res(self, color: TerminalColor, layer: Layer) -> Color:
         ...
+    def resolve(self, color: Color) -> Color:
+        ...
     def to_closest_ansi(self, color: Color) -> AnsiColor:
         ...
     def to_ansi_in_rgb(self, color: Color) -> AnsiColor:
@@ -462,3 +1223,36 @@ class Sampler:
         ...
     def adjust(self, color: TerminalColor, fidelity: Fidelity) -> None | TerminalColor:
         ...
+
+
+class CategoricalPalette:
+    def __new__(cls, space: ColorSpace, version: OkVersion) -> Self:
+        ...
+    def set_lightness(self, min: float, max: float) -> None:
+        ...
+    def set_contrast(self, background: Color, contrast: float) -> None:
+        ...
+    def avoid(self, color: Color) -> None:
+        ...
+    def avoid_theme(self, theme: Theme) -> None:
+        ...
+    def generate(self, count: int) -> list[Color]:
+        ...
+    def __repr__(self) -> str:
+        ...
+
+
+class TonalPalette:
+    def __new__(cls, hue: float, chroma: float) -> Self:
+        ...
+    @staticmethod
+    def from_color(color: Color) -> TonalPalette:
+        ...
+    def hue(self) -> float:
+        ...
+    def chroma(self) -> float:
+        ...
+    def tone(self, tone: float) -> Color:
+        ...
+    def tones(self) -> list[Color]:
+        ...
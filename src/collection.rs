@@ -1,9 +1,10 @@
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
+use crate::core::delta_e_ok;
 use crate::{
-    AnsiColor, Color, ColorSpace, EmbeddedRgb, Fidelity, Float, GrayGradient, Layer, OkVersion,
-    TerminalColor,
+    Adjustment, AnsiColor, Color, ColorFormatError, ColorSpace, ColorVisionDeficiency, CvdMethod,
+    EmbeddedRgb, Fidelity, Float, GrayGradient, Layer, OkVersion, TerminalColor, ViewingConditions,
 };
 
 // ====================================================================================================================
@@ -248,6 +249,79 @@ impl Theme {
     pub fn entries() -> ThemeEntryIterator {
         ThemeEntryIterator { index: 0 }
     }
+
+    /// Simulate how a person with the color vision deficiency perceives this
+    /// theme.
+    ///
+    /// This method applies [`Color::simulate_cvd`] to every color of this
+    /// theme.
+    pub fn simulate_cvd(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| {
+                self.colors[index].simulate_cvd(deficiency, method, severity)
+            }),
+        }
+    }
+
+    /// Daltonize this theme for a person with the color vision deficiency.
+    ///
+    /// This method applies [`Color::daltonize`] to every color of this theme.
+    pub fn daltonize(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| {
+                self.colors[index].daltonize(deficiency, method, severity)
+            }),
+        }
+    }
+
+    /// Adjust this theme.
+    ///
+    /// This method applies [`Color::adjust`] to every color of this theme.
+    #[pyo3(signature = (adjustment, target=None))]
+    pub fn adjust(&self, adjustment: Adjustment, target: Option<ColorSpace>) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| self.colors[index].adjust(adjustment, target)),
+        }
+    }
+
+    /// Parse a color that may reference this theme's colors.
+    ///
+    /// This method parses the string just like [`Color::parse_with`], while
+    /// resolving references of the form `ansi.<entry>` to this theme's colors.
+    /// The entry is the [`ThemeEntry::name`] in kebab case, e.g., `ansi.blue`,
+    /// `ansi.bright-blue`, or `ansi.foreground`. That makes it possible to
+    /// derive colors from a theme, e.g., `oklch(from ansi.blue l c calc(h +
+    /// 30))` or `color-mix(in oklab, ansi.foreground, ansi.background)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError, DEFAULT_THEME};
+    /// let muted = DEFAULT_THEME.parse("oklch(from ansi.bright-red l calc(c / 2) h)")?;
+    /// assert_eq!(muted.to_hex_format(), "#d17f79");
+    ///
+    /// let error = DEFAULT_THEME.parse("oklch(from ansi.nemo l c h)").unwrap_err();
+    /// assert_eq!(error.found(), "ansi.nemo");
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<Color, ColorFormatError> {
+        Color::parse_with(s, |reference| {
+            let name = reference.strip_prefix("ansi.")?.replace('-', "");
+            Self::entries()
+                .find(|entry| entry.name().eq_ignore_ascii_case(&name))
+                .map(|entry| self[entry].clone())
+        })
+    }
 }
 
 #[cfg(not(feature = "pyffi"))]
@@ -267,6 +341,78 @@ impl Theme {
     pub fn entries() -> ThemeEntryIterator {
         ThemeEntryIterator { index: 0 }
     }
+
+    /// Simulate how a person with the color vision deficiency perceives this
+    /// theme.
+    ///
+    /// This method applies [`Color::simulate_cvd`] to every color of this
+    /// theme.
+    pub fn simulate_cvd(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| {
+                self.colors[index].simulate_cvd(deficiency, method, severity)
+            }),
+        }
+    }
+
+    /// Daltonize this theme for a person with the color vision deficiency.
+    ///
+    /// This method applies [`Color::daltonize`] to every color of this theme.
+    pub fn daltonize(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| {
+                self.colors[index].daltonize(deficiency, method, severity)
+            }),
+        }
+    }
+
+    /// Adjust this theme.
+    ///
+    /// This method applies [`Color::adjust`] to every color of this theme.
+    pub fn adjust(&self, adjustment: Adjustment, target: Option<ColorSpace>) -> Theme {
+        Theme {
+            colors: std::array::from_fn(|index| self.colors[index].adjust(adjustment, target)),
+        }
+    }
+
+    /// Parse a color that may reference this theme's colors.
+    ///
+    /// This method parses the string just like [`Color::parse_with`], while
+    /// resolving references of the form `ansi.<entry>` to this theme's colors.
+    /// The entry is the [`ThemeEntry::name`] in kebab case, e.g., `ansi.blue`,
+    /// `ansi.bright-blue`, or `ansi.foreground`. That makes it possible to
+    /// derive colors from a theme, e.g., `oklch(from ansi.blue l c calc(h +
+    /// 30))` or `color-mix(in oklab, ansi.foreground, ansi.background)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError, DEFAULT_THEME};
+    /// let muted = DEFAULT_THEME.parse("oklch(from ansi.bright-red l calc(c / 2) h)")?;
+    /// assert_eq!(muted.to_hex_format(), "#d17f79");
+    ///
+    /// let error = DEFAULT_THEME.parse("oklch(from ansi.nemo l c h)").unwrap_err();
+    /// assert_eq!(error.found(), "ansi.nemo");
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<Color, ColorFormatError> {
+        Color::parse_with(s, |reference| {
+            let name = reference.strip_prefix("ansi.")?.replace('-', "");
+            Self::entries()
+                .find(|entry| entry.name().eq_ignore_ascii_case(&name))
+                .map(|entry| self[entry].clone())
+        })
+    }
 }
 
 impl<T: Into<ThemeEntry>> std::ops::Index<T> for Theme {
@@ -363,39 +509,63 @@ pub const DEFAULT_THEME: Theme = Theme::new([
 ///     will stick out amongst other translated colors and be visually
 ///     disruptive.
 ///
+/// Since terminals cannot display translucent colors, a sampler composites
+/// translucent colors over the theme's background color before downsampling
+/// them. See [`Sampler::resolve`].
+///
 /// Since a sampler incorporates the color values from a [`Theme`], an
 /// application should regenerate its sampler if the current theme changes.
+///
+/// By default, a sampler matches colors in Oklab or Oklrab, which assume a
+/// fixed viewing condition. Since dark terminal themes in darkened rooms change
+/// how colors appear, [`Sampler::with_conditions`] creates a sampler that
+/// matches colors in CAM16-UCS under the given [`ViewingConditions`] instead.
 #[doc = include_str!("style.html")]
 #[cfg_attr(feature = "pyffi", pyclass)]
 #[derive(Debug)]
 pub struct Sampler {
     theme: Theme,
     space: ColorSpace,
+    conditions: Option<ViewingConditions>,
     ansi: Vec<[Float; 3]>,
     eight_bit: Vec<[Float; 3]>,
 }
 
-/// Create the coordinates for the ANSI colors in the given color space.
-fn ansi_coordinates(space: ColorSpace, theme: &Theme) -> Vec<[Float; 3]> {
+/// Determine the coordinates for matching the color, i.e., the coordinates in
+/// CAM16-UCS under the viewing conditions, if any, or in the color space.
+fn matching_coordinates(
+    space: ColorSpace,
+    conditions: Option<&ViewingConditions>,
+    color: &Color,
+) -> [Float; 3] {
+    match conditions {
+        Some(conditions) => conditions.to_cam16_ucs(*color.to(ColorSpace::Xyz).as_ref()),
+        None => *color.to(space).as_ref(),
+    }
+}
+
+/// Create the coordinates for the ANSI colors in the given color space or
+/// viewing conditions.
+fn ansi_coordinates(
+    space: ColorSpace,
+    conditions: Option<&ViewingConditions>,
+    theme: &Theme,
+) -> Vec<[Float; 3]> {
     (0..=15)
-        .map(|n| *theme[AnsiColor::try_from(n).unwrap()].to(space).as_ref())
+        .map(|n| matching_coordinates(space, conditions, &theme[AnsiColor::try_from(n).unwrap()]))
         .collect()
 }
 
 /// Create the coordinates for the embedded RGB and gray gradient colors in the
-/// given color space.
-fn eight_bit_coordinates(space: ColorSpace) -> Vec<[Float; 3]> {
+/// given color space or viewing conditions.
+fn eight_bit_coordinates(
+    space: ColorSpace,
+    conditions: Option<&ViewingConditions>,
+) -> Vec<[Float; 3]> {
     (16..=231)
-        .map(|n| {
-            *Color::from(EmbeddedRgb::try_from(n).unwrap())
-                .to(space)
-                .as_ref()
-        })
-        .chain((232..=255).map(|n| {
-            *Color::from(GrayGradient::try_from(n).unwrap())
-                .to(space)
-                .as_ref()
-        }))
+        .map(|n| Color::from(EmbeddedRgb::try_from(n).unwrap()))
+        .chain((232..=255).map(|n| Color::from(GrayGradient::try_from(n).unwrap())))
+        .map(|color| matching_coordinates(space, conditions, &color))
         .collect()
 }
 
@@ -405,31 +575,51 @@ impl Sampler {
     #[cfg(feature = "pyffi")]
     #[new]
     pub fn new(theme: &Theme, ok_version: OkVersion) -> Self {
-        let space = ok_version.cartesian_space();
-        let ansi = ansi_coordinates(space, theme);
-        let eight_bit = eight_bit_coordinates(space);
-
-        Self {
-            theme: theme.clone(),
-            space,
-            ansi,
-            eight_bit,
-        }
+        Self::create(theme, ok_version.cartesian_space(), None)
     }
 
     /// Create a new sampler for the given theme and Oklab version.
     #[cfg(not(feature = "pyffi"))]
     pub fn new(theme: &Theme, ok_version: OkVersion) -> Self {
-        let space = ok_version.cartesian_space();
-        let ansi = ansi_coordinates(space, theme);
-        let eight_bit = eight_bit_coordinates(space);
+        Self::create(theme, ok_version.cartesian_space(), None)
+    }
 
-        Self {
-            theme: theme.clone(),
-            space,
-            ansi,
-            eight_bit,
-        }
+    /// Create a new sampler for the given theme and viewing conditions.
+    ///
+    /// The sampler matches colors in CAM16-UCS under the viewing conditions.
+    /// For a terminal with dark theme in a darkened room, use
+    /// [`ViewingConditions::with_surround`] with
+    /// [`Surround::Dark`](crate::Surround::Dark).
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn with_conditions(theme: &Theme, conditions: &ViewingConditions) -> Self {
+        Self::create(theme, ColorSpace::Cam16Ucs, Some(conditions.clone()))
+    }
+
+    /// Create a new sampler for the given theme and viewing conditions.
+    ///
+    /// The sampler matches colors in CAM16-UCS under the viewing conditions.
+    /// For a terminal with dark theme in a darkened room, use
+    /// [`ViewingConditions::with_surround`] with
+    /// [`Surround::Dark`](crate::Surround::Dark).
+    ///
+    /// ```
+    /// # use prettypretty::{AnsiColor, Sampler, Surround, ViewingConditions, DEFAULT_THEME};
+    /// let dark = ViewingConditions::with_surround(Surround::Dark);
+    /// let sampler = Sampler::with_conditions(&DEFAULT_THEME, &dark);
+    /// assert_eq!(sampler.conditions().map(|c| c.surround()), Some(Surround::Dark));
+    ///
+    /// let yellow = &DEFAULT_THEME[AnsiColor::Yellow];
+    /// assert_eq!(sampler.to_closest_ansi(yellow), AnsiColor::Yellow);
+    /// ```
+    #[cfg(not(feature = "pyffi"))]
+    pub fn with_conditions(theme: &Theme, conditions: &ViewingConditions) -> Self {
+        Self::create(theme, ColorSpace::Cam16Ucs, Some(conditions.clone()))
+    }
+
+    /// Get the viewing conditions for matching colors, if any.
+    pub fn conditions(&self) -> Option<ViewingConditions> {
+        self.conditions.clone()
     }
 
     /// Convert the 8-bit index color to a high-resolution color.
@@ -464,8 +654,41 @@ impl Sampler {
         }
     }
 
+    /// Resolve the possibly translucent color against the theme's background.
+    ///
+    /// This method composites a color that is not fully opaque over the
+    /// theme's [`ThemeEntry::Background`] color with [`Color::over`]. It
+    /// returns fully opaque colors unchanged. The methods converting
+    /// high-resolution colors to terminal colors automatically invoke this
+    /// method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError, Sampler, DEFAULT_THEME, OkVersion};
+    /// # use std::str::FromStr;
+    /// let sampler = Sampler::new(&DEFAULT_THEME, OkVersion::Revised);
+    /// let red = Color::from_str("color(srgb 1 0 0 / 0.5)")?;
+    /// let pink = sampler.resolve(&red);
+    /// assert_eq!(pink, Color::srgb(1, 0.5, 0.5));
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 1 0 0 / 0.5);"></div>
+    /// <div style="background-color: color(srgb 1 0.5 0.5);"></div>
+    /// </div>
+    pub fn resolve(&self, color: &Color) -> Color {
+        if color.is_opaque() {
+            color.clone()
+        } else {
+            color.over(&self.theme[ThemeEntry::Background])
+        }
+    }
+
     /// Find the ANSI color that comes closest to the given color.
     ///
+    /// This method resolves translucent colors before matching them.
+    ///
     /// # Examples
     ///
     /// The example code below matches the shades of orange `#ffa563` and
@@ -608,8 +831,8 @@ impl Sampler {
     pub fn to_closest_ansi(&self, color: &Color) -> AnsiColor {
         use crate::core::{delta_e_ok, find_closest};
 
-        let color = color.to(self.space);
-        find_closest(color.as_ref(), &self.ansi, delta_e_ok)
+        let color = self.to_matching_coordinates(&self.resolve(color));
+        find_closest(&color, &self.ansi, delta_e_ok)
             .map(|idx| AnsiColor::try_from(idx as u8).unwrap())
             .unwrap()
     }
@@ -628,8 +851,10 @@ impl Sampler {
     /// significant improvement over the algorithm used by
     /// [Chalk](https://github.com/chalk/chalk/blob/main/source/vendor/ansi-styles/index.js),
     /// which is one of the most popular terminal color libraries for JavaScript.
+    ///
+    /// This method resolves translucent colors before converting them.
     pub fn to_ansi_in_rgb(&self, color: &Color) -> AnsiColor {
-        let color = color.to(ColorSpace::LinearSrgb).clip();
+        let color = self.resolve(color).to(ColorSpace::LinearSrgb).clip();
         let [r, g, b] = color.as_ref();
         let mut index = ((b.round() as u8) << 2) + ((g.round() as u8) << 1) + (r.round() as u8);
         if index >= 2 {
@@ -642,12 +867,13 @@ impl Sampler {
     /// Find the 8-bit color that comes closest to the given color.
     ///
     /// This method does most of the heavy lifting for
-    /// [`Sampler::to_closest_8bit`] but does not wrap the 8-bit index.
+    /// [`Sampler::to_closest_8bit`] but does not wrap the 8-bit index. It
+    /// resolves translucent colors before matching them.
     pub fn to_closest_8bit_raw(&self, color: &Color) -> u8 {
         use crate::core::{delta_e_ok, find_closest};
 
-        let color = color.to(self.space);
-        find_closest(color.as_ref(), &self.eight_bit, delta_e_ok)
+        let color = self.to_matching_coordinates(&self.resolve(color));
+        find_closest(&color, &self.eight_bit, delta_e_ok)
             .map(|idx| idx as u8 + 16)
             .unwrap()
     }
@@ -684,7 +910,7 @@ impl Sampler {
     ///             assert!((color[0] - c1).abs() < Float::EPSILON);
     ///
     ///             let result = sampler.to_closest_8bit(&color);
-    ///             assert_eq!(result, TerminalColor::Rgb6(embedded));
+    ///             assert_eq!(result, TerminalColor::Rgb6 { color: embedded });
     ///         }
     ///     }
     /// }
@@ -734,12 +960,429 @@ impl Sampler {
     }
 }
 
+impl Sampler {
+    /// Create a new sampler that matches colors in the given color space or
+    /// viewing conditions.
+    fn create(theme: &Theme, space: ColorSpace, conditions: Option<ViewingConditions>) -> Self {
+        let ansi = ansi_coordinates(space, conditions.as_ref(), theme);
+        let eight_bit = eight_bit_coordinates(space, conditions.as_ref());
+
+        Self {
+            theme: theme.clone(),
+            space,
+            conditions,
+            ansi,
+            eight_bit,
+        }
+    }
+
+    /// Determine the coordinates for matching the color.
+    fn to_matching_coordinates(&self, color: &Color) -> [Float; 3] {
+        matching_coordinates(self.space, self.conditions.as_ref(), color)
+    }
+}
+
+// ====================================================================================================================
+// Tonal Palettes
+// ====================================================================================================================
+
+/// A tonal palette.
+///
+/// Following Material Design, a tonal palette comprises colors with the same
+/// [HCT](ColorSpace::Hct) hue and chroma but different tones. Since tone is
+/// CIELAB's lightness L*, tones that are far enough apart are guaranteed to
+/// have sufficient contrast, independent of hue and chroma. That makes tonal
+/// palettes a convenient foundation for themes.
+///
+/// Not all combinations of hue, chroma, and tone are in gamut. When generating
+/// a color for a tone, a tonal palette hence reduces the chroma as necessary,
+/// while preserving hue and tone, to produce an in-gamut sRGB color.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, TonalPalette};
+/// let palette = TonalPalette::from_color(&Color::srgb(0.0, 0.4, 0.8));
+/// let colors = palette.tones();
+/// assert_eq!(colors.len(), 13);
+///
+/// let dark = palette.tone(30.0);
+/// assert_eq!(dark.space(), ColorSpace::Srgb);
+/// assert!(dark.in_gamut());
+///
+/// let [h, _, t] = *dark.to(ColorSpace::Hct).as_ref();
+/// assert!((h - palette.hue()).abs() < 0.01);
+/// assert!((t - 30.0).abs() < 0.01);
+/// ```
+/// <div class=color-swatch>
+/// <div style="background-color: color(srgb 0 0.4 0.8);"></div>
+/// </div>
+#[doc = include_str!("style.html")]
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TonalPalette {
+    hue: Float,
+    chroma: Float,
+}
+
+impl TonalPalette {
+    /// The tones of Material Design's tonal palettes.
+    pub const TONES: [Float; 13] = [
+        0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 95.0, 99.0, 100.0,
+    ];
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl TonalPalette {
+    /// Create a new tonal palette with the given HCT hue and chroma.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(hue: Float, chroma: Float) -> Self {
+        Self { hue, chroma }
+    }
+
+    /// Create a new tonal palette with the given HCT hue and chroma.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(hue: Float, chroma: Float) -> Self {
+        Self { hue, chroma }
+    }
+
+    /// Create a new tonal palette with the HCT hue and chroma of the given key
+    /// color.
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn from_color(color: &Color) -> Self {
+        let [hue, chroma, _] = *color.to(ColorSpace::Hct).as_ref();
+        Self { hue, chroma }
+    }
+
+    /// Create a new tonal palette with the HCT hue and chroma of the given key
+    /// color.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn from_color(color: &Color) -> Self {
+        let [hue, chroma, _] = *color.to(ColorSpace::Hct).as_ref();
+        Self { hue, chroma }
+    }
+
+    /// Get the HCT hue.
+    pub fn hue(&self) -> Float {
+        self.hue
+    }
+
+    /// Get the HCT chroma.
+    pub fn chroma(&self) -> Float {
+        self.chroma
+    }
+
+    /// Get the in-gamut sRGB color with the given tone.
+    ///
+    /// This method reduces chroma as necessary, while preserving hue and tone.
+    pub fn tone(&self, tone: Float) -> Color {
+        Color::hct(self.hue, self.chroma, tone)
+            .to(ColorSpace::Srgb)
+            .to_gamut_hct()
+    }
+
+    /// Get the in-gamut sRGB colors for Material Design's tones.
+    ///
+    /// The tones are 0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99, and 100,
+    /// i.e., [`TonalPalette::TONES`].
+    pub fn tones(&self) -> Vec<Color> {
+        Self::TONES.iter().map(|tone| self.tone(*tone)).collect()
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A generator of categorical palettes.
+///
+/// A categorical palette comprises accent colors that are as easy to tell
+/// apart as possible, e.g., for distinguishing log sources or authors. This
+/// generator picks the requested number of in-gamut colors that maximize the
+/// minimum ΔE OK between any two of them. It samples candidates on a grid of
+/// lightness, chroma, and hue in Oklch or Oklrch, maps them into the target
+/// color space's gamut, and then greedily picks the candidate farthest from
+/// all colors picked so far, before improving on the greedy choice by
+/// swapping individual colors.
+///
+/// The generator optionally restricts candidates to a lightness band and to
+/// colors with sufficient contrast against a background. It also keeps
+/// generated colors away from colors to avoid, e.g., the colors of a
+/// [`Theme`].
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::{CategoricalPalette, Color, ColorSpace, DeltaE, OkVersion};
+/// let mut generator = CategoricalPalette::new(ColorSpace::Srgb, OkVersion::Revised);
+/// generator.set_lightness(0.5, 0.8);
+/// generator.set_contrast(&Color::srgb(1.0, 1.0, 1.0), 0.3);
+///
+/// let colors = generator.generate(6);
+/// assert_eq!(colors.len(), 6);
+/// for (index, color) in colors.iter().enumerate() {
+///     assert!(color.in_gamut());
+///     for other in colors[index + 1..].iter() {
+///         assert!(color.distance(other, DeltaE::Ok(OkVersion::Revised)) > 0.15);
+///     }
+/// }
+/// ```
+#[doc = include_str!("style.html")]
+#[cfg_attr(feature = "pyffi", pyclass)]
+#[derive(Clone, Debug)]
+pub struct CategoricalPalette {
+    space: ColorSpace,
+    version: OkVersion,
+    lightness: (Float, Float),
+    contrast: Option<(Color, Float)>,
+    avoided: Vec<[Float; 3]>,
+}
+
+impl CategoricalPalette {
+    /// The number of lightness levels sampled for candidates.
+    const LIGHTNESS_STEPS: usize = 10;
+
+    /// The chroma levels sampled for candidates.
+    const CHROMAS: [Float; 9] = [0.04, 0.08, 0.12, 0.16, 0.2, 0.24, 0.28, 0.32, 0.36];
+
+    /// The hue increment between candidates.
+    const HUE_STEP: Float = 10.0;
+
+    /// The largest palette improved upon by swapping colors.
+    const MAX_REFINED: usize = 32;
+
+    /// Create the in-gamut candidates meeting the lightness and contrast
+    /// constraints. Each candidate is paired with its Oklab/Oklrab coordinates.
+    fn candidates(&self) -> Vec<(Color, [Float; 3])> {
+        let (min, max) = self.lightness;
+        let polar = self.version.polar_space();
+        let cartesian = self.version.cartesian_space();
+        let mut candidates = Vec::new();
+
+        for step in 0..=Self::LIGHTNESS_STEPS {
+            let lightness = min + (max - min) * step as Float / Self::LIGHTNESS_STEPS as Float;
+            for chroma in Self::CHROMAS {
+                let mut hue = 0.0;
+                while hue < 360.0 {
+                    let color = Color::new(polar, [lightness, chroma, hue])
+                        .to(self.space)
+                        .to_gamut();
+                    hue += Self::HUE_STEP;
+
+                    let coordinates = *color.to(polar).as_ref();
+                    if coordinates[0] < min - 0.001 || max + 0.001 < coordinates[0] {
+                        continue;
+                    }
+                    if let Some((ref background, contrast)) = self.contrast {
+                        if color.contrast_against(background).abs() < contrast {
+                            continue;
+                        }
+                    }
+
+                    let coordinates = *color.to(cartesian).as_ref();
+                    candidates.push((color, coordinates));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// Determine the smallest ΔE OK between the coordinates and the chosen as
+    /// well as avoided coordinates, skipping the chosen coordinates with the
+    /// given index.
+    fn nearest(&self, coordinates: &[Float; 3], chosen: &[[Float; 3]], skip: usize) -> Float {
+        chosen
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != skip)
+            .map(|(_, other)| other)
+            .chain(self.avoided.iter())
+            .map(|other| delta_e_ok(coordinates, other))
+            .fold(Float::INFINITY, Float::min)
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl CategoricalPalette {
+    /// Create a new generator for colors in the given color space.
+    ///
+    /// The color space should be an RGB color space, since its gamut bounds
+    /// the generated colors. The Oklab version determines the color space for
+    /// lightness and ΔE OK.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(space: ColorSpace, version: OkVersion) -> Self {
+        Self {
+            space,
+            version,
+            lightness: (0.0, 1.0),
+            contrast: None,
+            avoided: Vec::new(),
+        }
+    }
+
+    /// Create a new generator for colors in the given color space.
+    ///
+    /// The color space should be an RGB color space, since its gamut bounds
+    /// the generated colors. The Oklab version determines the color space for
+    /// lightness and ΔE OK.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(space: ColorSpace, version: OkVersion) -> Self {
+        Self {
+            space,
+            version,
+            lightness: (0.0, 1.0),
+            contrast: None,
+            avoided: Vec::new(),
+        }
+    }
+
+    /// Restrict generated colors to the given band of (revised) lightness.
+    ///
+    /// The bounds are clamped to `0..=1` and swapped if necessary.
+    pub fn set_lightness(&mut self, min: Float, max: Float) {
+        let min = min.clamp(0.0, 1.0);
+        let max = max.clamp(0.0, 1.0);
+        self.lightness = if min <= max { (min, max) } else { (max, min) };
+    }
+
+    /// Restrict generated colors to those with at least the given contrast
+    /// against the background.
+    ///
+    /// This method uses the magnitude of [`Color::contrast_against`], i.e., of
+    /// the generated color as text against the background. Useful thresholds
+    /// are 0.3 for large text and 0.45 for body text.
+    pub fn set_contrast(&mut self, background: &Color, contrast: Float) {
+        self.contrast = Some((background.clone(), contrast));
+    }
+
+    /// Keep generated colors away from the given color.
+    pub fn avoid(&mut self, color: &Color) {
+        self.avoided
+            .push(*color.to(self.version.cartesian_space()).as_ref());
+    }
+
+    /// Keep generated colors away from the colors of the given theme.
+    pub fn avoid_theme(&mut self, theme: &Theme) {
+        for color in theme.colors.iter() {
+            self.avoid(color);
+        }
+    }
+
+    /// Generate a categorical palette with the given number of colors.
+    ///
+    /// The generated colors are in gamut for this generator's color space and
+    /// sorted by hue. If the constraints admit fewer distinct colors than
+    /// requested, this method returns fewer colors.
+    pub fn generate(&self, count: usize) -> Vec<Color> {
+        let candidates = self.candidates();
+        if candidates.is_empty() || count == 0 {
+            return Vec::new();
+        }
+
+        // Greedily pick the candidate farthest from all colors so far, starting
+        // with the most chromatic candidate when there are no colors to avoid.
+        let mut nearest: Vec<Float> = candidates
+            .iter()
+            .map(|(_, coordinates)| self.nearest(coordinates, &[], usize::MAX))
+            .collect();
+        let mut chosen: Vec<usize> = Vec::with_capacity(count);
+
+        while chosen.len() < count {
+            let best = if chosen.is_empty() && self.avoided.is_empty() {
+                let chroma = |index: usize| {
+                    let [_, a, b] = candidates[index].1;
+                    a.hypot(b)
+                };
+                (0..candidates.len())
+                    .max_by(|i1, i2| chroma(*i1).total_cmp(&chroma(*i2)))
+                    .unwrap()
+            } else {
+                (0..candidates.len())
+                    .max_by(|i1, i2| nearest[*i1].total_cmp(&nearest[*i2]))
+                    .unwrap()
+            };
+
+            if nearest[best] <= 0.0 {
+                break;
+            }
+
+            chosen.push(best);
+            let picked = candidates[best].1;
+            for (index, (_, coordinates)) in candidates.iter().enumerate() {
+                nearest[index] = nearest[index].min(delta_e_ok(coordinates, &picked));
+            }
+        }
+
+        // Improve on the greedy choice by swapping colors for candidates
+        // farther from the remaining colors. Since each round is quadratic in
+        // the number of colors, only do so for reasonably small palettes.
+        let rounds = if chosen.len() <= Self::MAX_REFINED {
+            10
+        } else {
+            0
+        };
+        for _ in 0..rounds {
+            let mut improved = false;
+
+            for slot in 0..chosen.len() {
+                let coordinates: Vec<[Float; 3]> =
+                    chosen.iter().map(|index| candidates[*index].1).collect();
+                let current = self.nearest(&coordinates[slot], &coordinates, slot);
+
+                let (best, distance) = candidates
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (_, c))| (index, self.nearest(c, &coordinates, slot)))
+                    .max_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+                    .unwrap();
+
+                if current + 1e-9 < distance {
+                    chosen[slot] = best;
+                    improved = true;
+                }
+            }
+
+            if !improved {
+                break;
+            }
+        }
+
+        let polar = self.version.polar_space();
+        let hue = |color: &Color| {
+            let h = color.to(polar)[2];
+            if h.is_nan() {
+                0.0
+            } else {
+                h
+            }
+        };
+
+        let mut colors: Vec<Color> = chosen
+            .into_iter()
+            .map(|index| candidates[index].0.clone())
+            .collect();
+        colors.sort_by(|c1, c2| hue(c1).total_cmp(&hue(c2)));
+        colors
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
 // ====================================================================================================================
 
 #[cfg(test)]
 mod test {
-    use super::{Sampler, DEFAULT_THEME};
-    use crate::{AnsiColor, Color, OkVersion, OutOfBoundsError};
+    use super::{CategoricalPalette, Sampler, Theme, DEFAULT_THEME};
+    use crate::{
+        Adjustment, AnsiColor, Color, ColorSpace, ColorVisionDeficiency, CvdMethod, DeltaE, Layer,
+        OkVersion, OutOfBoundsError, Surround, ViewingConditions,
+    };
 
     #[test]
     fn test_sampler() -> Result<(), OutOfBoundsError> {
@@ -748,6 +1391,98 @@ mod test {
         let result = sampler.to_closest_ansi(&Color::srgb(1.0, 1.0, 0.0));
         assert_eq!(result, AnsiColor::BrightYellow);
 
+        // A fully transparent color resolves to the theme's background.
+        let result = sampler.to_closest_ansi(&Color::srgb(0.0, 0.0, 1.0).with_alpha(0.0));
+        assert_eq!(result, AnsiColor::BrightWhite);
+
+        // Under dark surround viewing conditions, terminal colors still are
+        // closest to themselves.
+        let dark = ViewingConditions::with_surround(Surround::Dark);
+        let sampler = Sampler::with_conditions(&DEFAULT_THEME, &dark);
+        for index in 0..=15 {
+            let ansi = AnsiColor::try_from(index)?;
+            assert_eq!(sampler.to_closest_ansi(&DEFAULT_THEME[ansi]), ansi);
+        }
+        for index in 16..=255 {
+            let color = sampler.to_high_res_8bit(index);
+            assert_eq!(sampler.to_closest_8bit_raw(&color), index);
+        }
+
         Ok(())
     }
+
+    #[test]
+    fn test_theme_cvd() {
+        let deficiency = ColorVisionDeficiency::Deuteranopia;
+        let metric = DeltaE::Ok(OkVersion::Revised);
+        let red = &DEFAULT_THEME[AnsiColor::Red];
+        let green = &DEFAULT_THEME[AnsiColor::Green];
+
+        for method in [CvdMethod::Machado, CvdMethod::Brettel] {
+            let simulated = DEFAULT_THEME.simulate_cvd(deficiency, method, 1.0);
+            assert_eq!(simulated[Layer::Foreground].to_24bit(), [0, 0, 0]);
+            assert_eq!(simulated[Layer::Background].to_24bit(), [255, 255, 255]);
+
+            let simulated_red = &simulated[AnsiColor::Red];
+            let simulated_green = &simulated[AnsiColor::Green];
+            assert!(simulated_red.distance(simulated_green, metric) < red.distance(green, metric));
+
+            // Daltonization leaves neutral colors alone but visibly shifts red.
+            let daltonized = DEFAULT_THEME.daltonize(deficiency, method, 1.0);
+            assert_eq!(daltonized[Layer::Foreground].to_24bit(), [0, 0, 0]);
+            assert_eq!(daltonized[Layer::Background].to_24bit(), [255, 255, 255]);
+
+            let daltonized_red = daltonized[AnsiColor::Red].simulate_cvd(deficiency, method, 1.0);
+            assert!(daltonized_red.distance(simulated_red, metric) > 0.05);
+        }
+    }
+
+    #[test]
+    fn test_theme_adjust() {
+        let gray = DEFAULT_THEME.adjust(Adjustment::Grayscale(), Some(ColorSpace::Srgb));
+        for entry in Theme::entries() {
+            let color = &gray[entry];
+            assert_eq!(color.space(), ColorSpace::Srgb);
+            assert!(color.to(ColorSpace::Oklrch)[1] < 1e-4);
+
+            let [r, g, b] = color.to_24bit();
+            assert!(r == g && g == b);
+        }
+
+        let rotated = DEFAULT_THEME.adjust(Adjustment::RotateHue { degrees: 360.0 }, None);
+        assert_eq!(rotated[AnsiColor::Red].space(), ColorSpace::Oklrch);
+        assert_eq!(
+            rotated[AnsiColor::Red].to(ColorSpace::Srgb).to_24bit(),
+            DEFAULT_THEME[AnsiColor::Red].to_24bit()
+        );
+    }
+
+    #[test]
+    fn test_categorical_palette() {
+        let metric = DeltaE::Ok(OkVersion::Revised);
+        let mut generator = CategoricalPalette::new(ColorSpace::Srgb, OkVersion::Revised);
+        generator.set_lightness(0.6, 0.75);
+        generator.avoid_theme(&DEFAULT_THEME);
+
+        let colors = generator.generate(5);
+        assert_eq!(colors.len(), 5);
+
+        for (index, color) in colors.iter().enumerate() {
+            assert_eq!(color.space(), ColorSpace::Srgb);
+            assert!(color.in_gamut());
+
+            let lightness = color.to(ColorSpace::Oklrch)[0];
+            assert!((0.599..=0.751).contains(&lightness));
+
+            for other in colors[index + 1..].iter() {
+                assert!(color.distance(other, metric) > 0.15);
+            }
+            for ansi in 0..16 {
+                let ansi = &DEFAULT_THEME[AnsiColor::try_from(ansi).unwrap()];
+                assert!(color.distance(ansi, metric) > 0.15);
+            }
+        }
+
+        assert!(generator.generate(0).is_empty());
+    }
 }
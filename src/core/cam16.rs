@@ -0,0 +1,499 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use std::sync::OnceLock;
+
+use crate::core::conversion::multiply;
+use crate::Float;
+
+/// The surround of a viewing condition.
+///
+/// The surround describes the luminance of the area surrounding the viewing
+/// field relative to the white of the scene. Colors appear less colorful and
+/// lower in contrast in a dim or dark surround.
+///
+///   * `Average` is the surround for viewing surface colors, such as printed
+///     material under office lighting.
+///   * `Dim` is the surround for watching television in a dimly lit room.
+///   * `Dark` is the surround for watching film in a darkened cinema or, for
+///     that matter, a terminal with a dark theme in a darkened room.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Surround {
+    Average,
+    Dim,
+    Dark,
+}
+
+impl Surround {
+    /// Get the factor F, the impact of surround c, and the chromatic induction
+    /// factor N<sub>c</sub> for this surround.
+    const fn parameters(&self) -> [Float; 3] {
+        match *self {
+            Self::Average => [1.0, 0.69, 1.0],
+            Self::Dim => [0.9, 0.59, 0.9],
+            Self::Dark => [0.8, 0.525, 0.8],
+        }
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The correlates of the CAM16 color appearance model.
+///
+/// CAM16 describes the appearance of a color through six correlates. The
+/// lightness J, chroma C, and saturation s are relative to the white of the
+/// viewing conditions, whereas the brightness Q and colorfulness M are absolute
+/// and hence grow with the adapting luminance.
+#[cfg_attr(feature = "pyffi", pyclass(frozen, get_all))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cam16 {
+    /// The lightness J, which ranges `0..=100`.
+    pub lightness: Float,
+    /// The chroma C.
+    pub chroma: Float,
+    /// The hue angle h in degrees.
+    pub hue: Float,
+    /// The colorfulness M.
+    pub colorfulness: Float,
+    /// The saturation s.
+    pub saturation: Float,
+    /// The brightness Q.
+    pub brightness: Float,
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+#[rustfmt::skip]
+const CAT16: [[Float; 3]; 3] = [
+    [  0.401288,  0.650173, -0.051461 ],
+    [ -0.250268,  1.204414,  0.045854 ],
+    [ -0.002079,  0.048952,  0.953127 ],
+];
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const CAT16_INVERSE: [[Float; 3]; 3] = [
+    [  1.8620678550872327,    -1.0112546305316843,    0.14918677544445175  ],
+    [  0.3875265432361371,     0.6214474419314753,   -0.00897398516761252  ],
+    [ -0.015841498849333856,  -0.03412293802851556,   1.0499644368778493   ],
+];
+
+#[rustfmt::skip]
+const OPPONENT_TO_RGB: [[Float; 3]; 3] = [
+    [ 460.0,  451.0,   288.0 ],
+    [ 460.0, -891.0,  -261.0 ],
+    [ 460.0, -220.0, -6300.0 ],
+];
+
+/// The D65 white point, with Y = 1.
+const D65: [Float; 3] = [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290];
+
+/// The luminance Y of CIELAB lightness L* = 50, i.e., medium gray.
+const MEDIUM_GRAY: Float = 0.18418651851244416;
+
+const ADAPTED_EXPONENT: Float = 0.42;
+
+/// Apply the post-adaptation non-linear response compression.
+fn adapt(coordinates: &[Float; 3], fl: Float) -> [Float; 3] {
+    coordinates.map(|c| {
+        let x = (fl * c.abs() * 0.01).powf(ADAPTED_EXPONENT);
+        (400.0 * x / (x + 27.13)).copysign(c)
+    })
+}
+
+/// Undo the post-adaptation non-linear response compression.
+fn unadapt(coordinates: &[Float; 3], fl: Float) -> [Float; 3] {
+    let factor = 100.0 / fl * (27.13 as Float).powf(ADAPTED_EXPONENT.recip());
+    coordinates.map(|c| {
+        let magnitude = c.abs();
+        (factor * (magnitude / (400.0 - magnitude)).powf(ADAPTED_EXPONENT.recip())).copysign(c)
+    })
+}
+
+/// The viewing conditions for the CAM16 color appearance model.
+///
+/// Unlike Oklab and CIELAB, which assume a fixed viewing condition, CAM16
+/// accounts for the white point, the luminance of the adapting field, the
+/// luminance of the background, and the surround. Upon creation, this struct
+/// computes all values derived from the viewing condition, so that
+/// conversions only incur the per-color overhead.
+///
+/// The default viewing condition is the same as used by Material Design's HCT
+/// color space: the D65 white point, an adapting luminance of 200/π times the
+/// luminance of medium gray, a background of medium gray, and an average
+/// surround. [`ColorSpace::Cam16Ucs`](crate::ColorSpace::Cam16Ucs) uses the
+/// default viewing condition.
+///
+/// # Examples
+///
+/// The example below illustrates that red appears less colorful in a dark
+/// surround.
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, Surround, ViewingConditions};
+/// let average = ViewingConditions::default();
+/// let dark = ViewingConditions::with_surround(Surround::Dark);
+///
+/// let red = Color::srgb(1, 0, 0);
+/// let red_average = red.to_cam16(&average);
+/// let red_dark = red.to_cam16(&dark);
+///
+/// assert!((red_average.hue - 27.41).abs() < 0.005);
+/// assert!((red_average.chroma - 113.36).abs() < 0.005);
+/// assert!(red_dark.colorfulness < red_average.colorfulness);
+/// ```
+/// <div class=color-swatch>
+/// <div style="background-color: color(srgb 1 0 0);"></div>
+/// </div>
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Clone, Debug)]
+pub struct ViewingConditions {
+    white_point: [Float; 3],
+    adapting_luminance: Float,
+    background_luminance: Float,
+    surround: Surround,
+    discounting: bool,
+    n: Float,
+    z: Float,
+    fl: Float,
+    fl_root: Float,
+    nbb: Float,
+    c: Float,
+    nc: Float,
+    d_rgb: [Float; 3],
+    a_w: Float,
+}
+
+impl ViewingConditions {
+    fn create(
+        white_point: [Float; 3],
+        adapting_luminance: Float,
+        background_luminance: Float,
+        surround: Surround,
+        discounting: bool,
+    ) -> Self {
+        let xyz_w = white_point.map(|c| c * 100.0);
+        let y_w = xyz_w[1];
+        let rgb_w = multiply(&CAT16, &xyz_w);
+
+        let [f, c, nc] = surround.parameters();
+        let la = adapting_luminance;
+        let k = (5.0 * la + 1.0).recip();
+        let k4 = k.powi(4);
+        let fl = k4 * la + 0.1 * (1.0 - k4) * (1.0 - k4) * (5.0 * la).cbrt();
+        let n = background_luminance / y_w;
+        let z = 1.48 + n.sqrt();
+        let nbb = 0.725 * n.powf(-0.2);
+
+        let d = if discounting {
+            1.0
+        } else {
+            (f * (1.0 - (1.0 / 3.6) * ((-la - 42.0) / 92.0).exp())).clamp(0.0, 1.0)
+        };
+        let d_rgb = rgb_w.map(|c| d * (y_w / c) + 1.0 - d);
+
+        let rgb_cw = [0, 1, 2].map(|i| rgb_w[i] * d_rgb[i]);
+        let [r_aw, g_aw, b_aw] = adapt(&rgb_cw, fl);
+        let a_w = nbb * (2.0 * r_aw + g_aw + 0.05 * b_aw);
+
+        Self {
+            white_point,
+            adapting_luminance,
+            background_luminance,
+            surround,
+            discounting,
+            n,
+            z,
+            fl,
+            fl_root: fl.powf(0.25),
+            nbb,
+            c,
+            nc,
+            d_rgb,
+            a_w,
+        }
+    }
+
+    /// Convert the XYZ coordinates to the CAM16 correlates.
+    pub(crate) fn xyz_to_cam16(&self, coordinates: &[Float; 3]) -> Cam16 {
+        let xyz = coordinates.map(|c| c * 100.0);
+        let rgb = multiply(&CAT16, &xyz);
+        let [r, g, b] = adapt(&[0, 1, 2].map(|i| rgb[i] * self.d_rgb[i]), self.fl);
+
+        let a = r + (-12.0 * g + b) / 11.0;
+        let bb = (r + g - 2.0 * b) / 9.0;
+        let h_rad = bb.atan2(a).rem_euclid(std::f64::consts::TAU as Float);
+
+        let et = 0.25 * ((h_rad + 2.0).cos() + 3.8);
+        let denominator = r + g + 1.05 * b + 0.305;
+        let t = if denominator == 0.0 {
+            0.0
+        } else {
+            50_000.0 / 13.0 * self.nc * self.nbb * et * a.hypot(bb) / denominator
+        };
+        let alpha = t.powf(0.9) * (1.64 - (0.29 as Float).powf(self.n)).powf(0.73);
+
+        let achromatic = self.nbb * (2.0 * r + g + 0.05 * b);
+        let j_root = (achromatic / self.a_w).max(0.0).powf(0.5 * self.c * self.z);
+
+        let lightness = 100.0 * j_root * j_root;
+        let brightness = 4.0 / self.c * j_root * (self.a_w + 4.0) * self.fl_root;
+        let chroma = alpha * j_root;
+        let colorfulness = chroma * self.fl_root;
+        let saturation = 50.0 * (self.c * alpha / (self.a_w + 4.0)).sqrt();
+
+        Cam16 {
+            lightness,
+            chroma,
+            hue: h_rad.to_degrees(),
+            colorfulness,
+            saturation,
+            brightness,
+        }
+    }
+
+    /// Convert lightness J, chroma C, and hue h to XYZ.
+    pub(crate) fn jch_to_xyz(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        let [lightness, chroma, hue] = *coordinates;
+        if lightness <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+
+        let h_rad = hue.to_radians();
+        let (sin_h, cos_h) = h_rad.sin_cos();
+
+        let j_root = lightness.sqrt() * 0.1;
+        let alpha = chroma / j_root;
+        let t = (alpha * (1.64 - (0.29 as Float).powf(self.n)).powf(-0.73))
+            .max(0.0)
+            .powf(10.0 / 9.0);
+
+        let et = 0.25 * ((h_rad + 2.0).cos() + 3.8);
+        let achromatic = self.a_w * j_root.powf(2.0 / self.c / self.z);
+        let p1 = 50_000.0 / 13.0 * self.nc * self.nbb * et;
+        let p2 = achromatic / self.nbb;
+
+        let denominator = 23.0 * p1 + t * (11.0 * cos_h + 108.0 * sin_h);
+        let gamma = if denominator == 0.0 {
+            0.0
+        } else {
+            23.0 * (p2 + 0.305) * t / denominator
+        };
+
+        let rgb = multiply(&OPPONENT_TO_RGB, &[p2, gamma * cos_h, gamma * sin_h]);
+        let rgb = unadapt(&rgb.map(|c| c / 1403.0), self.fl);
+        let rgb = [0, 1, 2].map(|i| rgb[i] / self.d_rgb[i]);
+
+        multiply(&CAT16_INVERSE, &rgb).map(|c| c / 100.0)
+    }
+
+    /// Convert XYZ to CAM16-UCS.
+    pub(crate) fn xyz_to_cam16_ucs(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        let cam16 = self.xyz_to_cam16(coordinates);
+        let lightness = 1.7 * cam16.lightness / (1.0 + 0.007 * cam16.lightness);
+        let colorfulness = (0.0228 * cam16.colorfulness).ln_1p() / 0.0228;
+        let (sin_h, cos_h) = cam16.hue.to_radians().sin_cos();
+
+        [lightness, colorfulness * cos_h, colorfulness * sin_h]
+    }
+
+    /// Convert CAM16-UCS to XYZ.
+    pub(crate) fn cam16_ucs_to_xyz(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        let [lightness, a, b] = *coordinates;
+        let lightness = lightness / (1.7 - 0.007 * lightness);
+        let colorfulness = (a.hypot(b) * 0.0228).exp_m1() / 0.0228;
+        let hue = b.atan2(a).to_degrees();
+
+        self.jch_to_xyz(&[lightness, colorfulness / self.fl_root, hue])
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl ViewingConditions {
+    /// Create new viewing conditions.
+    ///
+    /// The white point is given as XYZ coordinates with Y = 1. The adapting
+    /// luminance is the luminance of the adapting field in cd/m², typically
+    /// 20% of the white's luminance. The background luminance is the relative
+    /// luminance of the background, with the white's luminance being 100. If
+    /// discounting is enabled, the observer is assumed to fully adapt to the
+    /// white point.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(
+        white_point: [Float; 3],
+        adapting_luminance: Float,
+        background_luminance: Float,
+        surround: Surround,
+        discounting: bool,
+    ) -> Self {
+        Self::create(
+            white_point,
+            adapting_luminance,
+            background_luminance,
+            surround,
+            discounting,
+        )
+    }
+
+    /// Create new viewing conditions.
+    ///
+    /// The white point is given as XYZ coordinates with Y = 1. The adapting
+    /// luminance is the luminance of the adapting field in cd/m², typically
+    /// 20% of the white's luminance. The background luminance is the relative
+    /// luminance of the background, with the white's luminance being 100. If
+    /// discounting is enabled, the observer is assumed to fully adapt to the
+    /// white point.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(
+        white_point: [Float; 3],
+        adapting_luminance: Float,
+        background_luminance: Float,
+        surround: Surround,
+        discounting: bool,
+    ) -> Self {
+        Self::create(
+            white_point,
+            adapting_luminance,
+            background_luminance,
+            surround,
+            discounting,
+        )
+    }
+
+    /// Create the default viewing conditions with the given surround.
+    ///
+    /// Passing [`Surround::Dark`] yields a viewing condition suitable for a
+    /// terminal with dark background in a darkened room.
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn with_surround(surround: Surround) -> Self {
+        Self::create(
+            D65,
+            200.0 / std::f64::consts::PI as Float * MEDIUM_GRAY,
+            MEDIUM_GRAY * 100.0,
+            surround,
+            false,
+        )
+    }
+
+    /// Create the default viewing conditions with the given surround.
+    ///
+    /// Passing [`Surround::Dark`] yields a viewing condition suitable for a
+    /// terminal with dark background in a darkened room.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn with_surround(surround: Surround) -> Self {
+        Self::create(
+            D65,
+            200.0 / std::f64::consts::PI as Float * MEDIUM_GRAY,
+            MEDIUM_GRAY * 100.0,
+            surround,
+            false,
+        )
+    }
+
+    /// Get the white point as XYZ coordinates.
+    pub fn white_point(&self) -> [Float; 3] {
+        self.white_point
+    }
+
+    /// Get the adapting luminance.
+    pub fn adapting_luminance(&self) -> Float {
+        self.adapting_luminance
+    }
+
+    /// Get the background luminance.
+    pub fn background_luminance(&self) -> Float {
+        self.background_luminance
+    }
+
+    /// Get the surround.
+    pub fn surround(&self) -> Surround {
+        self.surround
+    }
+
+    /// Determine whether the observer discounts the illuminant.
+    pub fn discounting(&self) -> bool {
+        self.discounting
+    }
+
+    /// Convert the XYZ coordinates to the CAM16 correlates.
+    pub fn to_cam16(&self, coordinates: [Float; 3]) -> Cam16 {
+        self.xyz_to_cam16(&coordinates)
+    }
+
+    /// Convert the CAM16 lightness J, colorfulness M, and hue h to XYZ.
+    pub fn from_cam16(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        let [lightness, colorfulness, hue] = coordinates;
+        self.jch_to_xyz(&[lightness, colorfulness / self.fl_root, hue])
+    }
+
+    /// Convert the XYZ coordinates to CAM16-UCS J', a', b'.
+    pub fn to_cam16_ucs(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        self.xyz_to_cam16_ucs(&coordinates)
+    }
+
+    /// Convert the CAM16-UCS coordinates J', a', b' to XYZ.
+    pub fn from_cam16_ucs(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        self.cam16_ucs_to_xyz(&coordinates)
+    }
+}
+
+impl Default for ViewingConditions {
+    /// Create the default viewing conditions, which use an average surround.
+    fn default() -> Self {
+        Self::with_surround(Surround::Average)
+    }
+}
+
+/// Get the default viewing conditions used by
+/// [`ColorSpace::Cam16Ucs`](crate::ColorSpace::Cam16Ucs).
+pub(crate) fn default_conditions() -> &'static ViewingConditions {
+    static CONDITIONS: OnceLock<ViewingConditions> = OnceLock::new();
+    CONDITIONS.get_or_init(ViewingConditions::default)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{default_conditions, Surround, ViewingConditions};
+    use crate::core::{convert, ColorSpace};
+
+    #[test]
+    fn test_cam16() {
+        // Material Design's reference values for sRGB red. Material Design uses
+        // a slightly different white point and hence the values differ in the
+        // third decimal.
+        let red = convert(ColorSpace::Srgb, ColorSpace::Xyz, &[1.0, 0.0, 0.0]);
+        let cam16 = default_conditions().xyz_to_cam16(&red);
+
+        assert!((cam16.lightness - 46.445).abs() < 0.01);
+        assert!((cam16.chroma - 113.358).abs() < 0.01);
+        assert!((cam16.hue - 27.408).abs() < 0.01);
+        assert!((cam16.colorfulness - 89.494).abs() < 0.01);
+        assert!((cam16.saturation - 91.890).abs() < 0.01);
+        assert!((cam16.brightness - 105.989).abs() < 0.01);
+
+        // Round-trip through JCh and CAM16-UCS for several viewing conditions.
+        for surround in [Surround::Average, Surround::Dim, Surround::Dark] {
+            let conditions = ViewingConditions::with_surround(surround);
+
+            for srgb in [[1.0, 0.5, 0.0], [0.2, 0.4, 0.6], [0.9, 0.9, 0.9]] {
+                let xyz = convert(ColorSpace::Srgb, ColorSpace::Xyz, &srgb);
+
+                let cam16 = conditions.xyz_to_cam16(&xyz);
+                let jch = [cam16.lightness, cam16.chroma, cam16.hue];
+                let ucs = conditions.xyz_to_cam16_ucs(&xyz);
+
+                for actual in [
+                    conditions.jch_to_xyz(&jch),
+                    conditions.cam16_ucs_to_xyz(&ucs),
+                ] {
+                    for (a, e) in actual.iter().zip(xyz.iter()) {
+                        assert!((a - e).abs() < 1e-10, "{:?} vs {:?}", actual, xyz);
+                    }
+                }
+            }
+        }
+    }
+}
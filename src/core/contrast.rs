@@ -21,6 +21,7 @@ const SRGB_CONTRAST: &[Float; 3] = &[0.2126729, 0.7151522, 0.0721750];
 #[allow(clippy::excessive_precision)]
 const P3_CONTRAST: &[Float; 3] = &[0.2289829594805780, 0.6917492625852380, 0.0792677779341829];
 
+/// Compute the contrast luminance for the given coefficients and coordinates.
 fn to_contrast_luminance(coefficients: &[Float; 3], coordinates: &[Float; 3]) -> Float {
     fn linearize(value: Float) -> Float {
         let magnitude = value.abs();
@@ -1,3 +1,5 @@
+use super::cam16::default_conditions;
+use super::hct::{hct_to_xyz, xyz_to_hct};
 use super::{normalize, ColorSpace};
 use crate::Float;
 
@@ -26,7 +28,7 @@ pub(crate) fn to_24bit(space: ColorSpace, coordinates: &[Float; 3]) -> [u8; 3] {
 /// Multiply the 3 by 3 matrix and 3-element vector with each other, producing a
 /// new 3-element vector.
 #[inline]
-fn multiply(matrix: &[[Float; 3]; 3], vector: &[Float; 3]) -> [Float; 3] {
+pub(crate) fn multiply(matrix: &[[Float; 3]; 3], vector: &[Float; 3]) -> [Float; 3] {
     let [row1, row2, row3] = matrix;
 
     [
@@ -222,133 +224,1223 @@ fn xyz_to_linear_rec2020(value: &[Float; 3]) -> [Float; 3] {
     multiply(&XYZ_TO_LINEAR_REC2020, value)
 }
 
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/main/src/spaces/rec2100-pq.js
+// https://github.com/color-js/color.js/blob/main/src/spaces/rec2100-hlg.js
+
+pub(crate) mod hdr {
+    use crate::Float;
+
+    /// The luminance of media white in cd/m², following ITU-R BT.2408.
+    pub(crate) const MEDIA_WHITE: Float = 203.0;
+
+    pub(crate) const M1: Float = 2610.0 / 16384.0;
+    pub(crate) const M2: Float = 2523.0 / 32.0;
+    pub(crate) const C1: Float = 3424.0 / 4096.0;
+    pub(crate) const C2: Float = 2413.0 / 128.0;
+    pub(crate) const C3: Float = 2392.0 / 128.0;
+
+    /// Convert the PQ-encoded value to its linear value, with 1.0 being
+    /// media white.
+    pub(crate) fn pq_to_linear(value: Float) -> Float {
+        let x = value.abs().powf(M2.recip());
+        let linear = ((x - C1).max(0.0) / (C2 - C3 * x)).powf(M1.recip());
+        (linear * 10_000.0 / MEDIA_WHITE).copysign(value)
+    }
+
+    /// Convert the linear value, with 1.0 being media white, to its
+    /// PQ-encoded value.
+    pub(crate) fn linear_to_pq(value: Float) -> Float {
+        let x = (value.abs() * MEDIA_WHITE / 10_000.0).powf(M1);
+        ((C1 + C2 * x) / (1.0 + C3 * x)).powf(M2).copysign(value)
+    }
+
+    const A: Float = 0.17883277;
+    const B: Float = 0.28466892; // 1 - 4 * A
+    const C: Float = 0.55991073; // 0.5 - A * ln(4 * A)
+
+    /// The scale factor that maps HLG's 75% signal level to media white.
+    const HLG_SCALE: Float = 3.7743;
+
+    /// Convert the HLG-encoded value to its linear value, with 1.0 being
+    /// media white.
+    pub(crate) fn hlg_to_linear(value: Float) -> Float {
+        let magnitude = value.abs();
+        let linear = if magnitude <= 0.5 {
+            magnitude * magnitude / 3.0
+        } else {
+            (((magnitude - C) / A).exp() + B) / 12.0
+        };
+
+        (linear * HLG_SCALE).copysign(value)
+    }
+
+    /// Convert the linear value, with 1.0 being media white, to its
+    /// HLG-encoded value.
+    pub(crate) fn linear_to_hlg(value: Float) -> Float {
+        let magnitude = value.abs() / HLG_SCALE;
+        let encoded = if magnitude <= 1.0 / 12.0 {
+            (3.0 * magnitude).sqrt()
+        } else {
+            A * (12.0 * magnitude - B).ln() + C
+        };
+
+        encoded.copysign(value)
+    }
+
+    /// Convert coordinates for Rec. 2100 PQ to linear Rec. 2020. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn rec2100_pq_to_linear_rec2020(value: &[Float; 3]) -> [Float; 3] {
+        value.map(pq_to_linear)
+    }
+
+    /// Convert coordinates for linear Rec. 2020 to Rec. 2100 PQ. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn linear_rec2020_to_rec2100_pq(value: &[Float; 3]) -> [Float; 3] {
+        value.map(linear_to_pq)
+    }
+
+    /// Convert coordinates for Rec. 2100 HLG to linear Rec. 2020. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn rec2100_hlg_to_linear_rec2020(value: &[Float; 3]) -> [Float; 3] {
+        value.map(hlg_to_linear)
+    }
+
+    /// Convert coordinates for linear Rec. 2020 to Rec. 2100 HLG. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn linear_rec2020_to_rec2100_hlg(value: &[Float; 3]) -> [Float; 3] {
+        value.map(linear_to_hlg)
+    }
+}
+
+use hdr::{
+    linear_rec2020_to_rec2100_hlg, linear_rec2020_to_rec2100_pq, rec2100_hlg_to_linear_rec2020,
+    rec2100_pq_to_linear_rec2020,
+};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/a98rgb.js
+
+mod a98 {
+    use crate::Float;
+
+    const GAMMA: Float = 563.0 / 256.0;
+
+    /// Convert coordinates for A98 RGB to linear A98 RGB. This is a one-hop,
+    /// direct conversion.
+    #[inline]
+    pub(super) fn a98_rgb_to_linear_a98_rgb(value: &[Float; 3]) -> [Float; 3] {
+        #[inline]
+        fn convert(value: Float) -> Float {
+            value.signum() * value.abs().powf(GAMMA)
+        }
+
+        [convert(value[0]), convert(value[1]), convert(value[2])]
+    }
+
+    /// Convert coordinates for linear A98 RGB to A98 RGB. This is a one-hop,
+    /// direct conversion.
+    #[inline]
+    pub(super) fn linear_a98_rgb_to_a98_rgb(value: &[Float; 3]) -> [Float; 3] {
+        #[inline]
+        fn convert(value: Float) -> Float {
+            value.signum() * value.abs().powf(GAMMA.recip())
+        }
+
+        [convert(value[0]), convert(value[1]), convert(value[2])]
+    }
+}
+
+use a98::{a98_rgb_to_linear_a98_rgb, linear_a98_rgb_to_a98_rgb};
+
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/a98rgb-linear.js
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LINEAR_A98_RGB_TO_XYZ: [[Float; 3]; 3] = [
+    [ 0.5766690429101305,  0.1855582379065463,  0.1882286462349947  ],
+    [ 0.29734497525053605, 0.6273635662554661,  0.07529145849399788 ],
+    [ 0.02703136138641234, 0.07068885253582723, 0.9913375368376388  ],
+];
+
+/// Convert coordinates for linear A98 RGB to XYZ. This is a one-hop, direct
+/// conversion.
+#[inline]
+fn linear_a98_rgb_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&LINEAR_A98_RGB_TO_XYZ, value)
+}
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LINEAR_A98_RGB: [[Float; 3]; 3] = [
+    [  2.0415879038107465,   -0.5650069742788596,  -0.34473135077832956 ],
+    [ -0.9692436362808795,    1.8759675015077202,   0.04155505740717557 ],
+    [  0.013444280632031142, -0.11836239223101838,  1.0151749943912054  ],
+];
+
+/// Convert coordinates for XYZ to linear A98 RGB. This is a one-hop, direct
+/// conversion.
+#[inline]
+fn xyz_to_linear_a98_rgb(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&XYZ_TO_LINEAR_A98_RGB, value)
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/prophoto.js
+
+mod prophoto {
+    use crate::Float;
+
+    const ET: Float = 1.0 / 512.0;
+    const ET2: Float = 16.0 / 512.0;
+
+    /// Convert coordinates for ProPhoto RGB to linear ProPhoto RGB. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn prophoto_rgb_to_linear_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+        #[inline]
+        fn convert(value: Float) -> Float {
+            if value.abs() <= ET2 {
+                value / 16.0
+            } else {
+                value.signum() * value.abs().powf(1.8)
+            }
+        }
+
+        [convert(value[0]), convert(value[1]), convert(value[2])]
+    }
+
+    /// Convert coordinates for linear ProPhoto RGB to ProPhoto RGB. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    pub(super) fn linear_prophoto_rgb_to_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+        #[inline]
+        fn convert(value: Float) -> Float {
+            if value.abs() >= ET {
+                value.signum() * value.abs().powf((1.8 as Float).recip())
+            } else {
+                16.0 * value
+            }
+        }
+
+        [convert(value[0]), convert(value[1]), convert(value[2])]
+    }
+}
+
+use prophoto::{linear_prophoto_rgb_to_prophoto_rgb, prophoto_rgb_to_linear_prophoto_rgb};
+
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/prophoto-linear.js
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LINEAR_PROPHOTO_RGB_TO_XYZ_D50: [[Float; 3]; 3] = [
+    [ 0.79776664490064230, 0.13518129740053308, 0.03134773412839220 ],
+    [ 0.28807482881940130, 0.71183523424187300, 0.00008993693872564 ],
+    [ 0.00000000000000000, 0.00000000000000000, 0.82510460251046020 ],
+];
+
+/// Convert coordinates for linear ProPhoto RGB to XYZ D50. This is a one-hop,
+/// direct conversion.
+#[inline]
+fn linear_prophoto_rgb_to_xyz_d50(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&LINEAR_PROPHOTO_RGB_TO_XYZ_D50, value)
+}
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_D50_TO_LINEAR_PROPHOTO_RGB: [[Float; 3]; 3] = [
+    [  1.34578688164715830, -0.25557208737979464, -0.05110186497554526 ],
+    [ -0.54463070512490190,  1.50824774284514680,  0.02052744743642139 ],
+    [  0.00000000000000000,  0.00000000000000000,  1.21196754563894520 ],
+];
+
+/// Convert coordinates for XYZ D50 to linear ProPhoto RGB. This is a one-hop,
+/// direct conversion.
+#[inline]
+fn xyz_d50_to_linear_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&XYZ_D50_TO_LINEAR_PROPHOTO_RGB, value)
+}
+
 // --------------------------------------------------------------------------------------------------------------------
 
 mod oklab {
     use crate::Float;
 
-    /// Convert coordinates for Oklch to Oklab or for Oklrch to Oklrab. This is a
-    /// one-hop, direct conversion.
+    /// Convert coordinates for Oklch to Oklab or for Oklrch to Oklrab. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(crate) fn okxch_to_okxab(value: &[Float; 3]) -> [Float; 3] {
+        let [L, C, h] = *value;
+
+        if h.is_nan() {
+            [L, 0.0, 0.0]
+        } else {
+            let hue_radian = h.to_radians();
+            [L, C * hue_radian.cos(), C * hue_radian.sin()]
+        }
+    }
+
+    const EPSILON: Float = 0.0002;
+
+    /// Convert coordinates for Oklab to Oklch or for Oklrab to Oklrch. This is a
+    /// one-hop, direct conversion.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(super) fn okxab_to_okxch(value: &[Float; 3]) -> [Float; 3] {
+        let [L, a, b] = *value;
+        let (C, h) = if a.abs() < EPSILON && b.abs() < EPSILON {
+            (0.0, Float::NAN)
+        } else {
+            ((a.powi(2) + b.powi(2)).sqrt(), b.atan2(a).to_degrees())
+        };
+
+        let h = if h < 0.0 { h + 360.0 } else { h };
+
+        [L, C, h]
+    }
+
+    const K1: Float = 0.206;
+    const K2: Float = 0.03;
+    const K3: Float = (1.0 + K1) / (1.0 + K2);
+
+    /// Convert coordinates for Oklab to Oklrab or for Oklch to Oklrch. This
+    /// function replaces the lightness L with the [improved lightness
+    /// Lr](https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab).
+    /// This is a one-hop, direct conversion.
+    #[inline]
+    pub(super) fn oklxx_to_oklrxx(value: &[Float; 3]) -> [Float; 3] {
+        let [l, a, b] = *value;
+        let k3l = K3 * l;
+        [
+            0.5 * (k3l - K1 + ((k3l - K1) * (k3l - K1) + 4.0 * K2 * k3l).sqrt()),
+            a,
+            b,
+        ]
+    }
+
+    /// Convert coordinates for Oklrab to Oklab or for Oklrch to Oklch. This
+    /// function replaces the [improved lightness
+    /// Lr](https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab)
+    /// with the original lightness L. This is a one-hop, direct conversion.
+    #[inline]
+    pub(super) fn oklrxx_to_oklxx(value: &[Float; 3]) -> [Float; 3] {
+        let [lr, a, b] = *value;
+        [(lr * (lr + K1)) / (K3 * (lr + K2)), a, b]
+    }
+}
+
+pub(crate) use oklab::okxch_to_okxab;
+use oklab::{oklrxx_to_oklxx, oklxx_to_oklrxx, okxab_to_okxch};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/oklab.js
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const OKLAB_TO_OKLMS: [[Float; 3]; 3] = [
+    [ 1.0000000000000000,  0.3963377773761749,  0.2158037573099136 ],
+    [ 1.0000000000000000, -0.1055613458156586, -0.0638541728258133 ],
+    [ 1.0000000000000000, -0.0894841775298119, -1.2914855480194092 ],
+];
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const OKLMS_TO_XYZ: [[Float; 3]; 3] = [
+    [  1.2268798758459243, -0.5578149944602171,  0.2813910456659647 ],
+    [ -0.0405757452148008,  1.1122868032803170, -0.0717110580655164 ],
+    [ -0.0763729366746601, -0.4214933324022432,  1.5869240198367816 ],
+];
+
+/// Convert coordinates for Oklab to XYZ. This is a one-hop, direct conversion,
+/// even though it requires two matrix multiplications and a coordinate-wise
+/// exponential.
+#[inline]
+fn oklab_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let [l, m, s] = multiply(&OKLAB_TO_OKLMS, value);
+    multiply(&OKLMS_TO_XYZ, &[l.powi(3), m.powi(3), s.powi(3)])
+}
+
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/oklab.js
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_OKLMS: [[Float; 3]; 3] = [
+    [ 0.8190224379967030, 0.3619062600528904, -0.1288737815209879 ],
+    [ 0.0329836539323885, 0.9292868615863434,  0.0361446663506424 ],
+    [ 0.0481771893596242, 0.2642395317527308,  0.6335478284694309 ],
+];
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const OKLMS_TO_OKLAB: [[Float; 3]; 3] = [
+    [ 0.2104542683093140,  0.7936177747023054, -0.0040720430116193 ],
+    [ 1.9779985324311684, -2.4285922420485799,  0.4505937096174110 ],
+    [ 0.0259040424655478,  0.7827717124575296, -0.8086757549230774 ],
+];
+
+/// Convert coordinates for XYZ to Oklab. This is a one-hop, direct conversion,
+/// even though it requires two matrix multiplications and a coordinate-wise
+/// exponential.
+#[inline]
+fn xyz_to_oklab(value: &[Float; 3]) -> [Float; 3] {
+    let [l, m, s] = multiply(&XYZ_TO_OKLMS, value);
+    multiply(&OKLMS_TO_OKLAB, &[l.cbrt(), m.cbrt(), s.cbrt()])
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://bottosson.github.io/posts/colorpicker/
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/okhsl.js
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/okhsv.js
+
+#[allow(clippy::excessive_precision)]
+mod okhsx {
+    use super::{oklab_to_xyz, xyz_to_linear_srgb, OKLAB_TO_OKLMS};
+    use crate::Float;
+
+    /// The product of `XYZ_TO_LINEAR_SRGB` and `OKLMS_TO_XYZ`, i.e., the
+    /// conversion from non-linear LMS to linear sRGB.
+    #[rustfmt::skip]
+    const OKLMS_TO_LINEAR_SRGB: [[Float; 3]; 3] = [
+        [  4.07674163607596,     -3.307711539258063,   0.23096990318210486 ],
+        [ -1.2684379732850315,    2.609757349287688,  -0.34131937600265727 ],
+        [ -0.004196076138675495, -0.7034186179359363,  1.7076146940746117  ],
+    ];
+
+    /// The threshold for chroma, below which colors are treated as achromatic.
+    const EPSILON: Float = 0.0002;
+
+    /// Convert Oklab to linear sRGB by way of XYZ.
+    #[inline]
+    fn oklab_to_linear_srgb(value: &[Float; 3]) -> [Float; 3] {
+        xyz_to_linear_srgb(&oklab_to_xyz(value))
+    }
+
+    /// Compute the coefficients for deriving non-linear LMS from normalized a
+    /// and b.
+    #[inline]
+    fn lms_coefficients(a: Float, b: Float) -> [Float; 3] {
+        [
+            OKLAB_TO_OKLMS[0][1].mul_add(a, OKLAB_TO_OKLMS[0][2] * b),
+            OKLAB_TO_OKLMS[1][1].mul_add(a, OKLAB_TO_OKLMS[1][2] * b),
+            OKLAB_TO_OKLMS[2][1].mul_add(a, OKLAB_TO_OKLMS[2][2] * b),
+        ]
+    }
+
+    /// Compute the maximum saturation S = C/L for the hue given by normalized
+    /// a and b, such that the color still is in sRGB's gamut. This function
+    /// uses Ottosson's polynomial approximation followed by one step of
+    /// Halley's method.
+    #[allow(non_snake_case)]
+    fn compute_max_saturation(a: Float, b: Float) -> Float {
+        // Select the polynomial for the RGB component that clips first.
+        let (k, w) = if -1.88170328 * a - 0.80936493 * b > 1.0 {
+            (
+                [1.19086277, 1.76576728, 0.59662641, 0.75515197, 0.56771245],
+                OKLMS_TO_LINEAR_SRGB[0],
+            )
+        } else if 1.81444104 * a - 1.19445276 * b > 1.0 {
+            (
+                [0.73956515, -0.45954404, 0.08285427, 0.12541070, 0.14503204],
+                OKLMS_TO_LINEAR_SRGB[1],
+            )
+        } else {
+            (
+                [
+                    1.35733652,
+                    -0.00915799,
+                    -1.15130210,
+                    -0.50559606,
+                    0.00692167,
+                ],
+                OKLMS_TO_LINEAR_SRGB[2],
+            )
+        };
+
+        let S = k[0] + k[1] * a + k[2] * b + k[3] * a * a + k[4] * a * b;
+
+        let [k_l, k_m, k_s] = lms_coefficients(a, b);
+        let l_ = S.mul_add(k_l, 1.0);
+        let m_ = S.mul_add(k_m, 1.0);
+        let s_ = S.mul_add(k_s, 1.0);
+
+        let [l, m, s] = [l_.powi(3), m_.powi(3), s_.powi(3)];
+        let [l_ds, m_ds, s_ds] = [
+            3.0 * k_l * l_ * l_,
+            3.0 * k_m * m_ * m_,
+            3.0 * k_s * s_ * s_,
+        ];
+        let [l_ds2, m_ds2, s_ds2] = [
+            6.0 * k_l * k_l * l_,
+            6.0 * k_m * k_m * m_,
+            6.0 * k_s * k_s * s_,
+        ];
+
+        let f = w[0] * l + w[1] * m + w[2] * s;
+        let f1 = w[0] * l_ds + w[1] * m_ds + w[2] * s_ds;
+        let f2 = w[0] * l_ds2 + w[1] * m_ds2 + w[2] * s_ds2;
+
+        S - f * f1 / (f1 * f1 - 0.5 * f * f2)
+    }
+
+    /// Find the cusp of sRGB's gamut for the hue given by normalized a and b.
+    /// This function returns the lightness and chroma of the cusp, i.e., the
+    /// color with maximum chroma.
+    #[allow(non_snake_case)]
+    fn find_cusp(a: Float, b: Float) -> [Float; 2] {
+        let S_cusp = compute_max_saturation(a, b);
+        let [r, g, b] = oklab_to_linear_srgb(&[1.0, S_cusp * a, S_cusp * b]);
+        let L_cusp = (1.0 / r.max(g).max(b)).cbrt();
+        [L_cusp, L_cusp * S_cusp]
+    }
+
+    /// Find the intersection of the line from (L0, 0) to (L1, C1) with sRGB's
+    /// gamut boundary for the hue given by normalized a and b. This function
+    /// returns the parameter t, such that the intersection is at (L0 * (1 - t)
+    /// + t * L1, t * C1).
+    #[allow(non_snake_case)]
+    fn find_gamut_intersection(
+        a: Float,
+        b: Float,
+        L1: Float,
+        C1: Float,
+        L0: Float,
+        cusp: [Float; 2],
+    ) -> Float {
+        let [L_cusp, C_cusp] = cusp;
+
+        if ((L1 - L0) * C_cusp - (L_cusp - L0) * C1) <= 0.0 {
+            // Lower half: Intersection with the triangle is precise enough.
+            return C_cusp * L0 / (C1 * L_cusp + C_cusp * (L0 - L1));
+        }
+
+        // Upper half: Start with the triangle and take one Halley step.
+        let mut t = C_cusp * (L0 - 1.0) / (C1 * (L_cusp - 1.0) + C_cusp * (L0 - L1));
+
+        let [k_l, k_m, k_s] = lms_coefficients(a, b);
+        let l_dt = (L1 - L0) + C1 * k_l;
+        let m_dt = (L1 - L0) + C1 * k_m;
+        let s_dt = (L1 - L0) + C1 * k_s;
+
+        let L = L0 * (1.0 - t) + t * L1;
+        let C = t * C1;
+
+        let l_ = L + C * k_l;
+        let m_ = L + C * k_m;
+        let s_ = L + C * k_s;
+
+        let [l, m, s] = [l_.powi(3), m_.powi(3), s_.powi(3)];
+        let [l1, m1, s1] = [
+            3.0 * l_dt * l_ * l_,
+            3.0 * m_dt * m_ * m_,
+            3.0 * s_dt * s_ * s_,
+        ];
+        let [l2, m2, s2] = [
+            6.0 * l_dt * l_dt * l_,
+            6.0 * m_dt * m_dt * m_,
+            6.0 * s_dt * s_dt * s_,
+        ];
+
+        let step = OKLMS_TO_LINEAR_SRGB
+            .iter()
+            .map(|w| {
+                let r = w[0] * l + w[1] * m + w[2] * s - 1.0;
+                let r1 = w[0] * l1 + w[1] * m1 + w[2] * s1;
+                let r2 = w[0] * l2 + w[1] * m2 + w[2] * s2;
+                let u = r1 / (r1 * r1 - 0.5 * r * r2);
+                if u >= 0.0 {
+                    -r * u
+                } else {
+                    Float::MAX
+                }
+            })
+            .fold(Float::MAX, Float::min);
+
+        t += step;
+        t
+    }
+
+    const K1: Float = 0.206;
+    const K2: Float = 0.03;
+    const K3: Float = (1.0 + K1) / (1.0 + K2);
+
+    /// Map Oklab's lightness L to the revised lightness Lr.
+    #[inline]
+    fn toe(x: Float) -> Float {
+        let y = K3 * x - K1;
+        0.5 * (y + y.mul_add(y, 4.0 * K2 * K3 * x).sqrt())
+    }
+
+    /// Map the revised lightness Lr to Oklab's lightness L.
+    #[inline]
+    fn toe_inv(x: Float) -> Float {
+        x.mul_add(x, K1 * x) / (K3 * (x + K2))
+    }
+
+    /// Convert the cusp's lightness and chroma to the slopes S and T of the
+    /// triangle approximating sRGB's gamut.
+    #[inline]
+    fn to_st(cusp: [Float; 2]) -> [Float; 2] {
+        let [l, c] = cusp;
+        [c / l, c / (1.0 - l)]
+    }
+
+    /// Approximate slopes S and T for a smooth, saturation-preserving gamut
+    /// boundary for the hue given by normalized a and b.
+    #[allow(non_snake_case)]
+    fn get_st_mid(a_: Float, b_: Float) -> [Float; 2] {
+        let S = 0.11516993
+            + 1.0
+                / (7.44778970
+                    + 4.15901240 * b_
+                    + a_ * (-2.19557347
+                        + 1.75198401 * b_
+                        + a_ * (-2.13704948 - 10.02301043 * b_
+                            + a_ * (-4.24894561 + 5.38770819 * b_ + 4.69891013 * a_))));
+
+        let T = 0.11239642
+            + 1.0
+                / (1.61320320 - 0.68124379 * b_
+                    + a_ * (0.40370612
+                        + 0.90148123 * b_
+                        + a_ * (-0.27087943
+                            + 0.61223990 * b_
+                            + a_ * (0.00299215 - 0.45399568 * b_ - 0.14661872 * a_))));
+
+        [S, T]
+    }
+
+    /// Compute the three chroma values anchoring Okhsl's saturation for the
+    /// given lightness and hue: the chroma at saturation 0, 0.8, and 1.
+    #[allow(non_snake_case)]
+    fn get_cs(L: Float, a_: Float, b_: Float) -> [Float; 3] {
+        let cusp = find_cusp(a_, b_);
+        let C_max = find_gamut_intersection(a_, b_, L, 1.0, L, cusp);
+        let [S_max, T_max] = to_st(cusp);
+
+        // Scale factor to compensate for the curved part of the gamut shape.
+        let k = C_max / (L * S_max).min((1.0 - L) * T_max);
+
+        let C_mid = {
+            let [S_mid, T_mid] = get_st_mid(a_, b_);
+            let C_a = L * S_mid;
+            let C_b = (1.0 - L) * T_mid;
+            0.9 * k
+                * (1.0 / (1.0 / C_a.powi(4) + 1.0 / C_b.powi(4)))
+                    .sqrt()
+                    .sqrt()
+        };
+
+        let C_0 = {
+            let C_a = L * 0.4;
+            let C_b = (1.0 - L) * 0.8;
+            (1.0 / (1.0 / C_a.powi(2) + 1.0 / C_b.powi(2))).sqrt()
+        };
+
+        [C_0, C_mid, C_max]
+    }
+
+    const MID: Float = 0.8;
+    const MID_INV: Float = 1.25;
+
+    /// Convert coordinates for Oklab to Okhsl. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn oklab_to_okhsl(value: &[Float; 3]) -> [Float; 3] {
+        let [L, a, b] = *value;
+
+        if L >= 1.0 {
+            return [Float::NAN, 0.0, 1.0];
+        } else if L <= 0.0 {
+            return [Float::NAN, 0.0, 0.0];
+        }
+
+        let C = a.hypot(b);
+        if C < EPSILON {
+            return [Float::NAN, 0.0, toe(L)];
+        }
+
+        let a_ = a / C;
+        let b_ = b / C;
+        let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+
+        let [C_0, C_mid, C_max] = get_cs(L, a_, b_);
+
+        let s = if C < C_mid {
+            let k_1 = MID * C_0;
+            let k_2 = 1.0 - k_1 / C_mid;
+            let t = C / (k_1 + k_2 * C);
+            t * MID
+        } else {
+            let k_0 = C_mid;
+            let k_1 = (1.0 - MID) * C_mid * C_mid * MID_INV * MID_INV / C_0;
+            let k_2 = 1.0 - k_1 / (C_max - C_mid);
+            let t = (C - k_0) / (k_1 + k_2 * (C - k_0));
+            MID + (1.0 - MID) * t
+        };
+
+        [h, s, toe(L)]
+    }
+
+    /// Convert coordinates for Okhsl to Oklab. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn okhsl_to_oklab(value: &[Float; 3]) -> [Float; 3] {
+        let [h, s, l] = *value;
+
+        if l >= 1.0 {
+            return [1.0, 0.0, 0.0];
+        } else if l <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+
+        let L = toe_inv(l);
+        if s == 0.0 {
+            return [L, 0.0, 0.0];
+        }
+
+        let hue_radian = h.to_radians();
+        let a_ = hue_radian.cos();
+        let b_ = hue_radian.sin();
+
+        let [C_0, C_mid, C_max] = get_cs(L, a_, b_);
+
+        let C = if s < MID {
+            let t = MID_INV * s;
+            let k_1 = MID * C_0;
+            let k_2 = 1.0 - k_1 / C_mid;
+            t * k_1 / (1.0 - k_2 * t)
+        } else {
+            let t = (s - MID) / (1.0 - MID);
+            let k_0 = C_mid;
+            let k_1 = (1.0 - MID) * C_mid * C_mid * MID_INV * MID_INV / C_0;
+            let k_2 = 1.0 - k_1 / (C_max - C_mid);
+            k_0 + t * k_1 / (1.0 - k_2 * t)
+        };
+
+        [L, C * a_, C * b_]
+    }
+
+    const S_0: Float = 0.5;
+
+    /// Determine the factor for scaling lightness and chroma to account for
+    /// the curvature of sRGB's gamut boundary near the cusp.
+    #[allow(non_snake_case)]
+    fn scale_l(L_vt: Float, C_vt: Float, a_: Float, b_: Float) -> Float {
+        let [r, g, b] = oklab_to_linear_srgb(&[L_vt, a_ * C_vt, b_ * C_vt]);
+        (1.0 / r.max(g).max(b).max(0.0)).cbrt()
+    }
+
+    /// Convert coordinates for Oklab to Okhsv. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn oklab_to_okhsv(value: &[Float; 3]) -> [Float; 3] {
+        let [L, a, b] = *value;
+
+        if L <= 0.0 {
+            return [Float::NAN, 0.0, 0.0];
+        }
+
+        let C = a.hypot(b);
+        if C < EPSILON {
+            return [Float::NAN, 0.0, toe(L)];
+        }
+
+        let a_ = a / C;
+        let b_ = b / C;
+        let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+
+        let [S_max, T_max] = to_st(find_cusp(a_, b_));
+        let k = 1.0 - S_0 / S_max;
+
+        // Find the triangle's lightness and chroma for the color.
+        let t = T_max / (C + L * T_max);
+        let L_v = t * L;
+        let C_v = t * C;
+
+        let L_vt = toe_inv(L_v);
+        let C_vt = C_v * L_vt / L_v;
+
+        // Invert the scaling and toe of okhsv_to_oklab.
+        let L = L / scale_l(L_vt, C_vt, a_, b_);
+        let v = toe(L) / L_v;
+        let s = (S_0 + T_max) * C_v / (T_max * S_0 + T_max * k * C_v);
+
+        [h, s, v]
+    }
+
+    /// Convert coordinates for Okhsv to Oklab. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn okhsv_to_oklab(value: &[Float; 3]) -> [Float; 3] {
+        let [h, s, v] = *value;
+
+        if v <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+
+        let hue_radian = h.to_radians();
+        let a_ = hue_radian.cos();
+        let b_ = hue_radian.sin();
+
+        let [S_max, T_max] = to_st(find_cusp(a_, b_));
+        let k = 1.0 - S_0 / S_max;
+
+        // Determine lightness and chroma for the triangle with v = 1.
+        let denominator = S_0 + T_max - T_max * k * s;
+        let L_v = 1.0 - s * S_0 / denominator;
+        let C_v = s * T_max * S_0 / denominator;
+
+        let L = v * L_v;
+        let C = v * C_v;
+
+        // Apply the inverse toe to account for the curvature of lightness.
+        let L_vt = toe_inv(L_v);
+        let C_vt = C_v * L_vt / L_v;
+
+        let L_new = toe_inv(L);
+        let C = C * L_new / L;
+        let L = L_new;
+
+        // Scale to fit sRGB's gamut boundary.
+        let scale = scale_l(L_vt, C_vt, a_, b_);
+        let L = L * scale;
+        let C = C * scale;
+
+        [L, C * a_, C * b_]
+    }
+}
+
+use okhsx::{okhsl_to_oklab, okhsv_to_oklab, oklab_to_okhsl, oklab_to_okhsv};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/adapt.js
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_XYZ_D50: [[Float; 3]; 3] = [
+    [  1.0479297925449969,   0.022946870601609652, -0.05019226628920524  ],
+    [  0.02962780877005599,  0.9904344267538799,   -0.017073799063418826 ],
+    [ -0.009243040646204504, 0.015055191490298152,  0.7518742814281371   ],
+];
+
+/// Convert coordinates for XYZ D65 to XYZ D50 with the Bradford chromatic
+/// adaptation transform. This is a one-hop, direct conversion.
+#[inline]
+fn xyz_to_xyz_d50(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&XYZ_TO_XYZ_D50, value)
+}
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_D50_TO_XYZ: [[Float; 3]; 3] = [
+    [  0.955473421488075,   -0.02309845494876471,  0.06325924320057072  ],
+    [ -0.0283697093338637,   1.0099953980813041,   0.021041441191917323 ],
+    [  0.012314014864481998, -0.020507649298898964, 1.330365926242124   ],
+];
+
+/// Convert coordinates for XYZ D50 to XYZ D65 with the Bradford chromatic
+/// adaptation transform. This is a one-hop, direct conversion.
+#[inline]
+fn xyz_d50_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    multiply(&XYZ_D50_TO_XYZ, value)
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/lab.js
+
+mod cielab {
+    use crate::Float;
+
+    /// The D50 white point in XYZ.
+    #[allow(clippy::excessive_precision)]
+    const D50: [Float; 3] = [0.9642956764295677, 1.0, 0.8251046025104602];
+
+    const EPSILON: Float = 216.0 / 24389.0;
+    const KAPPA: Float = 24389.0 / 27.0;
+
+    /// Convert coordinates for XYZ D50 to CIELAB. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    pub(super) fn xyz_d50_to_lab(value: &[Float; 3]) -> [Float; 3] {
+        #[inline]
+        fn convert(value: Float) -> Float {
+            if value > EPSILON {
+                value.cbrt()
+            } else {
+                (KAPPA * value + 16.0) / 116.0
+            }
+        }
+
+        let f0 = convert(value[0] / D50[0]);
+        let f1 = convert(value[1] / D50[1]);
+        let f2 = convert(value[2] / D50[2]);
+
+        [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+    }
+
+    /// Convert coordinates for CIELAB to XYZ D50. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(super) fn lab_to_xyz_d50(value: &[Float; 3]) -> [Float; 3] {
+        let [L, a, b] = *value;
+
+        let f1 = (L + 16.0) / 116.0;
+        let f0 = a / 500.0 + f1;
+        let f2 = f1 - b / 200.0;
+
+        let x = if f0.powi(3) > EPSILON {
+            f0.powi(3)
+        } else {
+            (116.0 * f0 - 16.0) / KAPPA
+        };
+        let y = if L > KAPPA * EPSILON {
+            f1.powi(3)
+        } else {
+            L / KAPPA
+        };
+        let z = if f2.powi(3) > EPSILON {
+            f2.powi(3)
+        } else {
+            (116.0 * f2 - 16.0) / KAPPA
+        };
+
+        [x * D50[0], y * D50[1], z * D50[2]]
+    }
+
+    const CHROMA_EPSILON: Float = 0.02;
+
+    /// Convert coordinates for CIELAB to CIELCh. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(super) fn lab_to_lch(value: &[Float; 3]) -> [Float; 3] {
+        let [L, a, b] = *value;
+        let (C, h) = if a.abs() < CHROMA_EPSILON && b.abs() < CHROMA_EPSILON {
+            (0.0, Float::NAN)
+        } else {
+            (a.hypot(b), b.atan2(a).to_degrees())
+        };
+
+        let h = if h < 0.0 { h + 360.0 } else { h };
+
+        [L, C, h]
+    }
+
+    /// Convert coordinates for CIELCh to CIELAB. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    #[allow(non_snake_case)]
+    pub(super) fn lch_to_lab(value: &[Float; 3]) -> [Float; 3] {
+        let [L, C, h] = *value;
+
+        if h.is_nan() {
+            [L, 0.0, 0.0]
+        } else {
+            let hue_radian = h.to_radians();
+            [L, C * hue_radian.cos(), C * hue_radian.sin()]
+        }
+    }
+}
+
+use cielab::{lab_to_lch, lab_to_xyz_d50, lch_to_lab, xyz_d50_to_lab};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/main/src/spaces/ictcp.js
+
+mod ictcp {
+    use super::hdr::{C1, C2, C3, M1, M2, MEDIA_WHITE};
+    use super::multiply;
+    use crate::Float;
+
+    // The product of the matrix converting XYZ to linear Rec. 2020 and the
+    // matrix converting linear Rec. 2020 to LMS from ITU-R BT.2100.
+    #[rustfmt::skip]
+    #[allow(clippy::excessive_precision)]
+    const XYZ_TO_LMS: [[Float; 3]; 3] = [
+        [  0.3592832590121217,   0.6976051147779501,  -0.035891593232028965 ],
+        [ -0.1920808463704993,   1.1004767970374318,   0.07537486585191183  ],
+        [  0.007079784460747904, 0.07483966621863616,  0.8433265453898765   ],
+    ];
+
+    #[rustfmt::skip]
+    #[allow(clippy::excessive_precision)]
+    const LMS_TO_XYZ: [[Float; 3]; 3] = [
+        [  2.0701522183894223,  -1.326347338967156,    0.20665104762940523 ],
+        [  0.3647385209748072,   0.6805660249472273,  -0.04530454592203466 ],
+        [ -0.04974720753581231, -0.04926096669661313,  1.188065924992304   ],
+    ];
+
+    #[rustfmt::skip]
+    const LMS_TO_ICTCP: [[Float; 3]; 3] = [
+        [  2048.0 / 4096.0,   2048.0 / 4096.0,     0.0          ],
+        [  6610.0 / 4096.0, -13613.0 / 4096.0,  7003.0 / 4096.0 ],
+        [ 17933.0 / 4096.0, -17390.0 / 4096.0,  -543.0 / 4096.0 ],
+    ];
+
+    #[rustfmt::skip]
+    #[allow(clippy::excessive_precision)]
+    const ICTCP_TO_LMS: [[Float; 3]; 3] = [
+        [ 1.0,  0.008609037037932756,  0.11102962500302596 ],
+        [ 1.0, -0.008609037037932756, -0.11102962500302596 ],
+        [ 1.0,  0.5600313357106791,   -0.32062717498731885 ],
+    ];
+
+    /// Convert coordinates for XYZ to ICtCp. This is a one-hop, direct
+    /// conversion.
+    pub(super) fn xyz_to_ictcp(value: &[Float; 3]) -> [Float; 3] {
+        let lms = multiply(&XYZ_TO_LMS, &value.map(|c| c * MEDIA_WHITE));
+        let pq = lms.map(|c| {
+            let x = (c.abs() / 10_000.0).powf(M1);
+            ((C1 + C2 * x) / (1.0 + C3 * x)).powf(M2).copysign(c)
+        });
+        multiply(&LMS_TO_ICTCP, &pq)
+    }
+
+    /// Convert coordinates for ICtCp to XYZ. This is a one-hop, direct
+    /// conversion.
+    pub(super) fn ictcp_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+        let pq = multiply(&ICTCP_TO_LMS, value);
+        let lms = pq.map(|c| {
+            let x = c.abs().powf(M2.recip());
+            (10_000.0 * ((x - C1).max(0.0) / (C2 - C3 * x)).powf(M1.recip())).copysign(c)
+        });
+        multiply(&LMS_TO_XYZ, &lms).map(|c| c / MEDIA_WHITE)
+    }
+}
+
+use ictcp::{ictcp_to_xyz, xyz_to_ictcp};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/main/src/spaces/jzazbz.js
+
+mod jzazbz {
+    use super::hdr::{C1, C2, C3, MEDIA_WHITE};
+    use super::multiply;
+    use crate::Float;
+
+    const B: Float = 1.15;
+    const G: Float = 0.66;
+    const N: Float = 2610.0 / 16384.0;
+    const P: Float = 1.7 * 2523.0 / 32.0;
+    const D: Float = -0.56;
+    #[allow(clippy::excessive_precision)]
+    const D0: Float = 1.6295499532821566e-11;
+
+    #[rustfmt::skip]
+    const XYZ_TO_CONE: [[Float; 3]; 3] = [
+        [  0.41478972, 0.579999, 0.0146480 ],
+        [ -0.2015100,  1.120649, 0.0531008 ],
+        [ -0.0166008,  0.264800, 0.6684799 ],
+    ];
+
+    #[rustfmt::skip]
+    #[allow(clippy::excessive_precision)]
+    const CONE_TO_XYZ: [[Float; 3]; 3] = [
+        [  1.924226435787607,   -1.004792312595366,   0.037651404030618014 ],
+        [  0.3503167620949992,   0.7264811939316554, -0.06538442294808504  ],
+        [ -0.09098281098284759, -0.31272829052307405, 1.5227665613052608   ],
+    ];
+
+    #[rustfmt::skip]
+    const CONE_TO_IAB: [[Float; 3]; 3] = [
+        [ 0.5,       0.5,       0.0      ],
+        [ 3.524000, -4.066708,  0.542708 ],
+        [ 0.199076,  1.096799, -1.295875 ],
+    ];
+
+    #[rustfmt::skip]
+    #[allow(clippy::excessive_precision)]
+    const IAB_TO_CONE: [[Float; 3]; 3] = [
+        [ 1.0,  0.13860504327153927,  0.058047316156118856 ],
+        [ 1.0, -0.13860504327153927, -0.058047316156118856 ],
+        [ 1.0, -0.09601924202631894, -0.8118918960560388   ],
+    ];
+
+    /// Convert coordinates for XYZ to Jzazbz. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn xyz_to_jzazbz(value: &[Float; 3]) -> [Float; 3] {
+        let [Xa, Ya, Za] = value.map(|c| c * MEDIA_WHITE);
+        let Xm = B * Xa - (B - 1.0) * Za;
+        let Ym = G * Ya - (G - 1.0) * Xa;
+
+        let lms = multiply(&XYZ_TO_CONE, &[Xm, Ym, Za]);
+        let pq = lms.map(|c| {
+            let x = (c.abs() / 10_000.0).powf(N);
+            ((C1 + C2 * x) / (1.0 + C3 * x)).powf(P).copysign(c)
+        });
+
+        let [Iz, az, bz] = multiply(&CONE_TO_IAB, &pq);
+        let Jz = ((1.0 + D) * Iz) / (1.0 + D * Iz) - D0;
+        [Jz, az, bz]
+    }
+
+    /// Convert coordinates for Jzazbz to XYZ. This is a one-hop, direct
+    /// conversion.
+    #[allow(non_snake_case)]
+    pub(super) fn jzazbz_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+        let [Jz, az, bz] = *value;
+        let Iz = (Jz + D0) / (1.0 + D - D * (Jz + D0));
+
+        let pq = multiply(&IAB_TO_CONE, &[Iz, az, bz]);
+        let lms = pq.map(|c| {
+            let x = c.abs().powf(P.recip());
+            (10_000.0 * ((C1 - x) / (C3 * x - C2)).powf(N.recip())).copysign(c)
+        });
+
+        let [Xm, Ym, Za] = multiply(&CONE_TO_XYZ, &lms);
+        let Xa = (Xm + (B - 1.0) * Za) / B;
+        let Ya = (Ym + (G - 1.0) * Xa) / G;
+        [Xa, Ya, Za].map(|c| c / MEDIA_WHITE)
+    }
+}
+
+use jzazbz::{jzazbz_to_xyz, xyz_to_jzazbz};
+
+// --------------------------------------------------------------------------------------------------------------------
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/hsl.js
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/hsv.js
+// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/hwb.js
+
+mod cylindrical {
+    use crate::Float;
+
+    /// Determine the hue for the given sRGB coordinates, maximum, and
+    /// difference between maximum and minimum. If the difference is zero, the
+    /// color is achromatic and the hue is not-a-number.
     #[inline]
-    #[allow(non_snake_case)]
-    pub(crate) fn okxch_to_okxab(value: &[Float; 3]) -> [Float; 3] {
-        let [L, C, h] = *value;
+    fn hue(value: &[Float; 3], max: Float, delta: Float) -> Float {
+        let [r, g, b] = *value;
 
-        if h.is_nan() {
-            [L, 0.0, 0.0]
-        } else {
-            let hue_radian = h.to_radians();
-            [L, C * hue_radian.cos(), C * hue_radian.sin()]
+        if delta == 0.0 {
+            return Float::NAN;
         }
-    }
 
-    const EPSILON: Float = 0.0002;
+        let h = if max == r {
+            (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
 
-    /// Convert coordinates for Oklab to Oklch or for Oklrab to Oklrch. This is a
-    /// one-hop, direct conversion.
+        h * 60.0
+    }
+
+    /// Convert coordinates for sRGB to HSL. This is a one-hop, direct
+    /// conversion.
     #[inline]
-    #[allow(non_snake_case)]
-    pub(super) fn okxab_to_okxch(value: &[Float; 3]) -> [Float; 3] {
-        let [L, a, b] = *value;
-        let (C, h) = if a.abs() < EPSILON && b.abs() < EPSILON {
-            (0.0, Float::NAN)
+    pub(super) fn srgb_to_hsl(value: &[Float; 3]) -> [Float; 3] {
+        let max = value[0].max(value[1]).max(value[2]);
+        let min = value[0].min(value[1]).min(value[2]);
+        let delta = max - min;
+        let lightness = (min + max) / 2.0;
+
+        let mut hue = hue(value, max, delta);
+        let mut saturation = if delta == 0.0 || lightness == 0.0 || lightness == 1.0 {
+            0.0
         } else {
-            ((a.powi(2) + b.powi(2)).sqrt(), b.atan2(a).to_degrees())
+            (max - lightness) / lightness.min(1.0 - lightness)
         };
 
-        let h = if h < 0.0 { h + 360.0 } else { h };
+        // Out-of-gamut colors may have negative saturation.
+        if saturation < 0.0 {
+            hue += 180.0;
+            saturation = saturation.abs();
+        }
+        if hue >= 360.0 {
+            hue -= 360.0;
+        }
 
-        [L, C, h]
+        [hue, saturation * 100.0, lightness * 100.0]
     }
 
-    const K1: Float = 0.206;
-    const K2: Float = 0.03;
-    const K3: Float = (1.0 + K1) / (1.0 + K2);
-
-    /// Convert coordinates for Oklab to Oklrab or for Oklch to Oklrch. This
-    /// function replaces the lightness L with the [improved lightness
-    /// Lr](https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab).
-    /// This is a one-hop, direct conversion.
+    /// Convert coordinates for HSL to sRGB. This is a one-hop, direct
+    /// conversion.
     #[inline]
-    pub(super) fn oklxx_to_oklrxx(value: &[Float; 3]) -> [Float; 3] {
-        let [l, a, b] = *value;
-        let k3l = K3 * l;
-        [
-            0.5 * (k3l - K1 + ((k3l - K1) * (k3l - K1) + 4.0 * K2 * k3l).sqrt()),
-            a,
-            b,
-        ]
+    pub(super) fn hsl_to_srgb(value: &[Float; 3]) -> [Float; 3] {
+        let hue = value[0].rem_euclid(360.0);
+        let saturation = value[1] / 100.0;
+        let lightness = value[2] / 100.0;
+
+        let convert = |n: Float| {
+            let k = (n + hue / 30.0) % 12.0;
+            let a = saturation * lightness.min(1.0 - lightness);
+            lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+
+        [convert(0.0), convert(8.0), convert(4.0)]
     }
 
-    /// Convert coordinates for Oklrab to Oklab or for Oklrch to Oklch. This
-    /// function replaces the [improved lightness
-    /// Lr](https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab)
-    /// with the original lightness L. This is a one-hop, direct conversion.
+    /// Convert coordinates for sRGB to HSV. This is a one-hop, direct
+    /// conversion.
     #[inline]
-    pub(super) fn oklrxx_to_oklxx(value: &[Float; 3]) -> [Float; 3] {
-        let [lr, a, b] = *value;
-        [(lr * (lr + K1)) / (K3 * (lr + K2)), a, b]
-    }
-}
+    pub(super) fn srgb_to_hsv(value: &[Float; 3]) -> [Float; 3] {
+        let max = value[0].max(value[1]).max(value[2]);
+        let min = value[0].min(value[1]).min(value[2]);
+        let delta = max - min;
 
-pub(crate) use oklab::okxch_to_okxab;
-use oklab::{oklrxx_to_oklxx, oklxx_to_oklrxx, okxab_to_okxch};
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
 
-// --------------------------------------------------------------------------------------------------------------------
-// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/oklab.js
+        [hue(value, max, delta), saturation * 100.0, max * 100.0]
+    }
 
-#[rustfmt::skip]
-#[allow(clippy::excessive_precision)]
-const OKLAB_TO_OKLMS: [[Float; 3]; 3] = [
-    [ 1.0000000000000000,  0.3963377773761749,  0.2158037573099136 ],
-    [ 1.0000000000000000, -0.1055613458156586, -0.0638541728258133 ],
-    [ 1.0000000000000000, -0.0894841775298119, -1.2914855480194092 ],
-];
+    /// Convert coordinates for HSV to sRGB. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    pub(super) fn hsv_to_srgb(value: &[Float; 3]) -> [Float; 3] {
+        let hue = value[0].rem_euclid(360.0);
+        let saturation = value[1] / 100.0;
+        let brightness = value[2] / 100.0;
+
+        let convert = |n: Float| {
+            let k = (n + hue / 60.0) % 6.0;
+            brightness - brightness * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
 
-#[rustfmt::skip]
-#[allow(clippy::excessive_precision)]
-const OKLMS_TO_XYZ: [[Float; 3]; 3] = [
-    [  1.2268798758459243, -0.5578149944602171,  0.2813910456659647 ],
-    [ -0.0405757452148008,  1.1122868032803170, -0.0717110580655164 ],
-    [ -0.0763729366746601, -0.4214933324022432,  1.5869240198367816 ],
-];
+        [convert(5.0), convert(3.0), convert(1.0)]
+    }
 
-/// Convert coordinates for Oklab to XYZ. This is a one-hop, direct conversion,
-/// even though it requires two matrix multiplications and a coordinate-wise
-/// exponential.
-#[inline]
-fn oklab_to_xyz(value: &[Float; 3]) -> [Float; 3] {
-    let [l, m, s] = multiply(&OKLAB_TO_OKLMS, value);
-    multiply(&OKLMS_TO_XYZ, &[l.powi(3), m.powi(3), s.powi(3)])
-}
+    /// Convert coordinates for sRGB to HWB. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    pub(super) fn srgb_to_hwb(value: &[Float; 3]) -> [Float; 3] {
+        let max = value[0].max(value[1]).max(value[2]);
+        let min = value[0].min(value[1]).min(value[2]);
 
-// https://github.com/color-js/color.js/blob/a77e080a070039c534dda3965a769675aac5f75e/src/spaces/oklab.js
+        [hue(value, max, max - min), min * 100.0, (1.0 - max) * 100.0]
+    }
 
-#[rustfmt::skip]
-#[allow(clippy::excessive_precision)]
-const XYZ_TO_OKLMS: [[Float; 3]; 3] = [
-    [ 0.8190224379967030, 0.3619062600528904, -0.1288737815209879 ],
-    [ 0.0329836539323885, 0.9292868615863434,  0.0361446663506424 ],
-    [ 0.0481771893596242, 0.2642395317527308,  0.6335478284694309 ],
-];
+    /// Convert coordinates for HWB to sRGB. This is a one-hop, direct
+    /// conversion.
+    #[inline]
+    pub(super) fn hwb_to_srgb(value: &[Float; 3]) -> [Float; 3] {
+        let whiteness = value[1] / 100.0;
+        let blackness = value[2] / 100.0;
 
-#[rustfmt::skip]
-#[allow(clippy::excessive_precision)]
-const OKLMS_TO_OKLAB: [[Float; 3]; 3] = [
-    [ 0.2104542683093140,  0.7936177747023054, -0.0040720430116193 ],
-    [ 1.9779985324311684, -2.4285922420485799,  0.4505937096174110 ],
-    [ 0.0259040424655478,  0.7827717124575296, -0.8086757549230774 ],
-];
+        if whiteness + blackness >= 1.0 {
+            let gray = whiteness / (whiteness + blackness);
+            return [gray, gray, gray];
+        }
 
-/// Convert coordinates for XYZ to Oklab. This is a one-hop, direct conversion,
-/// even though it requires two matrix multiplications and a coordinate-wise
-/// exponential.
-#[inline]
-fn xyz_to_oklab(value: &[Float; 3]) -> [Float; 3] {
-    let [l, m, s] = multiply(&XYZ_TO_OKLMS, value);
-    multiply(&OKLMS_TO_OKLAB, &[l.cbrt(), m.cbrt(), s.cbrt()])
+        let [r, g, b] = hsl_to_srgb(&[value[0], 100.0, 50.0]);
+        let factor = 1.0 - whiteness - blackness;
+        [
+            r.mul_add(factor, whiteness),
+            g.mul_add(factor, whiteness),
+            b.mul_add(factor, whiteness),
+        ]
+    }
 }
 
+use cylindrical::{hsl_to_srgb, hsv_to_srgb, hwb_to_srgb, srgb_to_hsl, srgb_to_hsv, srgb_to_hwb};
+
 // --------------------------------------------------------------------------------------------------------------------
 
 /// Convert coordinates for sRGB to XYZ. This is a two-hop conversion.
@@ -393,6 +1485,49 @@ fn xyz_to_rec2020(value: &[Float; 3]) -> [Float; 3] {
     linear_rec2020_to_rec2020(&linear_rec2020)
 }
 
+/// Convert coordinates for Rec. 2100 PQ to XYZ. This is a two-hop conversion.
+#[inline]
+fn rec2100_pq_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let linear_rec2020 = rec2100_pq_to_linear_rec2020(value);
+    linear_rec2020_to_xyz(&linear_rec2020)
+}
+
+/// Convert coordinates for XYZ to Rec. 2100 PQ. This is a two-hop conversion.
+#[inline]
+fn xyz_to_rec2100_pq(value: &[Float; 3]) -> [Float; 3] {
+    let linear_rec2020 = xyz_to_linear_rec2020(value);
+    linear_rec2020_to_rec2100_pq(&linear_rec2020)
+}
+
+/// Convert coordinates for Rec. 2100 HLG to XYZ. This is a two-hop conversion.
+#[inline]
+fn rec2100_hlg_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let linear_rec2020 = rec2100_hlg_to_linear_rec2020(value);
+    linear_rec2020_to_xyz(&linear_rec2020)
+}
+
+/// Convert coordinates for XYZ to Rec. 2100 HLG. This is a two-hop
+/// conversion.
+#[inline]
+fn xyz_to_rec2100_hlg(value: &[Float; 3]) -> [Float; 3] {
+    let linear_rec2020 = xyz_to_linear_rec2020(value);
+    linear_rec2020_to_rec2100_hlg(&linear_rec2020)
+}
+
+/// Convert coordinates for JzCzhz to XYZ. This is a two-hop conversion.
+#[inline]
+fn jzczhz_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let jzazbz = okxch_to_okxab(value);
+    jzazbz_to_xyz(&jzazbz)
+}
+
+/// Convert coordinates for XYZ to JzCzhz. This is a two-hop conversion.
+#[inline]
+fn xyz_to_jzczhz(value: &[Float; 3]) -> [Float; 3] {
+    let jzazbz = xyz_to_jzazbz(value);
+    okxab_to_okxch(&jzazbz)
+}
+
 /// Convert coordinates for Oklch to XYZ. This is a two-hop conversion.
 #[inline]
 fn oklch_to_xyz(value: &[Float; 3]) -> [Float; 3] {
@@ -420,6 +1555,34 @@ fn xyz_to_oklrab(value: &[Float; 3]) -> [Float; 3] {
     oklxx_to_oklrxx(&oklab)
 }
 
+/// Convert coordinates for Okhsv to XYZ. This is a two-hop conversion.
+#[inline]
+fn okhsv_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let oklab = okhsv_to_oklab(value);
+    oklab_to_xyz(&oklab)
+}
+
+/// Convert coordinates for XYZ to Okhsv. This is a two-hop conversion.
+#[inline]
+fn xyz_to_okhsv(value: &[Float; 3]) -> [Float; 3] {
+    let oklab = xyz_to_oklab(value);
+    oklab_to_okhsv(&oklab)
+}
+
+/// Convert coordinates for Okhsl to XYZ. This is a two-hop conversion.
+#[inline]
+fn okhsl_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let oklab = okhsl_to_oklab(value);
+    oklab_to_xyz(&oklab)
+}
+
+/// Convert coordinates for XYZ to Okhsl. This is a two-hop conversion.
+#[inline]
+fn xyz_to_okhsl(value: &[Float; 3]) -> [Float; 3] {
+    let oklab = xyz_to_oklab(value);
+    oklab_to_okhsl(&oklab)
+}
+
 /// Convert coordinates for Oklab to Oklrch. This is a two-hop conversion.
 #[inline]
 fn oklab_to_oklrch(value: &[Float; 3]) -> [Float; 3] {
@@ -448,6 +1611,80 @@ fn oklch_to_oklrab(value: &[Float; 3]) -> [Float; 3] {
     oklxx_to_oklrxx(&oklab)
 }
 
+/// Convert coordinates for A98 RGB to XYZ. This is a two-hop conversion.
+#[inline]
+fn a98_rgb_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let linear_a98_rgb = a98_rgb_to_linear_a98_rgb(value);
+    linear_a98_rgb_to_xyz(&linear_a98_rgb)
+}
+
+/// Convert coordinates for XYZ to A98 RGB. This is a two-hop conversion.
+#[inline]
+fn xyz_to_a98_rgb(value: &[Float; 3]) -> [Float; 3] {
+    let linear_a98_rgb = xyz_to_linear_a98_rgb(value);
+    linear_a98_rgb_to_a98_rgb(&linear_a98_rgb)
+}
+
+/// Convert coordinates for ProPhoto RGB to XYZ D50. This is a two-hop
+/// conversion.
+#[inline]
+fn prophoto_rgb_to_xyz_d50(value: &[Float; 3]) -> [Float; 3] {
+    let linear_prophoto_rgb = prophoto_rgb_to_linear_prophoto_rgb(value);
+    linear_prophoto_rgb_to_xyz_d50(&linear_prophoto_rgb)
+}
+
+/// Convert coordinates for XYZ D50 to ProPhoto RGB. This is a two-hop
+/// conversion.
+#[inline]
+fn xyz_d50_to_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+    let linear_prophoto_rgb = xyz_d50_to_linear_prophoto_rgb(value);
+    linear_prophoto_rgb_to_prophoto_rgb(&linear_prophoto_rgb)
+}
+
+/// Convert coordinates for linear ProPhoto RGB to XYZ. This is a two-hop
+/// conversion.
+#[inline]
+fn linear_prophoto_rgb_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = linear_prophoto_rgb_to_xyz_d50(value);
+    xyz_d50_to_xyz(&xyz_d50)
+}
+
+/// Convert coordinates for XYZ to linear ProPhoto RGB. This is a two-hop
+/// conversion.
+#[inline]
+fn xyz_to_linear_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = xyz_to_xyz_d50(value);
+    xyz_d50_to_linear_prophoto_rgb(&xyz_d50)
+}
+
+/// Convert coordinates for CIELCh to XYZ D50. This is a two-hop conversion.
+#[inline]
+fn lch_to_xyz_d50(value: &[Float; 3]) -> [Float; 3] {
+    let lab = lch_to_lab(value);
+    lab_to_xyz_d50(&lab)
+}
+
+/// Convert coordinates for XYZ D50 to CIELCh. This is a two-hop conversion.
+#[inline]
+fn xyz_d50_to_lch(value: &[Float; 3]) -> [Float; 3] {
+    let lab = xyz_d50_to_lab(value);
+    lab_to_lch(&lab)
+}
+
+/// Convert coordinates for CIELAB to XYZ. This is a two-hop conversion.
+#[inline]
+fn lab_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = lab_to_xyz_d50(value);
+    xyz_d50_to_xyz(&xyz_d50)
+}
+
+/// Convert coordinates for XYZ to CIELAB. This is a two-hop conversion.
+#[inline]
+fn xyz_to_lab(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = xyz_to_xyz_d50(value);
+    xyz_d50_to_lab(&xyz_d50)
+}
+
 // --------------------------------------------------------------------------------------------------------------------
 
 /// Convert coordinates for Oklrch to XYZ. This is a three-hop conversion.
@@ -463,6 +1700,92 @@ fn xyz_to_oklrch(value: &[Float; 3]) -> [Float; 3] {
     oklxx_to_oklrxx(&oklch)
 }
 
+/// Convert coordinates for HSL to XYZ. This is a three-hop conversion.
+#[inline]
+fn hsl_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = hsl_to_srgb(value);
+    srgb_to_xyz(&srgb)
+}
+
+/// Convert coordinates for XYZ to HSL. This is a three-hop conversion.
+#[inline]
+fn xyz_to_hsl(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = xyz_to_srgb(value);
+    srgb_to_hsl(&srgb)
+}
+
+/// Convert coordinates for HSV to XYZ. This is a three-hop conversion.
+#[inline]
+fn hsv_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = hsv_to_srgb(value);
+    srgb_to_xyz(&srgb)
+}
+
+/// Convert coordinates for XYZ to HSV. This is a three-hop conversion.
+#[inline]
+fn xyz_to_hsv(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = xyz_to_srgb(value);
+    srgb_to_hsv(&srgb)
+}
+
+/// Convert coordinates for HWB to XYZ. This is a three-hop conversion.
+#[inline]
+fn hwb_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = hwb_to_srgb(value);
+    srgb_to_xyz(&srgb)
+}
+
+/// Convert coordinates for XYZ to HWB. This is a three-hop conversion.
+#[inline]
+fn xyz_to_hwb(value: &[Float; 3]) -> [Float; 3] {
+    let srgb = xyz_to_srgb(value);
+    srgb_to_hwb(&srgb)
+}
+
+/// Convert coordinates for ProPhoto RGB to XYZ. This is a three-hop
+/// conversion.
+#[inline]
+fn prophoto_rgb_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = prophoto_rgb_to_xyz_d50(value);
+    xyz_d50_to_xyz(&xyz_d50)
+}
+
+/// Convert coordinates for XYZ to ProPhoto RGB. This is a three-hop
+/// conversion.
+#[inline]
+fn xyz_to_prophoto_rgb(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = xyz_to_xyz_d50(value);
+    xyz_d50_to_prophoto_rgb(&xyz_d50)
+}
+
+/// Convert coordinates for CIELCh to XYZ. This is a three-hop conversion.
+#[inline]
+fn lch_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = lch_to_xyz_d50(value);
+    xyz_d50_to_xyz(&xyz_d50)
+}
+
+/// Convert coordinates for XYZ to CIELCh. This is a three-hop conversion.
+#[inline]
+fn xyz_to_lch(value: &[Float; 3]) -> [Float; 3] {
+    let xyz_d50 = xyz_to_xyz_d50(value);
+    xyz_d50_to_lch(&xyz_d50)
+}
+
+/// Convert coordinates for CAM16-UCS to XYZ, using the default viewing
+/// conditions. This is a one-hop, direct conversion.
+#[inline]
+fn cam16_ucs_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    default_conditions().cam16_ucs_to_xyz(value)
+}
+
+/// Convert coordinates for XYZ to CAM16-UCS, using the default viewing
+/// conditions. This is a one-hop, direct conversion.
+#[inline]
+fn xyz_to_cam16_ucs(value: &[Float; 3]) -> [Float; 3] {
+    default_conditions().xyz_to_cam16_ucs(value)
+}
+
 // --------------------------------------------------------------------------------------------------------------------
 
 /// Convert the coordinates from one color space to another.
@@ -498,6 +1821,48 @@ pub(crate) fn convert(
         // Single-hop Rec2020 conversions
         (Rec2020, LinearRec2020) => return rec2020_to_linear_rec2020(&coordinates),
         (LinearRec2020, Rec2020) => return linear_rec2020_to_rec2020(&coordinates),
+        (Rec2100Pq, LinearRec2020) => return rec2100_pq_to_linear_rec2020(&coordinates),
+        (LinearRec2020, Rec2100Pq) => return linear_rec2020_to_rec2100_pq(&coordinates),
+        (Rec2100Hlg, LinearRec2020) => return rec2100_hlg_to_linear_rec2020(&coordinates),
+        (LinearRec2020, Rec2100Hlg) => return linear_rec2020_to_rec2100_hlg(&coordinates),
+
+        // Multi-hop conversions through linear Rec2020
+        (Rec2020 | Rec2100Pq | Rec2100Hlg, Rec2020 | Rec2100Pq | Rec2100Hlg) => {
+            let linear_rec2020 = convert(from_space, LinearRec2020, &coordinates);
+            return convert(LinearRec2020, to_space, &linear_rec2020);
+        }
+
+        // Single-hop A98 RGB conversions
+        (A98Rgb, LinearA98Rgb) => return a98_rgb_to_linear_a98_rgb(&coordinates),
+        (LinearA98Rgb, A98Rgb) => return linear_a98_rgb_to_a98_rgb(&coordinates),
+
+        // Single-hop ProPhoto RGB conversions
+        (ProPhotoRgb, LinearProPhotoRgb) => {
+            return prophoto_rgb_to_linear_prophoto_rgb(&coordinates)
+        }
+        (LinearProPhotoRgb, ProPhotoRgb) => {
+            return linear_prophoto_rgb_to_prophoto_rgb(&coordinates)
+        }
+        (LinearProPhotoRgb, XyzD50) => return linear_prophoto_rgb_to_xyz_d50(&coordinates),
+        (XyzD50, LinearProPhotoRgb) => return xyz_d50_to_linear_prophoto_rgb(&coordinates),
+
+        // Two-hop ProPhoto RGB conversions
+        (ProPhotoRgb, XyzD50) => return prophoto_rgb_to_xyz_d50(&coordinates),
+        (XyzD50, ProPhotoRgb) => return xyz_d50_to_prophoto_rgb(&coordinates),
+
+        // Single-hop conversions between sRGB and its cylindrical transformations
+        (Srgb, Hsl) => return srgb_to_hsl(&coordinates),
+        (Hsl, Srgb) => return hsl_to_srgb(&coordinates),
+        (Srgb, Hsv) => return srgb_to_hsv(&coordinates),
+        (Hsv, Srgb) => return hsv_to_srgb(&coordinates),
+        (Srgb, Hwb) => return srgb_to_hwb(&coordinates),
+        (Hwb, Srgb) => return hwb_to_srgb(&coordinates),
+
+        // Multi-hop conversions through sRGB
+        (Hsl | Hsv | Hwb, LinearSrgb | Hsl | Hsv | Hwb) | (LinearSrgb, Hsl | Hsv | Hwb) => {
+            let srgb = convert(from_space, Srgb, &coordinates);
+            return convert(Srgb, to_space, &srgb);
+        }
 
         // Single-hop Oklab variation conversions
         (Oklch, Oklab) | (Oklrch, Oklrab) => return okxch_to_okxab(&coordinates),
@@ -505,11 +1870,47 @@ pub(crate) fn convert(
         (Oklab, Oklrab) | (Oklch, Oklrch) => return oklxx_to_oklrxx(&coordinates),
         (Oklrab, Oklab) | (Oklrch, Oklch) => return oklrxx_to_oklxx(&coordinates),
 
+        // Single-hop conversions between Oklab and Okhsv/Okhsl
+        (Oklab, Okhsv) => return oklab_to_okhsv(&coordinates),
+        (Okhsv, Oklab) => return okhsv_to_oklab(&coordinates),
+        (Oklab, Okhsl) => return oklab_to_okhsl(&coordinates),
+        (Okhsl, Oklab) => return okhsl_to_oklab(&coordinates),
+
+        // Multi-hop conversions through Oklab
+        (Okhsv | Okhsl, Oklch | Oklrab | Oklrch | Okhsv | Okhsl)
+        | (Oklch | Oklrab | Oklrch, Okhsv | Okhsl) => {
+            let oklab = convert(from_space, Oklab, &coordinates);
+            return convert(Oklab, to_space, &oklab);
+        }
+
         // Two-hop Oklab variation conversions
         (Oklrch, Oklab) => return oklrch_to_oklab(&coordinates),
         (Oklch, Oklrab) => return oklch_to_oklrab(&coordinates),
         (Oklab, Oklrch) => return oklab_to_oklrch(&coordinates),
         (Oklrab, Oklch) => return oklrab_to_oklch(&coordinates),
+
+        // Single-hop CIELAB variation conversions
+        (XyzD50, Lab) => return xyz_d50_to_lab(&coordinates),
+        (Lab, XyzD50) => return lab_to_xyz_d50(&coordinates),
+        (Lab, Lch) => return lab_to_lch(&coordinates),
+        (Lch, Lab) => return lch_to_lab(&coordinates),
+
+        // Two-hop CIELAB variation conversions
+        (XyzD50, Lch) => return xyz_d50_to_lch(&coordinates),
+        (Lch, XyzD50) => return lch_to_xyz_d50(&coordinates),
+
+        // Single-hop Jzazbz variation conversions
+        (Jzazbz, Jzczhz) => return okxab_to_okxch(&coordinates),
+        (Jzczhz, Jzazbz) => return okxch_to_okxab(&coordinates),
+
+        // Multi-hop conversions through XYZ D50
+        (
+            Lab | Lch | ProPhotoRgb | LinearProPhotoRgb,
+            Lab | Lch | ProPhotoRgb | LinearProPhotoRgb,
+        ) => {
+            let xyz_d50 = convert(from_space, XyzD50, &coordinates);
+            return convert(XyzD50, to_space, &xyz_d50);
+        }
         _ => (),
     };
 
@@ -521,11 +1922,30 @@ pub(crate) fn convert(
         LinearDisplayP3 => linear_display_p3_to_xyz(&coordinates),
         Rec2020 => rec2020_to_xyz(&coordinates),
         LinearRec2020 => linear_rec2020_to_xyz(&coordinates),
+        Rec2100Pq => rec2100_pq_to_xyz(&coordinates),
+        Rec2100Hlg => rec2100_hlg_to_xyz(&coordinates),
+        A98Rgb => a98_rgb_to_xyz(&coordinates),
+        LinearA98Rgb => linear_a98_rgb_to_xyz(&coordinates),
+        ProPhotoRgb => prophoto_rgb_to_xyz(&coordinates),
+        LinearProPhotoRgb => linear_prophoto_rgb_to_xyz(&coordinates),
+        Hsl => hsl_to_xyz(&coordinates),
+        Hsv => hsv_to_xyz(&coordinates),
+        Hwb => hwb_to_xyz(&coordinates),
         Oklch => oklch_to_xyz(&coordinates),
         Oklab => oklab_to_xyz(&coordinates),
         Oklrch => oklrch_to_xyz(&coordinates),
         Oklrab => oklrab_to_xyz(&coordinates),
+        Okhsv => okhsv_to_xyz(&coordinates),
+        Okhsl => okhsl_to_xyz(&coordinates),
+        Lab => lab_to_xyz(&coordinates),
+        Lch => lch_to_xyz(&coordinates),
+        Cam16Ucs => cam16_ucs_to_xyz(&coordinates),
+        Hct => hct_to_xyz(&coordinates),
+        Ictcp => ictcp_to_xyz(&coordinates),
+        Jzazbz => jzazbz_to_xyz(&coordinates),
+        Jzczhz => jzczhz_to_xyz(&coordinates),
         Xyz => coordinates,
+        XyzD50 => xyz_d50_to_xyz(&coordinates),
     };
 
     // 3b. Convert from root XYZ to target color space on different branch
@@ -536,11 +1956,30 @@ pub(crate) fn convert(
         LinearDisplayP3 => xyz_to_linear_display_p3(&intermediate),
         Rec2020 => xyz_to_rec2020(&intermediate),
         LinearRec2020 => xyz_to_linear_rec2020(&intermediate),
+        Rec2100Pq => xyz_to_rec2100_pq(&intermediate),
+        Rec2100Hlg => xyz_to_rec2100_hlg(&intermediate),
+        A98Rgb => xyz_to_a98_rgb(&intermediate),
+        LinearA98Rgb => xyz_to_linear_a98_rgb(&intermediate),
+        ProPhotoRgb => xyz_to_prophoto_rgb(&intermediate),
+        LinearProPhotoRgb => xyz_to_linear_prophoto_rgb(&intermediate),
+        Hsl => xyz_to_hsl(&intermediate),
+        Hsv => xyz_to_hsv(&intermediate),
+        Hwb => xyz_to_hwb(&intermediate),
         Oklch => xyz_to_oklch(&intermediate),
         Oklab => xyz_to_oklab(&intermediate),
         Oklrch => xyz_to_oklrch(&intermediate),
         Oklrab => xyz_to_oklrab(&intermediate),
+        Okhsv => xyz_to_okhsv(&intermediate),
+        Okhsl => xyz_to_okhsl(&intermediate),
+        Lab => xyz_to_lab(&intermediate),
+        Lch => xyz_to_lch(&intermediate),
+        Cam16Ucs => xyz_to_cam16_ucs(&intermediate),
+        Hct => xyz_to_hct(&intermediate),
+        Ictcp => xyz_to_ictcp(&intermediate),
+        Jzazbz => xyz_to_jzazbz(&intermediate),
+        Jzczhz => xyz_to_jzczhz(&intermediate),
         Xyz => intermediate,
+        XyzD50 => xyz_to_xyz_d50(&intermediate),
     }
 }
 
@@ -558,11 +1997,42 @@ mod test {
         linear_p3: [Float; 3],
         rec2020: [Float; 3],
         linear_rec2020: [Float; 3],
+        a98_rgb: [Float; 3],
+        linear_a98_rgb: [Float; 3],
+        prophoto_rgb: [Float; 3],
+        linear_prophoto_rgb: [Float; 3],
+        hsl: [Float; 3],
+        hsv: [Float; 3],
+        hwb: [Float; 3],
         oklch: [Float; 3],
         oklab: [Float; 3],
         oklrch: [Float; 3],
         oklrab: [Float; 3],
+        okhsv: [Float; 3],
+        okhsl: [Float; 3],
+        lab: [Float; 3],
+        lch: [Float; 3],
         xyz: [Float; 3],
+        xyz_d50: [Float; 3],
+    }
+
+    /// Scale CIELAB/CIELCh coordinates (but not hues) down to unit range, so
+    /// that comparisons have the same precision as for other color spaces.
+    fn percent(coordinates: &[Float; 3], is_polar: bool) -> [Float; 3] {
+        let [c1, c2, c3] = *coordinates;
+        [
+            c1 / 100.0,
+            c2 / 100.0,
+            if is_polar { c3 } else { c3 / 100.0 },
+        ]
+    }
+
+    /// Move the hue of HSL, HSV, HWB, Okhsv, or Okhsl coordinates to the end
+    /// and scale the other two coordinates down to unit range, so that
+    /// comparisons treat the hue as hue.
+    fn hue_last(coordinates: &[Float; 3], range: Float) -> [Float; 3] {
+        let [h, c2, c3] = *coordinates;
+        [c2 / range, c3 / range, h]
     }
 
     const BLACK: Representations = Representations {
@@ -573,11 +2043,23 @@ mod test {
         linear_p3: [0.0, 0.0, 0.0],
         rec2020: [0.0, 0.0, 0.0],
         linear_rec2020: [0.0, 0.0, 0.0],
+        a98_rgb: [0.0, 0.0, 0.0],
+        linear_a98_rgb: [0.0, 0.0, 0.0],
+        prophoto_rgb: [0.0, 0.0, 0.0],
+        linear_prophoto_rgb: [0.0, 0.0, 0.0],
+        hsl: [Float::NAN, 0.0, 0.0],
+        hsv: [Float::NAN, 0.0, 0.0],
+        hwb: [Float::NAN, 0.0, 100.0],
         oklch: [0.0, 0.0, Float::NAN],
         oklab: [0.0, 0.0, 0.0],
         oklrch: [0.0, 0.0, Float::NAN],
         oklrab: [0.0, 0.0, 0.0],
+        okhsv: [Float::NAN, 0.0, 0.0],
+        okhsl: [Float::NAN, 0.0, 0.0],
+        lab: [0.0, 0.0, 0.0],
+        lch: [0.0, 0.0, Float::NAN],
         xyz: [0.0, 0.0, 0.0],
+        xyz_d50: [0.0, 0.0, 0.0],
     };
 
     const YELLOW: Representations = Representations {
@@ -588,6 +2070,13 @@ mod test {
         linear_p3: [0.9273192749713864, 0.6042079205196976, 0.059841923211596565],
         rec2020: [0.9071245864481046, 0.7821891940186851, 0.22941491945066222],
         linear_rec2020: [0.8218846623958427, 0.6121951716762088, 0.0683737567590739],
+        a98_rgb: [0.9451754434731714, 0.7870672536860406, 0.18450729546430608],
+        linear_a98_rgb: [0.8833777907421553, 0.5906188409193368, 0.024311022283590403],
+        prophoto_rgb: [0.8359258543208741, 0.7628086317797692, 0.25649724851292954],
+        linear_prophoto_rgb: [0.72427244690545, 0.6142541195745461, 0.08636720779258501],
+        hsl: [47.52941176470588, 100.0, 50.0],
+        hsv: [47.52941176470588, 100.0, 100.0],
+        hwb: [47.52941176470588, 0.0, 0.0],
         oklch: [0.8613332073307732, 0.1760097742886813, 89.440876452466],
         oklab: [
             0.8613332073307732,
@@ -600,7 +2089,12 @@ mod test {
             0.0017175723640959761,
             0.17600139371700052,
         ],
+        okhsv: [89.44087645246603, 0.999999909215475, 0.9999999999999994],
+        okhsl: [89.44087645246596, 1.0000000000000007, 0.8385912822460642],
+        lab: [84.27202490523537, 9.217455990603362, 84.47723289078787],
+        lch: [84.27202490523537, 84.9786112608527, 83.77299271233113],
         xyz: [0.6235868473237722, 0.635031101987136, 0.08972950140152941],
+        xyz_d50: [0.6635434850467424, 0.6459001538545541, 0.07126198065563918],
     };
 
     const BLUE: Representations = Representations {
@@ -615,6 +2109,13 @@ mod test {
         linear_p3: [0.058605969547446124, 0.18260572039525869, 0.763285235993837],
         rec2020: [0.318905170074285, 0.4141244051667745, 0.8687817570254107],
         linear_rec2020: [0.11675330225613656, 0.18417975425846383, 0.7539171810709095],
+        a98_rgb: [0.30882637791523676, 0.4674834000180614, 0.9017902112180911],
+        linear_a98_rgb: [0.07546929861724672, 0.1878207723006778, 0.7966493585356339],
+        prophoto_rgb: [0.40201218688921075, 0.39776289024699957, 0.8425969056359907],
+        linear_prophoto_rgb: [0.19392356831230187, 0.19024956515769442, 0.7347096562529328],
+        hsl: [216.97297297297297, 81.49779735682819, 55.490196078431374],
+        hsv: [216.97297297297297, 79.05982905982906, 91.76470588235294],
+        hwb: [216.97297297297297, 19.215686274509807, 8.235294117647063],
         oklch: [0.5909012953108558, 0.18665606306724153, 259.66681920272595],
         oklab: [
             0.5909012953108558,
@@ -627,7 +2128,12 @@ mod test {
             -0.03348086515869664,
             -0.1836287492414715,
         ],
+        okhsv: [259.66681920272583, 0.8506257951662151, 0.9214055890846797],
+        okhsl: [259.66681920272595, 0.9074508875465639, 0.5253778775789848],
+        lab: [50.84558268299661, 9.53423070844478, -65.21770438095244],
+        lch: [50.84558268299661, 65.91092868351313, 278.3172007684866],
         xyz: [0.22832473003420622, 0.20025321836938534, 0.80506528557483],
+        xyz_d50: [0.20345542047334936, 0.1913569200613913, 0.6062123188831731],
     };
 
     const WHITE: Representations = Representations {
@@ -638,9 +2144,21 @@ mod test {
         linear_p3: [1.0, 0.9999999999999998, 1.0],
         rec2020: [1.0000000000000002, 1.0, 1.0],
         linear_rec2020: [1.0000000000000004, 1.0, 0.9999999999999999],
+        a98_rgb: [1.0000000000000002, 0.9999999999999999, 1.0],
+        linear_a98_rgb: [1.0000000000000004, 0.9999999999999998, 1.0],
+        prophoto_rgb: [1.0000000000000002, 0.9999999999999999, 1.0],
+        linear_prophoto_rgb: [1.0000000000000002, 0.9999999999999999, 1.0],
+        hsl: [Float::NAN, 0.0, 100.0],
+        hsv: [Float::NAN, 0.0, 100.0],
+        hwb: [Float::NAN, 100.0, 0.0],
         oklch: [1.0000000000000002, 0.0, Float::NAN],
         oklab: [1.0000000000000002, -4.996003610813204e-16, 0.0],
+        okhsv: [Float::NAN, 0.0, 1.0000000000000002],
+        okhsl: [Float::NAN, 0.0, 1.0],
+        lab: [100.0, 0.0, 0.0],
+        lch: [100.0, 0.0, Float::NAN],
         xyz: [0.9504559270516717, 1.0, 1.0890577507598784],
+        xyz_d50: [0.9642956764295678, 1.0, 0.8251046025104604],
         oklrch: [1.0000000000000002, 0.0, Float::NAN],
         oklrab: [1.0000000000000002, 0.0, 0.0],
     };
@@ -708,6 +2226,153 @@ mod test {
 
             let oklch_too = oklrxx_to_oklxx(&oklrch);
             assert!(close_enough(&oklch_too, &color.oklch, true));
+
+            let linear_a98_rgb = xyz_to_linear_a98_rgb(&xyz);
+            assert!(close_enough(&linear_a98_rgb, &color.linear_a98_rgb, false));
+
+            let xyz_too = linear_a98_rgb_to_xyz(&linear_a98_rgb);
+            assert!(close_enough(&xyz_too, &xyz, false));
+
+            let a98_rgb = linear_a98_rgb_to_a98_rgb(&linear_a98_rgb);
+            assert!(close_enough(&a98_rgb, &color.a98_rgb, false));
+
+            let linear_a98_rgb_too = a98_rgb_to_linear_a98_rgb(&a98_rgb);
+            assert!(close_enough(&linear_a98_rgb_too, &linear_a98_rgb, false));
+
+            let hsl = srgb_to_hsl(&color.srgb);
+            assert!(close_enough(
+                &hue_last(&hsl, 100.0),
+                &hue_last(&color.hsl, 100.0),
+                true
+            ));
+
+            let srgb_again = hsl_to_srgb(&normalize(ColorSpace::Hsl, &hsl));
+            assert!(close_enough(&srgb_again, &color.srgb, false));
+
+            let hsv = srgb_to_hsv(&color.srgb);
+            assert!(close_enough(
+                &hue_last(&hsv, 100.0),
+                &hue_last(&color.hsv, 100.0),
+                true
+            ));
+
+            let srgb_again = hsv_to_srgb(&normalize(ColorSpace::Hsv, &hsv));
+            assert!(close_enough(&srgb_again, &color.srgb, false));
+
+            let hwb = srgb_to_hwb(&color.srgb);
+            assert!(close_enough(
+                &hue_last(&hwb, 100.0),
+                &hue_last(&color.hwb, 100.0),
+                true
+            ));
+
+            let srgb_again = hwb_to_srgb(&normalize(ColorSpace::Hwb, &hwb));
+            assert!(close_enough(&srgb_again, &color.srgb, false));
+
+            let okhsv = oklab_to_okhsv(&oklab);
+            assert!(close_enough(
+                &hue_last(&okhsv, 1.0),
+                &hue_last(&color.okhsv, 1.0),
+                true
+            ));
+
+            let oklab_again = okhsv_to_oklab(&normalize(ColorSpace::Okhsv, &okhsv));
+            assert!(close_enough(&oklab_again, &oklab, false));
+
+            let okhsl = oklab_to_okhsl(&oklab);
+            assert!(close_enough(
+                &hue_last(&okhsl, 1.0),
+                &hue_last(&color.okhsl, 1.0),
+                true
+            ));
+
+            let oklab_again = okhsl_to_oklab(&normalize(ColorSpace::Okhsl, &okhsl));
+            assert!(close_enough(&oklab_again, &oklab, false));
+
+            let xyz_d50 = xyz_to_xyz_d50(&xyz);
+            assert!(close_enough(&xyz_d50, &color.xyz_d50, false));
+
+            let xyz_again = xyz_d50_to_xyz(&xyz_d50);
+            assert!(close_enough(&xyz_again, &xyz, false));
+
+            let linear_prophoto_rgb = xyz_d50_to_linear_prophoto_rgb(&xyz_d50);
+            assert!(close_enough(
+                &linear_prophoto_rgb,
+                &color.linear_prophoto_rgb,
+                false
+            ));
+
+            let xyz_d50_too = linear_prophoto_rgb_to_xyz_d50(&linear_prophoto_rgb);
+            assert!(close_enough(&xyz_d50_too, &xyz_d50, false));
+
+            let prophoto_rgb = linear_prophoto_rgb_to_prophoto_rgb(&linear_prophoto_rgb);
+            assert!(close_enough(&prophoto_rgb, &color.prophoto_rgb, false));
+
+            let linear_prophoto_rgb_too = prophoto_rgb_to_linear_prophoto_rgb(&prophoto_rgb);
+            let xyz_d50_again = linear_prophoto_rgb_to_xyz_d50(&linear_prophoto_rgb_too);
+            assert!(close_enough(&xyz_d50_again, &xyz_d50, false));
+
+            let lab = xyz_d50_to_lab(&xyz_d50);
+            assert!(close_enough(
+                &percent(&lab, false),
+                &percent(&color.lab, false),
+                false
+            ));
+
+            let xyz_d50_again = lab_to_xyz_d50(&lab);
+            assert!(close_enough(&xyz_d50_again, &xyz_d50, false));
+
+            let lch = lab_to_lch(&lab);
+            assert!(close_enough(
+                &percent(&lch, true),
+                &percent(&color.lch, true),
+                true
+            ));
+
+            let lab_again = lch_to_lab(&lch);
+            assert!(close_enough(
+                &percent(&lab_again, false),
+                &percent(&lab, false),
+                false
+            ));
+        }
+    }
+
+    #[test]
+    fn test_hdr_conversions() {
+        fn assert_close(actual: &[Float; 3], expected: &[Float; 3], tolerance: Float) {
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!((a - e).abs() < tolerance, "{:?} vs {:?}", actual, expected);
+            }
+        }
+
+        // Media white is the same in all HDR color spaces.
+        let white = [1.0, 1.0, 1.0];
+        let pq = linear_rec2020_to_rec2100_pq(&white);
+        assert_close(&pq, &[0.5806888810416109; 3], 1e-12);
+        let hlg = linear_rec2020_to_rec2100_hlg(&white);
+        assert_close(&hlg, &[0.75; 3], 1e-4);
+
+        let xyz = linear_rec2020_to_xyz(&white);
+        let ictcp = xyz_to_ictcp(&xyz);
+        assert_close(&ictcp, &[0.5806888810416109, 0.0, 0.0], 1e-12);
+        let jzazbz = xyz_to_jzazbz(&xyz);
+        assert_close(&jzazbz, &[0.22206524953574297, -0.00016, -0.00012], 1e-5);
+
+        for color in [&YELLOW, &BLUE] {
+            let xyz = color.xyz;
+            assert_close(&rec2100_pq_to_xyz(&xyz_to_rec2100_pq(&xyz)), &xyz, 1e-12);
+            assert_close(&rec2100_hlg_to_xyz(&xyz_to_rec2100_hlg(&xyz)), &xyz, 1e-12);
+            assert_close(&ictcp_to_xyz(&xyz_to_ictcp(&xyz)), &xyz, 1e-12);
+            assert_close(&jzazbz_to_xyz(&xyz_to_jzazbz(&xyz)), &xyz, 1e-12);
+            assert_close(&jzczhz_to_xyz(&xyz_to_jzczhz(&xyz)), &xyz, 1e-12);
+
+            let rec2020 = convert(
+                ColorSpace::Rec2100Pq,
+                ColorSpace::Rec2020,
+                &xyz_to_rec2100_pq(&xyz),
+            );
+            assert_close(&rec2020, &color.rec2020, 1e-12);
         }
     }
 }
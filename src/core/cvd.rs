@@ -0,0 +1,520 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use crate::core::conversion::multiply;
+use crate::core::{clip, convert, to_gamut};
+use crate::{ColorSpace, Float};
+
+/// A color vision deficiency.
+///
+/// Each variant names the dichromacy, i.e., the complete absence of one cone
+/// type. Anomalous trichromacy, i.e., the partial loss of a cone type's
+/// sensitivity, is modelled by a severity less than 1.
+///
+/// # Protanopia
+///
+/// Protanopia is the absence of long-wavelength (L) cones. It makes reds
+/// appear darker and harder to distinguish from greens.
+///
+/// # Deuteranopia
+///
+/// Deuteranopia is the absence of medium-wavelength (M) cones. It is the most
+/// common color vision deficiency and also confuses reds and greens, though
+/// without darkening reds.
+///
+/// # Tritanopia
+///
+/// Tritanopia is the absence of short-wavelength (S) cones. It is rare and
+/// confuses blues with greens and yellows with violets.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+/// A method for simulating color vision deficiencies.
+///
+/// Both methods operate on linear sRGB, which makes them inexpensive, and
+/// produce very similar results for protanopia and deuteranopia.
+///
+/// # Machado
+///
+/// The method by [Machado, Oliveira, and
+/// Fernandes](https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html)
+/// (2009) uses a single matrix per deficiency and severity. It models
+/// anomalous trichromacy by shifting the affected cone's spectral
+/// sensitivity. This method interpolates between the paper's matrices, which
+/// are tabulated in steps of 0.1 severity. It is the less accurate method for
+/// tritanopia.
+///
+/// # Brettel
+///
+/// The method by [Brettel, Viénot, and
+/// Mollon](https://doi.org/10.1364/JOSAA.14.002647) (1997) projects colors
+/// onto two half-planes in LMS that represent the colors seen by dichromats.
+/// This method uses the precomputed parameters for sRGB from
+/// [libDaltonLens](https://github.com/DaltonLens/libDaltonLens). It models
+/// anomalous trichromacy by interpolating between the original and the
+/// dichromat's color.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CvdMethod {
+    #[default]
+    Machado,
+    Brettel,
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+type Matrix = [[Float; 3]; 3];
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+// Machado et al.'s matrices for severities 0.1, 0.2, ..., 1.0. The matrix for
+// severity 0.0 is the identity.
+const MACHADO_PROTAN: [Matrix; 10] = [
+    [
+        [0.856167, 0.182038, -0.038205],
+        [0.029342, 0.955115, 0.015544],
+        [-0.002880, -0.001563, 1.004443],
+    ],
+    [
+        [0.734766, 0.334872, -0.069637],
+        [0.051840, 0.919198, 0.028963],
+        [-0.004928, -0.004209, 1.009137],
+    ],
+    [
+        [0.630323, 0.465641, -0.095964],
+        [0.069181, 0.890046, 0.040773],
+        [-0.006308, -0.007724, 1.014032],
+    ],
+    [
+        [0.539009, 0.579343, -0.118352],
+        [0.082546, 0.866121, 0.051332],
+        [-0.007136, -0.011959, 1.019095],
+    ],
+    [
+        [0.458064, 0.679578, -0.137642],
+        [0.092785, 0.846313, 0.060902],
+        [-0.007494, -0.016807, 1.024301],
+    ],
+    [
+        [0.385450, 0.769005, -0.154455],
+        [0.100526, 0.829802, 0.069673],
+        [-0.007442, -0.022190, 1.029632],
+    ],
+    [
+        [0.319627, 0.849633, -0.169261],
+        [0.106241, 0.815969, 0.077790],
+        [-0.007025, -0.028051, 1.035076],
+    ],
+    [
+        [0.259411, 0.923008, -0.182420],
+        [0.110296, 0.804340, 0.085364],
+        [-0.006276, -0.034346, 1.040622],
+    ],
+    [
+        [0.203876, 0.990338, -0.194214],
+        [0.112975, 0.794542, 0.092483],
+        [-0.005222, -0.041043, 1.046265],
+    ],
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+];
+
+const MACHADO_DEUTAN: [Matrix; 10] = [
+    [
+        [0.866435, 0.177704, -0.044139],
+        [0.049567, 0.939063, 0.011370],
+        [-0.003453, 0.007233, 0.996220],
+    ],
+    [
+        [0.760729, 0.319078, -0.079807],
+        [0.090568, 0.889315, 0.020117],
+        [-0.006027, 0.013325, 0.992702],
+    ],
+    [
+        [0.675425, 0.433850, -0.109275],
+        [0.125303, 0.847755, 0.026942],
+        [-0.007950, 0.018572, 0.989378],
+    ],
+    [
+        [0.605511, 0.528560, -0.134071],
+        [0.155318, 0.812366, 0.032316],
+        [-0.009376, 0.023176, 0.986200],
+    ],
+    [
+        [0.547494, 0.607765, -0.155259],
+        [0.181692, 0.781742, 0.036566],
+        [-0.010410, 0.027275, 0.983136],
+    ],
+    [
+        [0.498864, 0.674741, -0.173604],
+        [0.205199, 0.754872, 0.039929],
+        [-0.011131, 0.030969, 0.980162],
+    ],
+    [
+        [0.457771, 0.731899, -0.189670],
+        [0.226409, 0.731012, 0.042579],
+        [-0.011595, 0.034333, 0.977261],
+    ],
+    [
+        [0.422823, 0.781057, -0.203881],
+        [0.245752, 0.709602, 0.044646],
+        [-0.011843, 0.037423, 0.974421],
+    ],
+    [
+        [0.392952, 0.823610, -0.216562],
+        [0.263559, 0.690210, 0.046232],
+        [-0.011910, 0.040281, 0.971630],
+    ],
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+];
+
+const MACHADO_TRITAN: [Matrix; 10] = [
+    [
+        [0.926670, 0.092514, -0.019184],
+        [0.021191, 0.964503, 0.014306],
+        [0.008437, 0.054813, 0.936750],
+    ],
+    [
+        [0.895720, 0.133330, -0.029050],
+        [0.029997, 0.945400, 0.024603],
+        [0.013027, 0.104707, 0.882266],
+    ],
+    [
+        [0.905871, 0.127791, -0.033662],
+        [0.026856, 0.941251, 0.031893],
+        [0.013410, 0.148296, 0.838294],
+    ],
+    [
+        [0.948035, 0.089490, -0.037526],
+        [0.014364, 0.946792, 0.038844],
+        [0.010853, 0.193991, 0.795156],
+    ],
+    [
+        [1.017277, 0.027029, -0.044306],
+        [-0.006113, 0.958479, 0.047634],
+        [0.006379, 0.248708, 0.744913],
+    ],
+    [
+        [1.104996, -0.046633, -0.058363],
+        [-0.032137, 0.971635, 0.060503],
+        [0.001336, 0.317922, 0.680742],
+    ],
+    [
+        [1.193214, -0.109812, -0.083402],
+        [-0.058496, 0.979410, 0.079086],
+        [-0.002346, 0.403492, 0.598854],
+    ],
+    [
+        [1.257728, -0.139648, -0.118081],
+        [-0.078003, 0.975409, 0.102594],
+        [-0.003316, 0.501214, 0.502102],
+    ],
+    [
+        [1.278864, -0.125333, -0.153531],
+        [-0.084748, 0.957674, 0.127074],
+        [-0.000989, 0.601151, 0.399838],
+    ],
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+/// Determine Machado et al.'s matrix for the deficiency and severity.
+fn machado_matrix(deficiency: ColorVisionDeficiency, severity: Float) -> Matrix {
+    let table = match deficiency {
+        ColorVisionDeficiency::Protanopia => &MACHADO_PROTAN,
+        ColorVisionDeficiency::Deuteranopia => &MACHADO_DEUTAN,
+        ColorVisionDeficiency::Tritanopia => &MACHADO_TRITAN,
+    };
+
+    let position = severity * 10.0;
+    let index = (position.floor() as usize).min(9);
+    let fraction = position - index as Float;
+
+    let lower = if index == 0 {
+        &IDENTITY
+    } else {
+        &table[index - 1]
+    };
+    let upper = &table[index];
+
+    let mut matrix = [[0.0; 3]; 3];
+    for row in 0..3 {
+        for column in 0..3 {
+            let start = lower[row][column];
+            matrix[row][column] = start + (upper[row][column] - start) * fraction;
+        }
+    }
+    matrix
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The parameters for Brettel et al.'s method in linear sRGB: The matrices for
+/// the two half-planes and the normal of the plane separating them.
+struct BrettelParameters {
+    matrix1: Matrix,
+    matrix2: Matrix,
+    normal: [Float; 3],
+}
+
+const BRETTEL_PROTAN: BrettelParameters = BrettelParameters {
+    matrix1: [
+        [0.14980, 1.19548, -0.34528],
+        [0.10764, 0.84864, 0.04372],
+        [0.00384, -0.00540, 1.00156],
+    ],
+    matrix2: [
+        [0.14570, 1.16172, -0.30742],
+        [0.10816, 0.85291, 0.03892],
+        [0.00386, -0.00524, 1.00139],
+    ],
+    normal: [0.00048, 0.00393, -0.00441],
+};
+
+const BRETTEL_DEUTAN: BrettelParameters = BrettelParameters {
+    matrix1: [
+        [0.36477, 0.86381, -0.22858],
+        [0.26294, 0.64245, 0.09462],
+        [-0.02006, 0.02728, 0.99278],
+    ],
+    matrix2: [
+        [0.37298, 0.88166, -0.25464],
+        [0.25954, 0.63506, 0.10540],
+        [-0.01980, 0.02784, 0.99196],
+    ],
+    normal: [-0.00281, -0.00611, 0.00892],
+};
+
+const BRETTEL_TRITAN: BrettelParameters = BrettelParameters {
+    matrix1: [
+        [1.01277, 0.13548, -0.14826],
+        [-0.01243, 0.86812, 0.14431],
+        [0.07589, 0.80500, 0.11911],
+    ],
+    matrix2: [
+        [0.93678, 0.18979, -0.12657],
+        [0.06154, 0.81526, 0.12320],
+        [-0.37562, 1.12767, 0.24796],
+    ],
+    normal: [0.03901, -0.02788, -0.01113],
+};
+
+/// Simulate the dichromacy with Brettel et al.'s method.
+fn brettel(deficiency: ColorVisionDeficiency, linear: &[Float; 3]) -> [Float; 3] {
+    let parameters = match deficiency {
+        ColorVisionDeficiency::Protanopia => &BRETTEL_PROTAN,
+        ColorVisionDeficiency::Deuteranopia => &BRETTEL_DEUTAN,
+        ColorVisionDeficiency::Tritanopia => &BRETTEL_TRITAN,
+    };
+
+    let [n1, n2, n3] = parameters.normal;
+    let side = n1.mul_add(linear[0], n2.mul_add(linear[1], n3 * linear[2]));
+    if side >= 0.0 {
+        multiply(&parameters.matrix1, linear)
+    } else {
+        multiply(&parameters.matrix2, linear)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Simulate the color vision deficiency on the linear sRGB coordinates.
+fn simulate_linear(
+    deficiency: ColorVisionDeficiency,
+    method: CvdMethod,
+    severity: Float,
+    linear: &[Float; 3],
+) -> [Float; 3] {
+    match method {
+        CvdMethod::Machado => multiply(&machado_matrix(deficiency, severity), linear),
+        CvdMethod::Brettel => {
+            let [r1, g1, b1] = *linear;
+            let [r2, g2, b2] = brettel(deficiency, linear);
+            [
+                r1 + (r2 - r1) * severity,
+                g1 + (g2 - g1) * severity,
+                b1 + (b2 - b1) * severity,
+            ]
+        }
+    }
+}
+
+/// Convert the coordinates to gamut-mapped linear sRGB.
+fn to_linear_srgb(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
+    let srgb = to_gamut(
+        ColorSpace::Srgb,
+        &convert(space, ColorSpace::Srgb, coordinates),
+    );
+    convert(ColorSpace::Srgb, ColorSpace::LinearSrgb, &srgb)
+}
+
+/// Convert the linear sRGB coordinates to clipped sRGB.
+fn from_linear_srgb(linear: &[Float; 3]) -> [Float; 3] {
+    convert(
+        ColorSpace::LinearSrgb,
+        ColorSpace::Srgb,
+        &clip(ColorSpace::LinearSrgb, linear),
+    )
+}
+
+/// Simulate how a person with the color vision deficiency perceives the color.
+///
+/// This function gamut-maps the coordinates to sRGB, applies the simulation in
+/// linear sRGB, and returns the clipped result as sRGB coordinates. It clamps
+/// the severity to `0..=1`.
+pub(crate) fn simulate_cvd(
+    deficiency: ColorVisionDeficiency,
+    method: CvdMethod,
+    severity: Float,
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+) -> [Float; 3] {
+    let severity = severity.clamp(0.0, 1.0);
+    let linear = to_linear_srgb(space, coordinates);
+    from_linear_srgb(&simulate_linear(deficiency, method, severity, &linear))
+}
+
+/// Daltonize the color for a person with the color vision deficiency.
+///
+/// Following Fidaner, Lin, and Ozguven, this function computes the error
+/// between the color and its simulation, i.e., the information lost to the
+/// deficiency, and redistributes that error onto the channels still perceived.
+/// For protanopia and deuteranopia, it shifts the error into green and blue;
+/// for tritanopia, it shifts the error into red and green. The result is
+/// returned as clipped sRGB coordinates.
+pub(crate) fn daltonize(
+    deficiency: ColorVisionDeficiency,
+    method: CvdMethod,
+    severity: Float,
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+) -> [Float; 3] {
+    const RED_GREEN_SHIFT: Matrix = [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]];
+    const BLUE_YELLOW_SHIFT: Matrix = [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]];
+
+    let severity = severity.clamp(0.0, 1.0);
+    let linear = to_linear_srgb(space, coordinates);
+    let simulated = simulate_linear(deficiency, method, severity, &linear);
+    let error = [
+        linear[0] - simulated[0],
+        linear[1] - simulated[1],
+        linear[2] - simulated[2],
+    ];
+
+    let shift = match deficiency {
+        ColorVisionDeficiency::Protanopia | ColorVisionDeficiency::Deuteranopia => &RED_GREEN_SHIFT,
+        ColorVisionDeficiency::Tritanopia => &BLUE_YELLOW_SHIFT,
+    };
+    let [dr, dg, db] = multiply(shift, &error);
+
+    from_linear_srgb(&[linear[0] + dr, linear[1] + dg, linear[2] + db])
+}
+
+// ====================================================================================================================
+
+#[cfg(test)]
+mod test {
+    use super::{
+        daltonize, machado_matrix, simulate_cvd, ColorVisionDeficiency, CvdMethod, IDENTITY,
+        MACHADO_DEUTAN, MACHADO_PROTAN, MACHADO_TRITAN,
+    };
+    use crate::core::convert;
+    use crate::core::{delta_e_ok, test_util::close_enough, ColorSpace};
+
+    #[test]
+    fn test_machado_matrices() {
+        use ColorVisionDeficiency::*;
+
+        for deficiency in [Protanopia, Deuteranopia, Tritanopia] {
+            assert_eq!(machado_matrix(deficiency, 0.0), IDENTITY);
+        }
+        assert_eq!(machado_matrix(Protanopia, 1.0), MACHADO_PROTAN[9]);
+        assert_eq!(machado_matrix(Deuteranopia, 1.0), MACHADO_DEUTAN[9]);
+        assert_eq!(machado_matrix(Tritanopia, 1.0), MACHADO_TRITAN[9]);
+
+        // All matrices preserve white, i.e., their rows sum to one.
+        for table in [&MACHADO_PROTAN, &MACHADO_DEUTAN, &MACHADO_TRITAN] {
+            for matrix in table.iter() {
+                for row in matrix.iter() {
+                    assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-5);
+                }
+            }
+        }
+
+        // Interpolation halfway between two tabulated severities.
+        let matrix = machado_matrix(Deuteranopia, 0.55);
+        let expected = (MACHADO_DEUTAN[4][0][0] + MACHADO_DEUTAN[5][0][0]) / 2.0;
+        assert!((matrix[0][0] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simulate_cvd() {
+        use ColorVisionDeficiency::*;
+
+        let red = [1.0, 0.0, 0.0];
+        let green = [0.0, 0.5, 0.0];
+        let white = [1.0, 1.0, 1.0];
+
+        for method in [CvdMethod::Machado, CvdMethod::Brettel] {
+            for deficiency in [Protanopia, Deuteranopia, Tritanopia] {
+                // White stays white, and zero severity changes nothing.
+                let simulated = simulate_cvd(deficiency, method, 1.0, ColorSpace::Srgb, &white);
+                assert!(simulated.iter().all(|c| (c - 1.0).abs() < 1e-3));
+                let unchanged = simulate_cvd(deficiency, method, 0.0, ColorSpace::Srgb, &red);
+                assert!(close_enough(&unchanged, &red, false));
+            }
+
+            // Red and green become much harder to distinguish for red-green
+            // deficiencies.
+            let original = delta_e(&red, &green);
+            for deficiency in [Protanopia, Deuteranopia] {
+                let red2 = simulate_cvd(deficiency, method, 1.0, ColorSpace::Srgb, &red);
+                let green2 = simulate_cvd(deficiency, method, 1.0, ColorSpace::Srgb, &green);
+                assert!(delta_e(&red2, &green2) < original / 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_daltonize() {
+        use ColorVisionDeficiency::*;
+
+        let red = [1.0, 0.0, 0.0];
+        let green = [0.0, 0.5, 0.0];
+
+        for method in [CvdMethod::Machado, CvdMethod::Brettel] {
+            for deficiency in [Protanopia, Deuteranopia] {
+                let simulate =
+                    |c: &[f64; 3]| simulate_cvd(deficiency, method, 1.0, ColorSpace::Srgb, c);
+                let before = delta_e(&simulate(&red), &simulate(&green));
+
+                let red2 = daltonize(deficiency, method, 1.0, ColorSpace::Srgb, &red);
+                let green2 = daltonize(deficiency, method, 1.0, ColorSpace::Srgb, &green);
+                let after = delta_e(&simulate(&red2), &simulate(&green2));
+                assert!(after > before);
+            }
+        }
+    }
+
+    fn delta_e(srgb1: &[f64; 3], srgb2: &[f64; 3]) -> f64 {
+        delta_e_ok(
+            &convert(ColorSpace::Srgb, ColorSpace::Oklab, srgb1),
+            &convert(ColorSpace::Srgb, ColorSpace::Oklab, srgb2),
+        )
+    }
+}
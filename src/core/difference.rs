@@ -7,13 +7,30 @@ use crate::{Bits, Float};
 /// Normalize coordinates for equality testing and hashing.
 #[must_use = "function returns new color coordinates and does not mutate original value"]
 pub(crate) fn to_eq_bits(space: ColorSpace, coordinates: &[Float; 3]) -> [Bits; 3] {
-    let [mut c1, mut c2, mut c3] = normalize(space, coordinates);
+    let mut coordinates = normalize(space, coordinates);
+    let hue_index = space.hue_index();
 
-    // Normalize rotation and scale to unit range.
-    if space.is_polar() {
-        c3 = c3.rem_euclid(360.0) / 360.0
+    for (index, coordinate) in coordinates.iter_mut().enumerate() {
+        if hue_index == Some(index) {
+            // Normalize rotation and scale to unit range.
+            *coordinate = coordinate.rem_euclid(360.0) / 360.0;
+        } else if space.is_lab()
+            || matches!(
+                space,
+                ColorSpace::Hsl
+                    | ColorSpace::Hsv
+                    | ColorSpace::Hwb
+                    | ColorSpace::Cam16Ucs
+                    | ColorSpace::Hct
+            )
+        {
+            // Scale percent-based coordinates to (roughly) unit range, too.
+            *coordinate /= 100.0;
+        }
     }
 
+    let [mut c1, mut c2, mut c3] = coordinates;
+
     // Drop one digit of precision.
     let factor = (10.0 as Float).powi((Float::DIGITS as i32) - 1);
     c1 = (c1 * factor).round();
@@ -34,6 +51,27 @@ pub(crate) fn to_eq_bits(space: ColorSpace, coordinates: &[Float; 3]) -> [Bits;
     [c1.to_bits(), c2.to_bits(), c3.to_bits()]
 }
 
+/// Normalize alpha for equality testing and hashing.
+///
+/// This function replaces a not-a-number alpha with zero, clamps the alpha to
+/// `0..=1`, and then rounds just like [`to_eq_bits`] does for coordinates.
+#[must_use = "function returns new alpha and does not mutate original value"]
+pub(crate) fn alpha_to_eq_bits(alpha: Float) -> Bits {
+    let alpha = if alpha.is_nan() {
+        0.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    };
+
+    let factor = (10.0 as Float).powi((Float::DIGITS as i32) - 1);
+    let alpha = (alpha * factor).round();
+    if alpha == -0.0 {
+        (0.0 as Float).to_bits()
+    } else {
+        alpha.to_bits()
+    }
+}
+
 // --------------------------------------------------------------------------------------------------------------------
 
 /// Compute Delta-E for Oklab or Oklrab.
@@ -50,6 +88,220 @@ pub(crate) fn delta_e_ok(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -
     ΔL.mul_add(ΔL, Δa.mul_add(Δa, Δb * Δb)).sqrt()
 }
 
+/// Compute ΔE ITP for ICtCp.
+///
+/// This metric follows ITU-R BT.2124, which scales the Ct coordinate by one
+/// half and the overall result by 720. As a result, a ΔE ITP of 1.0 roughly
+/// corresponds to a just noticeable difference.
+#[inline]
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_itp(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+    let [I1, Ct1, Cp1] = coordinates1;
+    let [I2, Ct2, Cp2] = coordinates2;
+
+    let ΔI = I1 - I2;
+    let ΔT = 0.5 * (Ct1 - Ct2);
+    let ΔP = Cp1 - Cp2;
+
+    720.0 * ΔI.mul_add(ΔI, ΔT.mul_add(ΔT, ΔP * ΔP)).sqrt()
+}
+
+/// Compute ΔEz for JzCzhz.
+///
+/// Like CIEDE2000, this metric computes the hue difference ΔH as a chord
+/// scaled by the geometric mean of both chromas. If either hue is missing, the
+/// hue difference is zero.
+#[inline]
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_z(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+    let [Jz1, Cz1, hz1] = coordinates1;
+    let [Jz2, Cz2, hz2] = coordinates2;
+
+    let ΔJ = Jz1 - Jz2;
+    let ΔC = Cz1 - Cz2;
+    let Δh = if hz1.is_nan() || hz2.is_nan() {
+        0.0
+    } else {
+        (hz1 - hz2).to_radians()
+    };
+    let ΔH = 2.0 * (Cz1 * Cz2).sqrt() * (Δh / 2.0).sin();
+
+    ΔJ.mul_add(ΔJ, ΔC.mul_add(ΔC, ΔH * ΔH)).sqrt()
+}
+
+/// Compute CIEDE2000 for CIELAB.
+///
+/// This function implements the color difference as specified by CIE
+/// 142-2001, with all weighting factors set to one, and follows [Sharma et
+/// al.](https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/) in resolving
+/// the ambiguities of mean hue and hue difference.
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_2000(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+    let [L1, a1, b1] = *coordinates1;
+    let [L2, a2, b2] = *coordinates2;
+
+    // Scale a* to account for the chroma of neutral colors.
+    const POW25_7: Float = 6_103_515_625.0;
+    let C_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let C_mean7 = C_mean.powi(7);
+    let G = 0.5 * (1.0 - (C_mean7 / (C_mean7 + POW25_7)).sqrt());
+
+    let a1 = (1.0 + G) * a1;
+    let a2 = (1.0 + G) * a2;
+    let C1 = a1.hypot(b1);
+    let C2 = a2.hypot(b2);
+
+    let hue = |a: Float, b: Float| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+    let achromatic = C1 * C2 == 0.0;
+
+    // Compute the differences.
+    let ΔL = L2 - L1;
+    let ΔC = C2 - C1;
+    let Δh = if achromatic {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let ΔH = 2.0 * (C1 * C2).sqrt() * (Δh / 2.0).to_radians().sin();
+
+    // Compute the means.
+    let L_mean = (L1 + L2) / 2.0;
+    let C_mean = (C1 + C2) / 2.0;
+    let h_mean = if achromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    // Compute the weighting functions.
+    let cos = |degrees: Float| degrees.to_radians().cos();
+    let T =
+        1.0 - 0.17 * cos(h_mean - 30.0) + 0.24 * cos(2.0 * h_mean) + 0.32 * cos(3.0 * h_mean + 6.0)
+            - 0.20 * cos(4.0 * h_mean - 63.0);
+    let Δθ = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let C_mean7 = C_mean.powi(7);
+    let RC = 2.0 * (C_mean7 / (C_mean7 + POW25_7)).sqrt();
+    let L50 = (L_mean - 50.0).powi(2);
+    let SL = 1.0 + 0.015 * L50 / (20.0 + L50).sqrt();
+    let SC = 1.0 + 0.045 * C_mean;
+    let SH = 1.0 + 0.015 * C_mean * T;
+    let RT = -(2.0 * Δθ).to_radians().sin() * RC;
+
+    let ΔL = ΔL / SL;
+    let ΔC = ΔC / SC;
+    let ΔH = ΔH / SH;
+
+    ΔL.mul_add(ΔL, ΔC.mul_add(ΔC, ΔH.mul_add(ΔH, RT * ΔC * ΔH)))
+        .sqrt()
+}
+
+/// Compute the chroma difference and the squared hue difference for CIELAB.
+///
+/// The squared hue difference is derived from the Euclidian distance in the
+/// a*b* plane and the chroma difference. It is clamped to zero to absorb
+/// floating point error.
+#[inline]
+#[allow(non_snake_case)]
+fn chroma_and_hue_difference(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> [Float; 2] {
+    let [_, a1, b1] = *coordinates1;
+    let [_, a2, b2] = *coordinates2;
+
+    let Δa = a1 - a2;
+    let Δb = b1 - b2;
+    let ΔC = a1.hypot(b1) - a2.hypot(b2);
+    let ΔH2 = Δa.mul_add(Δa, Δb.mul_add(Δb, -ΔC * ΔC)).max(0.0);
+
+    [ΔC, ΔH2]
+}
+
+/// Compute CIE94 for CIELAB.
+///
+/// This function uses the weighting factors for graphic arts. The metric is
+/// not symmetric; it uses the first color as reference.
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_94(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+    let [L1, a1, b1] = *coordinates1;
+    let L2 = coordinates2[0];
+    let C1 = a1.hypot(b1);
+    let [ΔC, ΔH2] = chroma_and_hue_difference(coordinates1, coordinates2);
+
+    let ΔL = L1 - L2;
+    let ΔC = ΔC / (1.0 + 0.045 * C1);
+    let SH = 1.0 + 0.015 * C1;
+
+    ΔL.mul_add(ΔL, ΔC.mul_add(ΔC, ΔH2 / (SH * SH))).sqrt()
+}
+
+/// Compute CMC l:c for CIELAB.
+///
+/// This function weighs lightness by `lightness` and chroma by `chroma`,
+/// which are 2 and 1 for acceptability and 1 and 1 for perceptibility. The
+/// metric is not symmetric; it uses the first color as reference.
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_cmc(
+    lightness: Float,
+    chroma: Float,
+    coordinates1: &[Float; 3],
+    coordinates2: &[Float; 3],
+) -> Float {
+    let [L1, a1, b1] = *coordinates1;
+    let L2 = coordinates2[0];
+    let C1 = a1.hypot(b1);
+    let H1 = b1.atan2(a1).to_degrees().rem_euclid(360.0);
+    let [ΔC, ΔH2] = chroma_and_hue_difference(coordinates1, coordinates2);
+
+    let SL = if L1 < 16.0 {
+        0.511
+    } else {
+        0.040975 * L1 / (1.0 + 0.01765 * L1)
+    };
+    let SC = 0.0638 * C1 / (1.0 + 0.0131 * C1) + 0.638;
+    let C1_4 = C1.powi(4);
+    let F = (C1_4 / (C1_4 + 1900.0)).sqrt();
+    let T = if (164.0..=345.0).contains(&H1) {
+        0.56 + (0.2 * (H1 + 168.0).to_radians().cos()).abs()
+    } else {
+        0.36 + (0.4 * (H1 + 35.0).to_radians().cos()).abs()
+    };
+    let SH = SC * F.mul_add(T, 1.0 - F);
+
+    let ΔL = (L1 - L2) / (lightness * SL);
+    let ΔC = ΔC / (chroma * SC);
+
+    ΔL.mul_add(ΔL, ΔC.mul_add(ΔC, ΔH2 / (SH * SH))).sqrt()
+}
+
+/// Compute HyAB for CIELAB.
+///
+/// This metric by [Abasi et
+/// al.](https://onlinelibrary.wiley.com/doi/10.1002/col.22451) combines the
+/// absolute lightness difference with the Euclidian distance in the a*b* plane.
+/// It is more accurate than Euclidian metrics for large color differences.
+#[inline]
+#[allow(non_snake_case)]
+pub(crate) fn delta_e_hyab(coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+    let [L1, a1, b1] = coordinates1;
+    let [L2, a2, b2] = coordinates2;
+
+    (L1 - L2).abs() + (a1 - a2).hypot(b1 - b2)
+}
+
 /// Find the candidate color closest to the origin.
 ///
 /// This function compares the origin to every candidate color, computing the
@@ -93,25 +345,57 @@ where
 ///
 /// This function panics if the index is out of bounds.
 fn carry_forward(from_space: ColorSpace, to_space: ColorSpace, index: usize) -> Option<usize> {
-    use ColorSpace::*;
-
     if !(0..=2).contains(&index) {
         panic!("0..=2.contains({}) does not hold!", index)
     }
 
-    match (from_space, to_space, index) {
-        // Analogous components are (r,x) -- (g,y) -- (b,z) -- (L) -- (Lr) -- (C) -- (h) -- (a) -- (b)
+    let component = analogous_component(from_space, index)?;
+    (0..=2).find(|&index| analogous_component(to_space, index) == Some(component))
+}
+
+/// The categories of analogous components used for carrying forward.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Analogous {
+    Red,
+    Green,
+    Blue,
+    Lightness,
+    RevisedLightness,
+    Colorfulness,
+    Hue,
+    OpponentA,
+    OpponentB,
+}
+
+/// Determine the category of analogous components for the coordinate with the
+/// given index. Following CSS Color 4, this function treats the lightness of
+/// HSL and CIELAB as analogous to the lightness of Oklab and the saturation of
+/// HSL as analogous to chroma. By extension, it treats Okhsl's lightness as
+/// analogous to the revised lightness of Oklrab and Okhsl's saturation as
+/// analogous to chroma, too. It also treats XYZ's coordinates as analogous to
+/// the red, green, and blue of RGB.
+fn analogous_component(space: ColorSpace, index: usize) -> Option<Analogous> {
+    use Analogous::*;
+    use ColorSpace::*;
+
+    let component = match (space, index) {
         (
-            Srgb | LinearSrgb | DisplayP3 | LinearDisplayP3 | Rec2020 | LinearRec2020 | Xyz,
-            Srgb | LinearSrgb | DisplayP3 | LinearDisplayP3 | Rec2020 | LinearRec2020 | Xyz,
+            Srgb | LinearSrgb | DisplayP3 | LinearDisplayP3 | Rec2020 | LinearRec2020 | Rec2100Pq
+            | Rec2100Hlg | A98Rgb | LinearA98Rgb | ProPhotoRgb | LinearProPhotoRgb | Xyz | XyzD50,
             _,
-        ) => Some(index),
-        (Oklab | Oklch, Oklab | Oklch, 0) => Some(0),
-        (Oklrab | Oklrch, Oklrab | Oklrch, 0) => Some(0),
-        (Oklab | Oklrab, Oklab | Oklrab, 1 | 2) => Some(index),
-        (Oklch | Oklrch, Oklch | Oklrch, 1 | 2) => Some(index),
-        _ => None,
-    }
+        ) => [Red, Green, Blue][index],
+        (Oklab | Oklch | Lab | Lch | Cam16Ucs | Ictcp | Jzazbz | Jzczhz, 0) | (Hsl | Hct, 2) => {
+            Lightness
+        }
+        (Oklrab | Oklrch, 0) | (Okhsl, 2) => RevisedLightness,
+        (Oklch | Oklrch | Lch | Hsl | Okhsl | Hct | Jzczhz, 1) => Colorfulness,
+        (Oklch | Oklrch | Lch | Jzczhz, 2) | (Hsl | Hsv | Hwb | Okhsv | Okhsl | Hct, 0) => Hue,
+        (Oklab | Oklrab | Lab | Cam16Ucs | Jzazbz, 1) => OpponentA,
+        (Oklab | Oklrab | Lab | Cam16Ucs | Jzazbz, 2) => OpponentB,
+        _ => return None,
+    };
+
+    Some(component)
 }
 
 /// Convert the coordinates while carrying forward missing values.
@@ -236,14 +520,127 @@ pub(crate) fn prepare_to_interpolate(
     }
 
     // Adjust hue based on interpolation strategy
-    if interpolation_space.is_polar() {
-        [coordinates1[2], coordinates2[2]] =
-            prepare_hue_interpolation(strategy, coordinates1[2], coordinates2[2])
+    if let Some(index) = interpolation_space.hue_index() {
+        [coordinates1[index], coordinates2[index]] =
+            prepare_hue_interpolation(strategy, coordinates1[index], coordinates2[index])
     }
 
     (coordinates1, coordinates2)
 }
 
+/// Prepare a sequence of coordinates for interpolation.
+///
+/// This function generalizes [`prepare_to_interpolate`] to more than two
+/// colors. It carries forward missing components when converting to the
+/// interpolation color space and then fills them with the nearest preceding
+/// color's component or, if there is none, the nearest following color's
+/// component. It also unwraps hues, applying the interpolation strategy to
+/// each consecutive pair of hues, so that hues can be interpolated without
+/// further adjustment.
+#[must_use = "function returns new color coordinates and does not mutate original values"]
+pub(crate) fn prepare_to_interpolate_sequence(
+    colors: &[(ColorSpace, [Float; 3])],
+    interpolation_space: ColorSpace,
+    strategy: HueInterpolation,
+) -> Vec<[Float; 3]> {
+    let mut sequence: Vec<[Float; 3]> = colors
+        .iter()
+        .map(|(space, coordinates)| {
+            prepare_coordinate_interpolation(*space, interpolation_space, coordinates)
+        })
+        .collect();
+
+    // Fill in missing components
+    for index in 0..=2 {
+        for position in 1..sequence.len() {
+            if sequence[position][index].is_nan() {
+                sequence[position][index] = sequence[position - 1][index];
+            }
+        }
+        for position in (0..sequence.len().saturating_sub(1)).rev() {
+            if sequence[position][index].is_nan() {
+                sequence[position][index] = sequence[position + 1][index];
+            }
+        }
+    }
+
+    // Unwrap hues based on interpolation strategy
+    if let Some(index) = interpolation_space.hue_index() {
+        for position in 1..sequence.len() {
+            let previous = sequence[position - 1][index];
+            let [h1, h2] = prepare_hue_interpolation(
+                strategy,
+                previous.rem_euclid(360.0),
+                sequence[position][index].rem_euclid(360.0),
+            );
+            sequence[position][index] = previous + (h2 - h1);
+        }
+    }
+
+    sequence
+}
+
+/// Prepare alpha values for interpolation.
+///
+/// Consistent with coordinates, a missing, i.e., not-a-number alpha takes on
+/// the other color's alpha. If both alpha values are missing, they remain
+/// not-a-number.
+pub(crate) fn prepare_alpha_interpolation(alpha1: Float, alpha2: Float) -> [Float; 2] {
+    if alpha1.is_nan() {
+        [alpha2, alpha2]
+    } else if alpha2.is_nan() {
+        [alpha1, alpha1]
+    } else {
+        [alpha1, alpha2]
+    }
+}
+
+/// Premultiply the coordinates with the alpha.
+///
+/// Following [CSS Color
+/// 4](https://www.w3.org/TR/css-color-4/#interpolation-alpha), this function
+/// multiplies all coordinates but the hue with the alpha. A missing alpha is
+/// treated as 1.0.
+#[must_use = "function returns new color coordinates and does not mutate original values"]
+pub(crate) fn premultiply(space: ColorSpace, coordinates: &[Float; 3], alpha: Float) -> [Float; 3] {
+    if alpha.is_nan() || alpha == 1.0 {
+        return *coordinates;
+    }
+
+    let hue_index = space.hue_index();
+    let mut result = *coordinates;
+    for (index, coordinate) in result.iter_mut().enumerate() {
+        if hue_index != Some(index) {
+            *coordinate *= alpha;
+        }
+    }
+    result
+}
+
+/// Undo the premultiplication of coordinates with the alpha.
+///
+/// This function divides all coordinates but the hue by the alpha. If the
+/// alpha is zero or missing, it returns the coordinates unchanged.
+#[must_use = "function returns new color coordinates and does not mutate original values"]
+pub(crate) fn unpremultiply(
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+    alpha: Float,
+) -> [Float; 3] {
+    if alpha.is_nan() || alpha == 0.0 || alpha == 1.0 {
+        return *coordinates;
+    }
+
+    let hue_index = space.hue_index();
+    let mut result = *coordinates;
+    for (index, coordinate) in result.iter_mut().enumerate() {
+        if hue_index != Some(index) {
+            *coordinate /= alpha;
+        }
+    }
+    result
+}
+
 /// Interpolate between the prepared coordinates.
 ///
 /// This function calculates the linear interpolation for the given factor
@@ -262,3 +659,115 @@ pub(crate) fn interpolate(
         coordinates1[2] + fraction * (coordinates2[2] - coordinates1[2]),
     ]
 }
+
+/// Mix two colors.
+///
+/// This function implements the semantics of CSS Color 5's
+/// [`color-mix()`](https://www.w3.org/TR/css-color-5/#color-mix). Each color
+/// is given by its color space, coordinates, and alpha. The weights are
+/// fractions between zero and one. If both weights are missing, they default
+/// to one half. If one is missing, it defaults to one minus the other. If the
+/// weights do not add up to one, they are normalized. If their sum is less
+/// than one, it also becomes the multiplier for the result's alpha. This
+/// function returns `None` if a weight is out of range or both weights are
+/// zero. Otherwise, it returns the coordinates in the interpolation color
+/// space and the alpha.
+pub(crate) fn mix(
+    color1: (ColorSpace, &[Float; 3], Float),
+    weight1: Option<Float>,
+    color2: (ColorSpace, &[Float; 3], Float),
+    weight2: Option<Float>,
+    interpolation_space: ColorSpace,
+    strategy: HueInterpolation,
+) -> Option<([Float; 3], Float)> {
+    let (weight1, weight2) = match (weight1, weight2) {
+        (None, None) => (0.5, 0.5),
+        (Some(w1), None) => (w1, 1.0 - w1),
+        (None, Some(w2)) => (1.0 - w2, w2),
+        (Some(w1), Some(w2)) => (w1, w2),
+    };
+    if !(0.0..=1.0).contains(&weight1) || !(0.0..=1.0).contains(&weight2) {
+        return None;
+    }
+
+    let sum = weight1 + weight2;
+    if sum == 0.0 {
+        return None;
+    }
+    let multiplier = sum.min(1.0);
+    let fraction = weight2 / sum;
+
+    let (space1, coordinates1, alpha1) = color1;
+    let (space2, coordinates2, alpha2) = color2;
+    let (coordinates1, coordinates2) = prepare_to_interpolate(
+        space1,
+        coordinates1,
+        space2,
+        coordinates2,
+        interpolation_space,
+        strategy,
+    );
+    let [alpha1, alpha2] = prepare_alpha_interpolation(alpha1, alpha2);
+
+    let coordinates = interpolate(
+        fraction,
+        &premultiply(interpolation_space, &coordinates1, alpha1),
+        &premultiply(interpolation_space, &coordinates2, alpha2),
+    );
+    let alpha = alpha1 + fraction * (alpha2 - alpha1);
+    let coordinates = unpremultiply(interpolation_space, &coordinates, alpha);
+
+    Some((coordinates, alpha * multiplier))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{delta_e_2000, delta_e_94, delta_e_cmc, delta_e_hyab};
+
+    #[test]
+    fn test_delta_e_2000() {
+        // A selection of Sharma et al.'s test data.
+        for (lab1, lab2, expected) in [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+        ] {
+            let actual = delta_e_2000(&lab1, &lab2);
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "{} vs {}",
+                actual,
+                expected
+            );
+            let actual = delta_e_2000(&lab2, &lab1);
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "{} vs {}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_other_delta_e() {
+        let lab1 = [50.0, 2.5, 0.0];
+        let lab2 = [73.0, 25.0, -18.0];
+
+        assert_eq!(delta_e_94(&lab1, &lab1), 0.0);
+        assert_eq!(delta_e_cmc(2.0, 1.0, &lab1, &lab1), 0.0);
+        assert_eq!(delta_e_hyab(&lab1, &lab1), 0.0);
+
+        assert!((delta_e_94(&lab1, &lab2) - 34.6892).abs() < 1e-4);
+        assert!((delta_e_cmc(2.0, 1.0, &lab1, &lab2) - 37.9233).abs() < 1e-4);
+        assert!((delta_e_hyab(&lab1, &lab2) - 51.8141).abs() < 1e-4);
+    }
+}
@@ -1,21 +1,40 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
 use crate::core::conversion::okxch_to_okxab;
 use crate::core::{convert, delta_e_ok, normalize};
 use crate::{ColorSpace, Float};
 
 /// Determine whether the coordinates are in gamut for their color space.
+///
+/// Since HSL, HSV, HWB, Okhsv, and Okhsl model sRGB's gamut, coordinates in
+/// those color spaces are in gamut if their sRGB equivalents are in gamut.
 pub(crate) fn in_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> bool {
     if space.is_rgb() {
         coordinates.iter().all(|c| 0.0 <= *c && *c <= 1.0)
+    } else if space.is_cylindrical() {
+        in_gamut(
+            ColorSpace::Srgb,
+            &convert(space, ColorSpace::Srgb, coordinates),
+        )
     } else {
         true
     }
 }
 
 /// Clip the coordinates to the gamut of their color space.
+///
+/// Coordinates in HSL, HSV, HWB, Okhsv, and Okhsl are clipped in sRGB.
 pub(crate) fn clip(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
     if space.is_rgb() {
         let [r, g, b] = coordinates;
         [r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0)]
+    } else if space.is_cylindrical() {
+        let srgb = clip(
+            ColorSpace::Srgb,
+            &convert(space, ColorSpace::Srgb, coordinates),
+        );
+        convert(ColorSpace::Srgb, space, &srgb)
     } else {
         *coordinates
     }
@@ -24,6 +43,133 @@ pub(crate) fn clip(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
 const JND: Float = 0.02;
 const EPSILON: Float = 0.0001;
 
+/// A gamut-mapping algorithm.
+///
+/// All algorithms map colors with an Oklch lightness of 1 or more to white
+/// and colors with an Oklch lightness of 0 or less to black. They also leave
+/// colors that already are in gamut unchanged. They differ in how they bring
+/// the remaining colors into gamut.
+///
+/// # Css
+///
+/// The [CSS Color 4
+/// algorithm](https://drafts.csswg.org/css-color/#css-gamut-mapping) performs
+/// a binary search in Oklch for a color with less chroma but the same lightness
+/// and hue, whose clipped version is within the *just noticeable difference*
+/// `jnd`. Since it returns the clipped version, it may adjust lightness and hue
+/// ever so slightly. [`GamutMapping::CSS`] uses the specification's JND of
+/// 0.02. Smaller values produce colors closer to the chroma-reduced color,
+/// larger values colors closer to the clipped color.
+///
+/// # Raytrace
+///
+/// The ray tracing algorithm, which has been proposed to the CSS working group
+/// as a faster replacement for the binary search, casts a ray from the
+/// achromatic color with the same Oklch lightness to the color and intersects
+/// it with the RGB cube. It then corrects the intersection's lightness and hue
+/// in Oklch and repeats the process a few times, before clipping the result.
+/// Its results closely track those of chroma compression, at a fraction of the
+/// cost.
+///
+/// # ChromaCompression
+///
+/// Lightness-preserving chroma compression performs a binary search in Oklch
+/// for the color with the largest chroma that is in gamut and has the same
+/// lightness and hue. Unlike the CSS algorithm, it does not trade in accuracy
+/// of lightness and hue for more chroma. Saturated colors hence come out a
+/// little duller.
+///
+/// # HuePreservingClip
+///
+/// Hue-preserving clipping projects the color towards the achromatic color
+/// with the same lightness as the gamut's cusp, i.e., the most chromatic color
+/// for the hue, until the color is in gamut. It preserves Oklch hue exactly but
+/// adjusts both lightness and chroma. By moving lightness towards the cusp, it
+/// retains more chroma than chroma compression.
+#[cfg_attr(feature = "pyffi", pyclass(eq, frozen))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GamutMapping {
+    Css { jnd: Float },
+    Raytrace(),
+    ChromaCompression(),
+    HuePreservingClip(),
+}
+
+impl GamutMapping {
+    /// The CSS Color 4 algorithm with the specification's JND.
+    pub const CSS: Self = Self::Css { jnd: JND };
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl GamutMapping {
+    /// The CSS Color 4 algorithm with the specification's JND. <span
+    /// class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "CSS")]
+    pub fn css() -> Self {
+        Self::CSS
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl Default for GamutMapping {
+    /// Get the default gamut-mapping algorithm, [`GamutMapping::CSS`].
+    fn default() -> Self {
+        Self::CSS
+    }
+}
+
+/// A bounded color space suitable for gamut mapping.
+///
+/// This trait abstracts over the operations needed by the CSS Color 4 gamut
+/// mapping algorithm. It is implemented by [`ColorSpace`] for built-in color
+/// spaces and by [`RgbSpace`](crate::RgbSpace) for user-defined RGB color
+/// spaces.
+pub(crate) trait Gamut {
+    /// Convert the coordinates to Oklab.
+    fn to_oklab(&self, coordinates: &[Float; 3]) -> [Float; 3];
+
+    /// Convert the coordinates to Oklch.
+    fn to_oklch(&self, coordinates: &[Float; 3]) -> [Float; 3];
+
+    /// Convert Oklch coordinates back to this color space.
+    fn oklch_to_space(&self, coordinates: &[Float; 3]) -> [Float; 3];
+
+    /// Determine whether the coordinates are in gamut.
+    fn in_gamut(&self, coordinates: &[Float; 3]) -> bool;
+
+    /// Clip the coordinates to the gamut.
+    fn clip(&self, coordinates: &[Float; 3]) -> [Float; 3];
+}
+
+impl Gamut for ColorSpace {
+    fn to_oklab(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        convert(*self, ColorSpace::Oklab, coordinates)
+    }
+
+    fn to_oklch(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        convert(*self, ColorSpace::Oklch, coordinates)
+    }
+
+    fn oklch_to_space(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        convert(ColorSpace::Oklch, *self, coordinates)
+    }
+
+    fn in_gamut(&self, coordinates: &[Float; 3]) -> bool {
+        in_gamut(*self, coordinates)
+    }
+
+    fn clip(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        clip(*self, coordinates)
+    }
+}
+
 /// Map the given color coordinates into the gamut of their color space.
 ///
 /// This function implements the CSS Color 4 [gamut mapping
@@ -33,8 +179,26 @@ const EPSILON: Float = 0.0001;
 /// difference*. Since, by definition, the clipped version also is in gamut, it
 /// becomes the result of the search.
 pub(crate) fn to_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
-    use ColorSpace::*;
+    let coordinates = normalize(space, coordinates);
 
+    // If the color space is unbounded, there is nothing to map to
+    if !space.is_bounded() {
+        return coordinates;
+    }
+
+    map_to_gamut(&space, &coordinates)
+}
+
+/// Map the given color coordinates into the gamut of their color space with
+/// the given algorithm.
+///
+/// Since ray tracing and hue-preserving clipping operate on the RGB cube, this
+/// function maps coordinates in HSL, HSV, HWB, Okhsv, and Okhsl in sRGB.
+pub(crate) fn to_gamut_with(
+    mapping: GamutMapping,
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+) -> [Float; 3] {
     let coordinates = normalize(space, coordinates);
 
     // If the color space is unbounded, there is nothing to map to
@@ -42,32 +206,75 @@ pub(crate) fn to_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3
         return coordinates;
     }
 
-    // Preliminary 1/2: Clamp Lightness
-    let origin_as_oklch = convert(space, Oklch, &coordinates);
+    if space.is_cylindrical()
+        && matches!(
+            mapping,
+            GamutMapping::Raytrace() | GamutMapping::HuePreservingClip()
+        )
+    {
+        let srgb = convert(space, ColorSpace::Srgb, &coordinates);
+        let srgb = map_to_gamut_with(mapping, &ColorSpace::Srgb, &srgb);
+        return convert(ColorSpace::Srgb, space, &srgb);
+    }
+
+    map_to_gamut_with(mapping, &space, &coordinates)
+}
+
+/// Map the given, normalized coordinates into the given gamut.
+pub(crate) fn map_to_gamut(gamut: &impl Gamut, coordinates: &[Float; 3]) -> [Float; 3] {
+    map_to_gamut_with(GamutMapping::CSS, gamut, coordinates)
+}
+
+/// Map the given, normalized coordinates into the given gamut with the given
+/// algorithm.
+///
+/// Ray tracing and hue-preserving clipping require that the gamut's
+/// coordinates are RGB coordinates.
+pub(crate) fn map_to_gamut_with(
+    mapping: GamutMapping,
+    gamut: &impl Gamut,
+    coordinates: &[Float; 3],
+) -> [Float; 3] {
+    // Preliminary 1/2: Check gamut. This must come first, since HDR colors in
+    // Rec. 2100 may be brighter than SDR white and yet in gamut.
+    if gamut.in_gamut(coordinates) {
+        return *coordinates;
+    }
+
+    // Preliminary 2/2: Clamp Lightness
+    let origin_as_oklch = gamut.to_oklch(coordinates);
     let l = origin_as_oklch[0];
     if l >= 1.0 {
-        return convert(Oklch, space, &[1.0, 0.0, 0.0]);
+        return gamut.oklch_to_space(&[1.0, 0.0, 0.0]);
     }
     if l <= 0.0 {
-        return convert(Oklch, space, &[0.0, 0.0, 0.0]);
+        return gamut.oklch_to_space(&[0.0, 0.0, 0.0]);
     }
 
-    // Preliminary 2/2: Check gamut
-    if in_gamut(space, &coordinates) {
-        return coordinates;
+    match mapping {
+        GamutMapping::Css { jnd } => map_css(gamut, &origin_as_oklch, jnd),
+        GamutMapping::Raytrace() => map_raytrace(gamut, coordinates, &origin_as_oklch),
+        GamutMapping::ChromaCompression() => {
+            let [l, c, h] = origin_as_oklch;
+            find_max_chroma(gamut, c, EPSILON, |c| gamut.oklch_to_space(&[l, c, h]))
+        }
+        GamutMapping::HuePreservingClip() => map_to_cusp(gamut, &origin_as_oklch),
     }
+}
 
+/// Map the out-of-gamut color with the CSS Color 4 algorithm.
+fn map_css(gamut: &impl Gamut, origin_as_oklch: &[Float; 3], jnd: Float) -> [Float; 3] {
     // Goal: Minimize just noticeable difference between current and clipped
     // colors
-    let mut current_as_oklch = origin_as_oklch;
-    let mut clipped_as_target = clip(space, &convert(Oklch, space, &current_as_oklch));
+    let mut current_as_oklch = *origin_as_oklch;
+    let mut clipped_as_target = gamut.clip(&gamut.oklch_to_space(&current_as_oklch));
 
     let difference = delta_e_ok(
-        &convert(space, Oklab, &clipped_as_target),
+        &gamut.to_oklab(&clipped_as_target),
         &okxch_to_okxab(&current_as_oklch),
     );
 
-    if difference < JND {
+    if difference < jnd {
         return clipped_as_target;
     }
 
@@ -80,22 +287,22 @@ pub(crate) fn to_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3
         let chroma = (min + max) / 2.0;
         current_as_oklch = [current_as_oklch[0], chroma, current_as_oklch[2]];
 
-        let current_as_target = convert(Oklch, space, &current_as_oklch);
+        let current_as_target = gamut.oklch_to_space(&current_as_oklch);
 
-        if min_in_gamut && in_gamut(space, &current_as_target) {
+        if min_in_gamut && gamut.in_gamut(&current_as_target) {
             min = chroma;
             continue;
         }
 
-        clipped_as_target = clip(space, &current_as_target);
+        clipped_as_target = gamut.clip(&current_as_target);
 
         let difference = delta_e_ok(
-            &convert(space, Oklab, &clipped_as_target),
+            &gamut.to_oklab(&clipped_as_target),
             &okxch_to_okxab(&current_as_oklch),
         );
 
-        if difference < JND {
-            if JND - difference < EPSILON {
+        if difference < jnd {
+            if jnd - difference < EPSILON {
                 return clipped_as_target;
             }
             min_in_gamut = false;
@@ -108,9 +315,423 @@ pub(crate) fn to_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3
     clipped_as_target
 }
 
+/// Map the out-of-gamut color by ray tracing against the RGB cube.
+fn map_raytrace(
+    gamut: &impl Gamut,
+    coordinates: &[Float; 3],
+    origin_as_oklch: &[Float; 3],
+) -> [Float; 3] {
+    const LOW: Float = 1e-6;
+    const HIGH: Float = 1.0 - LOW;
+
+    let [l, _, h] = *origin_as_oklch;
+    let mut anchor = gamut.oklch_to_space(&[l, 0.0, h]);
+    let mut current = *coordinates;
+
+    for iteration in 0..4 {
+        if 0 < iteration {
+            // Correct lightness and hue of the previous intersection.
+            let [_, c, _] = gamut.to_oklch(&current);
+            current = gamut.oklch_to_space(&[l, c, h]);
+        }
+
+        let intersection = raytrace_box(&anchor, &current, LOW, HIGH);
+
+        // If the corrected color is inside the cube, cast later rays from it.
+        if 0 < iteration && current.iter().all(|c| LOW < *c && *c < HIGH) {
+            anchor = current;
+        }
+
+        match intersection {
+            Some(point) => current = point,
+            None => break,
+        }
+    }
+
+    gamut.clip(&current)
+}
+
+/// Intersect the ray from the start through the end point with the cube.
+///
+/// This function returns the point where the ray exits the cube with the
+/// given minimum and maximum coordinates. It returns `None` if the start and
+/// end points are the same.
+fn raytrace_box(
+    start: &[Float; 3],
+    end: &[Float; 3],
+    low: Float,
+    high: Float,
+) -> Option<[Float; 3]> {
+    let mut exit = Float::INFINITY;
+    for index in 0..3 {
+        let delta = end[index] - start[index];
+        if delta.abs() < 1e-12 {
+            continue;
+        }
+
+        let bound = if 0.0 < delta { high } else { low };
+        exit = exit.min((bound - start[index]) / delta);
+    }
+
+    if !exit.is_finite() {
+        return None;
+    }
+
+    Some(std::array::from_fn(|index| {
+        (end[index] - start[index]).mul_add(exit, start[index])
+    }))
+}
+
+/// The corners of the RGB cube for primaries and secondaries, in hue order and
+/// with red repeated at the end.
+const CORNERS: [[Float; 3]; 7] = [
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 1.0, 1.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 0.0, 0.0],
+];
+
+/// Find the cusp of the RGB gamut for the given Oklch hue.
+///
+/// The cusp is the most chromatic color for the hue. Since it always lies on
+/// one of the six edges of the RGB cube connecting primaries and secondaries,
+/// this function first finds the edge spanning the hue and then performs a
+/// binary search along that edge. It returns Oklch coordinates.
+pub(crate) fn find_cusp(gamut: &impl Gamut, hue: Float) -> [Float; 3] {
+    let point = |start: &[Float; 3], end: &[Float; 3], t: Float| -> [Float; 3] {
+        std::array::from_fn(|index| (end[index] - start[index]).mul_add(t, start[index]))
+    };
+
+    let hue = hue.rem_euclid(360.0);
+    let mut best = gamut.to_oklch(&CORNERS[0]);
+
+    for edge in CORNERS.windows(2) {
+        let start_hue = gamut.to_oklch(&edge[0])[2];
+        let end_hue = gamut.to_oklch(&edge[1])[2];
+        let span = (end_hue - start_hue).rem_euclid(360.0);
+        let target = (hue - start_hue).rem_euclid(360.0);
+        if span < target {
+            continue;
+        }
+
+        let mut min = 0.0;
+        let mut max = 1.0;
+        while max - min > 1e-10 {
+            let t = (min + max) / 2.0;
+            let current_hue = gamut.to_oklch(&point(&edge[0], &edge[1], t))[2];
+            if (current_hue - start_hue).rem_euclid(360.0) < target {
+                min = t;
+            } else {
+                max = t;
+            }
+        }
+
+        best = gamut.to_oklch(&point(&edge[0], &edge[1], (min + max) / 2.0));
+        break;
+    }
+
+    best
+}
+
+/// Map the out-of-gamut color by projecting it towards the achromatic color
+/// with the cusp's lightness.
+fn map_to_cusp(gamut: &impl Gamut, origin_as_oklch: &[Float; 3]) -> [Float; 3] {
+    let [l, c, h] = *origin_as_oklch;
+    let cusp_lightness = find_cusp(gamut, h)[0];
+
+    let to_space = |t: Float| {
+        let lightness = (l - cusp_lightness).mul_add(t, cusp_lightness);
+        gamut.oklch_to_space(&[lightness, c * t, h])
+    };
+
+    let mut min = 0.0;
+    let mut max = 1.0;
+    while max - min > EPSILON {
+        let t = (min + max) / 2.0;
+        if gamut.in_gamut(&to_space(t)) {
+            min = t;
+        } else {
+            max = t;
+        }
+    }
+
+    gamut.clip(&to_space(min))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Determine the gamut used for boundary queries.
+///
+/// Since HSL, HSV, HWB, Okhsv, and Okhsl model sRGB's gamut, boundary queries
+/// for those color spaces use sRGB instead.
+fn boundary_space(space: ColorSpace) -> Option<ColorSpace> {
+    if space.is_rgb() {
+        Some(space)
+    } else if space.is_cylindrical() {
+        Some(ColorSpace::Srgb)
+    } else {
+        None
+    }
+}
+
+/// Determine the cusp for the hue in the bounded color space.
+///
+/// This function returns the Oklch lightness and chroma of the most chromatic
+/// in-gamut color with the given Oklch hue. It returns `None` for unbounded
+/// color spaces.
+pub(crate) fn cusp(space: ColorSpace, hue: Float) -> Option<[Float; 2]> {
+    let space = boundary_space(space)?;
+    let [lightness, chroma, _] = find_cusp(&space, hue);
+    Some([lightness, chroma])
+}
+
+/// Determine the maximum chroma for the lightness and hue in the bounded color
+/// space.
+///
+/// This function returns the largest Oklch chroma, for which the color with
+/// the given Oklch lightness and hue is in gamut. It returns `None` for
+/// unbounded color spaces.
+pub(crate) fn max_chroma(space: ColorSpace, lightness: Float, hue: Float) -> Option<Float> {
+    let space = boundary_space(space)?;
+    if lightness.is_nan() || hue.is_nan() || lightness <= 0.0 || 1.0 <= lightness {
+        return Some(0.0);
+    }
+
+    Some(search_chroma(
+        space,
+        lightness,
+        hue,
+        find_cusp(&space, hue)[1],
+    ))
+}
+
+/// Search for the maximum in-gamut chroma for the lightness and hue.
+///
+/// Since the cusp has the largest chroma for the hue, its chroma makes for a
+/// tight upper bound.
+fn search_chroma(space: ColorSpace, lightness: Float, hue: Float, upper: Float) -> Float {
+    let mut min = 0.0;
+    let mut max = upper;
+    while max - min > 1e-7 {
+        let chroma = (min + max) / 2.0;
+        if in_gamut(
+            space,
+            &convert(ColorSpace::Oklch, space, &[lightness, chroma, hue]),
+        ) {
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    min
+}
+
+/// A table of a color space's gamut boundary.
+///
+/// A gamut boundary caches 360 cusps, sampled at even intervals along the six
+/// edges of the RGB cube connecting primaries and secondaries. For each cusp,
+/// it also caches the maximum in-gamut Oklch chroma for 50 Oklch lightness
+/// values between black and the cusp as well as 50 lightness values between
+/// the cusp and white. Since Oklch hue changes very unevenly along the edges
+/// and the boundary has a sharp bend at the cusp, sampling relative to the RGB
+/// cube and cusp is far more accurate than sampling hue and lightness
+/// uniformly. A gamut boundary answers queries for other lightness and hue
+/// values by linear interpolation. While the interpolated maximum chroma may
+/// differ slightly from [`ColorSpace::max_chroma`], a gamut boundary answers
+/// queries in constant time, which makes it the preferred choice for
+/// generating palettes. Use [`ColorSpace::gamut_boundary`] to create a gamut
+/// boundary.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::ColorSpace;
+/// let boundary = ColorSpace::Srgb.gamut_boundary().unwrap();
+/// let exact = ColorSpace::Srgb.max_chroma(0.7, 145.0).unwrap();
+/// assert!((boundary.max_chroma(0.7, 145.0) - exact).abs() < 0.001);
+///
+/// let [lightness, chroma] = boundary.cusp(145.0);
+/// assert!((lightness - 0.869).abs() < 0.001);
+/// assert!((chroma - 0.273).abs() < 0.001);
+/// ```
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Clone, Debug)]
+pub struct GamutBoundary {
+    space: ColorSpace,
+    hues: Vec<Float>,
+    cusps: Vec<[Float; 2]>,
+    chroma: Vec<Float>,
+}
+
+impl GamutBoundary {
+    const EDGE_STEPS: usize = 60;
+    const LIGHTNESS_STEPS: usize = 50;
+    const COLUMN: usize = 2 * Self::LIGHTNESS_STEPS + 1;
+
+    /// Create a new gamut boundary for the bounded color space.
+    pub(crate) fn new(space: ColorSpace) -> Option<Self> {
+        let space = boundary_space(space)?;
+
+        let mut samples: Vec<[Float; 3]> = CORNERS
+            .windows(2)
+            .flat_map(|edge| {
+                (0..Self::EDGE_STEPS).map(|step| {
+                    // Cluster samples near the corners, where the boundary
+                    // changes most rapidly.
+                    let t = step as Float / Self::EDGE_STEPS as Float;
+                    let t = (1.0 - (t * 180.0).to_radians().cos()) / 2.0;
+                    let point = std::array::from_fn(|index| {
+                        (edge[1][index] - edge[0][index]).mul_add(t, edge[0][index])
+                    });
+                    let [lightness, chroma, hue] = space.to_oklch(&point);
+                    [lightness, chroma, hue.rem_euclid(360.0)]
+                })
+            })
+            .collect();
+        samples.sort_by(|s1, s2| s1[2].total_cmp(&s2[2]));
+
+        let hues: Vec<Float> = samples.iter().map(|s| s[2]).collect();
+        let cusps: Vec<[Float; 2]> = samples.iter().map(|s| [s[0], s[1]]).collect();
+
+        let mut chroma = Vec::with_capacity(hues.len() * Self::COLUMN);
+        for (&h, &[cusp_lightness, cusp_chroma]) in hues.iter().zip(cusps.iter()) {
+            for step in 0..Self::COLUMN {
+                let lightness = Self::lightness(cusp_lightness, step);
+                chroma.push(if step == Self::LIGHTNESS_STEPS {
+                    cusp_chroma
+                } else if lightness <= 0.0 || 1.0 <= lightness {
+                    0.0
+                } else {
+                    search_chroma(space, lightness, h, cusp_chroma)
+                });
+            }
+        }
+
+        Some(Self {
+            space,
+            hues,
+            cusps,
+            chroma,
+        })
+    }
+
+    /// Determine the lightness for the step relative to the cusp.
+    fn lightness(cusp_lightness: Float, step: usize) -> Float {
+        let steps = Self::LIGHTNESS_STEPS as Float;
+        if step <= Self::LIGHTNESS_STEPS {
+            cusp_lightness * step as Float / steps
+        } else {
+            let step = (step - Self::LIGHTNESS_STEPS) as Float;
+            (1.0 - cusp_lightness).mul_add(step / steps, cusp_lightness)
+        }
+    }
+
+    /// Look up the maximum chroma for the lightness in the hue's column.
+    fn lookup(&self, hue: usize, lightness: Float) -> Float {
+        let [cusp_lightness, _] = self.cusps[hue];
+        let steps = Self::LIGHTNESS_STEPS as Float;
+        let position = if lightness <= cusp_lightness {
+            lightness / cusp_lightness * steps
+        } else {
+            (lightness - cusp_lightness) / (1.0 - cusp_lightness) * steps + steps
+        };
+
+        let index = (position.floor() as usize).min(Self::COLUMN - 2);
+        let column = &self.chroma[hue * Self::COLUMN..(hue + 1) * Self::COLUMN];
+        let t = position - index as Float;
+        (column[index + 1] - column[index]).mul_add(t, column[index])
+    }
+
+    /// Determine the hue indices and interpolation factor.
+    fn hue_index(&self, hue: Float) -> (usize, usize, Float) {
+        let hue = hue.rem_euclid(360.0);
+        let count = self.hues.len();
+        let index = self.hues.partition_point(|h| *h <= hue).max(1) - 1;
+        let next = (index + 1) % count;
+
+        let start = self.hues[index];
+        let end = if next == 0 { 360.0 } else { self.hues[next] };
+        if end - start < 1e-9 {
+            (index, next, 0.0)
+        } else {
+            (index, next, (hue - start) / (end - start))
+        }
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl GamutBoundary {
+    /// Get the color space.
+    ///
+    /// For HSL, HSV, HWB, Okhsv, and Okhsl, this method returns sRGB.
+    pub fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    /// Look up the maximum in-gamut Oklch chroma for the Oklch lightness and
+    /// hue.
+    pub fn max_chroma(&self, lightness: Float, hue: Float) -> Float {
+        if lightness.is_nan() || hue.is_nan() || lightness <= 0.0 || 1.0 <= lightness {
+            return 0.0;
+        }
+
+        let (h0, h1, t) = self.hue_index(hue);
+        let c0 = self.lookup(h0, lightness);
+        let c1 = self.lookup(h1, lightness);
+        (c1 - c0).mul_add(t, c0)
+    }
+
+    /// Look up the Oklch lightness and chroma of the cusp for the Oklch hue.
+    pub fn cusp(&self, hue: Float) -> [Float; 2] {
+        let (h0, h1, t) = self.hue_index(if hue.is_nan() { 0.0 } else { hue });
+        let [l0, c0] = self.cusps[h0];
+        let [l1, c1] = self.cusps[h1];
+        [(l1 - l0).mul_add(t, l0), (c1 - c0).mul_add(t, c0)]
+    }
+}
+
+/// Find the maximum chroma that is in gamut.
+///
+/// This function performs a binary search for the maximum chroma between zero
+/// and the given chroma, for which the coordinates produced by `to_space` are
+/// in the given gamut. It stops when the search interval is smaller than the
+/// given precision and returns the clipped coordinates for the maximum chroma.
+pub(crate) fn find_max_chroma<F>(
+    gamut: &impl Gamut,
+    chroma: Float,
+    precision: Float,
+    to_space: F,
+) -> [Float; 3]
+where
+    F: Fn(Float) -> [Float; 3],
+{
+    let current = to_space(chroma);
+    if gamut.in_gamut(&current) {
+        return current;
+    }
+
+    let mut min = 0.0;
+    let mut max = chroma;
+
+    while max - min > precision {
+        let chroma = (min + max) / 2.0;
+        if gamut.in_gamut(&to_space(chroma)) {
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    gamut.clip(&to_space(min))
+}
+
 #[cfg(test)]
 mod test {
-    use super::to_gamut;
+    use super::{find_cusp, in_gamut, max_chroma, to_gamut, to_gamut_with, GamutMapping};
     use crate::core::test_util::close_enough;
     use crate::core::{convert, ColorSpace};
 
@@ -155,4 +776,120 @@ mod test {
             false
         ));
     }
+
+    #[test]
+    fn test_gamut_mapping() {
+        let red = convert(ColorSpace::Srgb, ColorSpace::Oklch, &[1.0, 0.0, 0.0]);
+        let cusp = find_cusp(&ColorSpace::Srgb, red[2]);
+        for (c1, c2) in cusp.iter().zip(red.iter()) {
+            assert!((c1 - c2).abs() < 1e-6);
+        }
+
+        for p3 in [
+            [0.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.2, 1.0],
+        ] {
+            let srgb = convert(ColorSpace::DisplayP3, ColorSpace::Srgb, &p3);
+            let [l, _, h] = convert(ColorSpace::Srgb, ColorSpace::Oklch, &srgb);
+
+            assert_eq!(
+                to_gamut_with(GamutMapping::CSS, ColorSpace::Srgb, &srgb),
+                to_gamut(ColorSpace::Srgb, &srgb)
+            );
+
+            for mapping in [
+                GamutMapping::Css { jnd: 0.05 },
+                GamutMapping::Raytrace(),
+                GamutMapping::ChromaCompression(),
+                GamutMapping::HuePreservingClip(),
+            ] {
+                let mapped = to_gamut_with(mapping, ColorSpace::Srgb, &srgb);
+                assert!(in_gamut(ColorSpace::Srgb, &mapped), "{:?}", mapping);
+
+                let [ml, _, mh] = convert(ColorSpace::Srgb, ColorSpace::Oklch, &mapped);
+                match mapping {
+                    GamutMapping::ChromaCompression() => {
+                        assert!((l - ml).abs() < 1e-9);
+                        assert!((h - mh).abs() < 1e-9);
+                    }
+                    GamutMapping::HuePreservingClip() => {
+                        assert!((h - mh).abs() < 1e-9);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Cylindrical color spaces are mapped in sRGB.
+            let hsl = convert(ColorSpace::Srgb, ColorSpace::Hsl, &srgb);
+            let mapped = to_gamut_with(GamutMapping::Raytrace(), ColorSpace::Hsl, &hsl);
+            assert!(in_gamut(ColorSpace::Hsl, &mapped));
+        }
+    }
+
+    #[test]
+    fn test_hdr_gamut() {
+        // Highlights brighter than SDR white are in gamut and stay unchanged.
+        for (space, coordinates) in [
+            (ColorSpace::Rec2100Pq, [0.9, 0.9, 0.9]),
+            (ColorSpace::Rec2100Hlg, [1.0, 1.0, 1.0]),
+            (ColorSpace::Rec2100Pq, [0.9, 0.6, 0.3]),
+        ] {
+            assert!(in_gamut(space, &coordinates));
+            assert_eq!(to_gamut(space, &coordinates), coordinates);
+
+            for mapping in [
+                GamutMapping::Css { jnd: 0.05 },
+                GamutMapping::Raytrace(),
+                GamutMapping::ChromaCompression(),
+                GamutMapping::HuePreservingClip(),
+            ] {
+                assert_eq!(
+                    to_gamut_with(mapping, space, &coordinates),
+                    coordinates,
+                    "{:?} {:?}",
+                    space,
+                    mapping
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gamut_boundary() {
+        let boundary = ColorSpace::DisplayP3.gamut_boundary().unwrap();
+        assert_eq!(ColorSpace::Oklab.gamut_boundary().map(|b| b.space()), None);
+
+        for hue in [0.0, 29.0, 90.0, 110.0, 145.0, 200.0, 264.0, 330.0] {
+            let [cl, cc] = ColorSpace::DisplayP3.cusp(hue).unwrap();
+            let [bl, bc] = boundary.cusp(hue);
+            assert!((cl - bl).abs() < 0.005, "{}", hue);
+            assert!((cc - bc).abs() < 0.005, "{}", hue);
+
+            for lightness in [0.1, 0.3, 0.5, 0.7, 0.9] {
+                let chroma = max_chroma(ColorSpace::DisplayP3, lightness, hue).unwrap();
+                let inside = convert(
+                    ColorSpace::Oklch,
+                    ColorSpace::DisplayP3,
+                    &[lightness, chroma, hue],
+                );
+                let outside = convert(
+                    ColorSpace::Oklch,
+                    ColorSpace::DisplayP3,
+                    &[lightness, chroma + 0.001, hue],
+                );
+                assert!(in_gamut(ColorSpace::DisplayP3, &inside));
+                assert!(!in_gamut(ColorSpace::DisplayP3, &outside));
+                assert!((boundary.max_chroma(lightness, hue) - chroma).abs() < 0.005);
+            }
+        }
+
+        // Cylindrical color spaces use sRGB's gamut.
+        assert_eq!(
+            max_chroma(ColorSpace::Hsl, 0.5, 180.0),
+            max_chroma(ColorSpace::Srgb, 0.5, 180.0)
+        );
+        assert_eq!(max_chroma(ColorSpace::Srgb, 1.0, 180.0), Some(0.0));
+    }
 }
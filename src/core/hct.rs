@@ -0,0 +1,122 @@
+use crate::core::cam16::default_conditions;
+use crate::core::gamut::find_max_chroma;
+use crate::core::{convert, in_gamut, normalize, ColorSpace};
+use crate::Float;
+
+const EPSILON: Float = 216.0 / 24389.0;
+const KAPPA: Float = 24389.0 / 27.0;
+
+/// Convert the luminance Y to the tone, i.e., CIELAB lightness L*.
+pub(crate) fn y_to_tone(y: Float) -> Float {
+    if y > EPSILON {
+        116.0 * y.cbrt() - 16.0
+    } else {
+        KAPPA * y
+    }
+}
+
+/// Convert the tone, i.e., CIELAB lightness L*, to the luminance Y.
+pub(crate) fn tone_to_y(tone: Float) -> Float {
+    if tone > 8.0 {
+        ((tone + 16.0) / 116.0).powi(3)
+    } else {
+        tone / KAPPA
+    }
+}
+
+/// Convert XYZ to HCT.
+pub(crate) fn xyz_to_hct(value: &[Float; 3]) -> [Float; 3] {
+    let cam16 = default_conditions().xyz_to_cam16(value);
+    [cam16.hue, cam16.chroma, y_to_tone(value[1])]
+}
+
+/// Convert HCT to XYZ.
+///
+/// Since there is no closed-form inverse, this function uses Newton's method
+/// to find the CAM16 lightness J that, together with the given hue and chroma,
+/// yields the luminance for the given tone.
+pub(crate) fn hct_to_xyz(value: &[Float; 3]) -> [Float; 3] {
+    let [hue, chroma, tone] = *value;
+    if tone <= 0.0 {
+        return [0.0, 0.0, 0.0];
+    }
+
+    let conditions = default_conditions();
+    let y = tone_to_y(tone);
+
+    // Initial estimate based on a quadratic fit of J over tone.
+    let mut j = 0.00379058511492914 * tone * tone + 0.608983189401032 * tone + 0.9155088574762233;
+    let mut best = [0.0, 0.0, 0.0];
+    let mut best_delta = Float::INFINITY;
+
+    for _ in 0..16 {
+        let xyz = conditions.jch_to_xyz(&[j, chroma, hue]);
+        let delta = (xyz[1] - y).abs();
+        if delta < best_delta {
+            best = xyz;
+            best_delta = delta;
+            if delta <= 2e-12 {
+                break;
+            }
+        }
+
+        if xyz[1] <= 0.0 {
+            break;
+        }
+        j -= (xyz[1] - y) * j / (2.0 * xyz[1]);
+    }
+
+    best
+}
+
+/// Map the given color coordinates into the gamut of their color space while
+/// preserving HCT hue and tone.
+///
+/// This function performs a binary search over HCT chroma, reducing chroma
+/// until the color fits into the gamut. Unlike the CSS Color 4 gamut mapping
+/// algorithm, it never changes tone and hence preserves contrast.
+pub(crate) fn to_gamut_hct(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
+    let coordinates = normalize(space, coordinates);
+
+    // If the color space is unbounded, there is nothing to map to
+    if !space.is_bounded() || in_gamut(space, &coordinates) {
+        return coordinates;
+    }
+
+    let [hue, chroma, tone] = convert(space, ColorSpace::Hct, &coordinates);
+    find_max_chroma(&space, chroma, 0.0001, |chroma| {
+        convert(ColorSpace::Hct, space, &[hue, chroma, tone])
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hct_to_xyz, tone_to_y, xyz_to_hct};
+    use crate::core::{convert, ColorSpace};
+
+    #[test]
+    fn test_hct() {
+        // Material Design's reference values for sRGB blue. As for CAM16, the
+        // slightly different white point results in slightly different values.
+        let blue = convert(ColorSpace::Srgb, ColorSpace::Xyz, &[0.0, 0.0, 1.0]);
+        let [h, c, t] = xyz_to_hct(&blue);
+        assert!((h - 282.788).abs() < 0.05);
+        assert!((c - 87.23).abs() < 0.01);
+        assert!((t - 32.30).abs() < 0.01);
+
+        for srgb in [
+            [1.0, 0.5, 0.0],
+            [0.2, 0.4, 0.6],
+            [0.9, 0.9, 0.9],
+            [0.0, 0.0, 1.0],
+        ] {
+            let xyz = convert(ColorSpace::Srgb, ColorSpace::Xyz, &srgb);
+            let actual = hct_to_xyz(&xyz_to_hct(&xyz));
+            for (a, e) in actual.iter().zip(xyz.iter()) {
+                assert!((a - e).abs() < 1e-10, "{:?} vs {:?}", actual, xyz);
+            }
+        }
+
+        assert!((tone_to_y(50.0) - 0.18418651851244416).abs() < 1e-15);
+    }
+}
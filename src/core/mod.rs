@@ -1,23 +1,37 @@
 #[cfg(test)]
 mod test_util;
 
+mod cam16;
 mod contrast;
 mod conversion;
+mod cvd;
 mod difference;
 mod gamut;
+mod hct;
+mod names;
+mod rgb;
 mod space;
 mod string;
 
+pub use cam16::{Cam16, Surround, ViewingConditions};
 pub(crate) use contrast::{
     scale_lightness, to_contrast, to_contrast_luminance_p3, to_contrast_luminance_srgb,
 };
 pub(crate) use conversion::{convert, from_24bit, to_24bit};
+pub(crate) use cvd::{daltonize, simulate_cvd};
+pub use cvd::{ColorVisionDeficiency, CvdMethod};
 pub use difference::HueInterpolation;
 pub(crate) use difference::{
-    delta_e_ok, find_closest, interpolate, prepare_to_interpolate, to_eq_bits,
+    alpha_to_eq_bits, delta_e_2000, delta_e_94, delta_e_cmc, delta_e_hyab, delta_e_itp, delta_e_ok,
+    delta_e_z, find_closest, interpolate, mix, premultiply, prepare_alpha_interpolation,
+    prepare_to_interpolate, prepare_to_interpolate_sequence, to_eq_bits, unpremultiply,
 };
-pub(crate) use gamut::{clip, in_gamut, to_gamut};
+pub(crate) use gamut::{clip, in_gamut, to_gamut, to_gamut_with};
+pub use gamut::{GamutBoundary, GamutMapping};
+pub(crate) use hct::to_gamut_hct;
+pub(crate) use names::{from_css_name, from_x11_name, to_css_name, to_x11_name};
+pub use rgb::{RgbSpace, TransferCurve, TransferFunction};
 pub(crate) use space::normalize;
 pub use space::ColorSpace;
-pub use string::ColorFormatError;
-pub(crate) use string::{format, parse};
+pub(crate) use string::{format, format_as, parse, parse_with};
+pub use string::{ColorFormat, ColorFormatError, ColorFormatErrorKind};
@@ -0,0 +1,657 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use crate::core::conversion::hdr::{hlg_to_linear, linear_to_hlg, linear_to_pq, pq_to_linear};
+use crate::core::conversion::multiply;
+use crate::core::gamut::{find_max_chroma, map_to_gamut, map_to_gamut_with, Gamut};
+use crate::core::{convert, ColorSpace, GamutMapping};
+use crate::error::RgbSpaceError;
+use crate::Float;
+
+/// A function converting between encoded and linear values.
+///
+/// This struct wraps the function pointers of [`TransferFunction::Custom`].
+/// Python code can inspect but not create custom transfer functions.
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Copy, Clone, Debug)]
+pub struct TransferCurve(pub fn(Float) -> Float);
+
+/// A transfer function for a user-defined RGB color space.
+///
+/// A transfer function converts between gamma-encoded and linear coordinates.
+/// Consistent with the built-in RGB color spaces, transfer functions extend to
+/// negative values by mirroring the positive values.
+///
+/// # Piecewise
+///
+/// The piecewise transfer function uses a linear segment near zero and a power
+/// law elsewhere. Following the ICC's parametric curves, it converts an
+/// encoded value V to its linear value (aV + b)<sup>γ</sup> for V ≥ d and to cV
+/// otherwise. [`TransferFunction::SRGB`] and [`TransferFunction::REC709`]
+/// provide the parameters for sRGB and Rec. 709 (and hence BT.601).
+///
+/// # Gamma
+///
+/// The gamma transfer function is a pure power law, which converts an encoded
+/// value V to its linear value V<sup>γ</sup>.
+///
+/// # PQ and HLG
+///
+/// The perceptual quantizer (PQ) and hybrid log-gamma (HLG) transfer functions
+/// are the HDR transfer functions of Rec. 2100. Consistent with
+/// [`ColorSpace::Rec2100Pq`](crate::ColorSpace::Rec2100Pq) and
+/// [`ColorSpace::Rec2100Hlg`](crate::ColorSpace::Rec2100Hlg), linear 1.0
+/// corresponds to media white at 203 cd/m². [`TransferFunction::PQ`] and
+/// [`TransferFunction::HLG`] provide both transfer functions.
+///
+/// # Custom
+///
+/// The custom transfer function uses two functions, the first converting an
+/// encoded value to its linear value and the second converting a linear value
+/// to its encoded value. Unlike for the other transfer functions, these
+/// functions are responsible for handling negative values. Since a
+/// [`TransferCurve`] wraps a Rust function pointer, only Rust code can create
+/// custom transfer functions.
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Copy, Clone, Debug)]
+pub enum TransferFunction {
+    Piecewise {
+        gamma: Float,
+        a: Float,
+        b: Float,
+        c: Float,
+        d: Float,
+    },
+    Gamma {
+        gamma: Float,
+    },
+    Pq(),
+    Hlg(),
+    Custom {
+        to_linear: TransferCurve,
+        from_linear: TransferCurve,
+    },
+}
+
+impl TransferFunction {
+    /// sRGB's transfer function.
+    pub const SRGB: Self = Self::Piecewise {
+        gamma: 2.4,
+        a: 1.0 / 1.055,
+        b: 0.055 / 1.055,
+        c: 1.0 / 12.92,
+        d: 0.04045,
+    };
+
+    /// Rec. 709's transfer function, which is also used by BT.601.
+    pub const REC709: Self = Self::Piecewise {
+        gamma: 1.0 / 0.45,
+        a: 1.0 / 1.099,
+        b: 0.099 / 1.099,
+        c: 1.0 / 4.5,
+        d: 0.081,
+    };
+
+    /// Rec. 2100's perceptual quantizer.
+    pub const PQ: Self = Self::Pq();
+
+    /// Rec. 2100's hybrid log-gamma.
+    pub const HLG: Self = Self::Hlg();
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl TransferFunction {
+    /// sRGB's transfer function. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "SRGB")]
+    pub fn srgb() -> Self {
+        Self::SRGB
+    }
+
+    /// Rec. 709's transfer function. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "REC709")]
+    pub fn rec709() -> Self {
+        Self::REC709
+    }
+
+    /// Rec. 2100's perceptual quantizer. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "PQ")]
+    pub fn pq() -> Self {
+        Self::PQ
+    }
+
+    /// Rec. 2100's hybrid log-gamma. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "HLG")]
+    pub fn hlg() -> Self {
+        Self::HLG
+    }
+
+    /// Convert the encoded value to its linear value.
+    pub fn to_linear(&self, value: Float) -> Float {
+        let magnitude = value.abs();
+        let linear = match *self {
+            Self::Piecewise { gamma, a, b, c, d } => {
+                if magnitude < d {
+                    c * magnitude
+                } else {
+                    a.mul_add(magnitude, b).powf(gamma)
+                }
+            }
+            Self::Gamma { gamma } => magnitude.powf(gamma),
+            Self::Pq() => pq_to_linear(magnitude),
+            Self::Hlg() => hlg_to_linear(magnitude),
+            Self::Custom { to_linear, .. } => return (to_linear.0)(value),
+        };
+
+        linear.copysign(value)
+    }
+
+    /// Convert the linear value to its encoded value.
+    pub fn from_linear(&self, value: Float) -> Float {
+        let magnitude = value.abs();
+        let encoded = match *self {
+            Self::Piecewise { gamma, a, b, c, d } => {
+                if magnitude < c * d {
+                    magnitude / c
+                } else {
+                    (magnitude.powf(gamma.recip()) - b) / a
+                }
+            }
+            Self::Gamma { gamma } => magnitude.powf(gamma.recip()),
+            Self::Pq() => linear_to_pq(magnitude),
+            Self::Hlg() => linear_to_hlg(magnitude),
+            Self::Custom { from_linear, .. } => return (from_linear.0)(value),
+        };
+
+        encoded.copysign(value)
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Compute the inverse of the 3 by 3 matrix. This function returns `None` if
+/// the matrix is singular.
+fn invert(matrix: &[[Float; 3]; 3]) -> Option<[[Float; 3]; 3]> {
+    let [[a, b, c], [d, e, f], [g, h, i]] = *matrix;
+
+    let co1 = e * i - f * h;
+    let co2 = f * g - d * i;
+    let co3 = d * h - e * g;
+    let determinant = a * co1 + b * co2 + c * co3;
+
+    if !determinant.is_finite() || determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    let factor = determinant.recip();
+    Some([
+        [
+            co1 * factor,
+            (c * h - b * i) * factor,
+            (b * f - c * e) * factor,
+        ],
+        [
+            co2 * factor,
+            (a * i - c * g) * factor,
+            (c * d - a * f) * factor,
+        ],
+        [
+            co3 * factor,
+            (b * g - a * h) * factor,
+            (a * e - b * d) * factor,
+        ],
+    ])
+}
+
+/// Multiply the two 3 by 3 matrices with each other.
+fn multiply_matrices(m1: &[[Float; 3]; 3], m2: &[[Float; 3]; 3]) -> [[Float; 3]; 3] {
+    let mut result = [[0.0; 3]; 3];
+    for (row, result_row) in result.iter_mut().enumerate() {
+        for (column, cell) in result_row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| m1[row][k] * m2[k][column]).sum();
+        }
+    }
+    result
+}
+
+/// Convert the xy chromaticity to XYZ with Y = 1.
+fn xy_to_xyz(xy: &[Float; 2]) -> Option<[Float; 3]> {
+    let [x, y] = *xy;
+    if !x.is_finite() || !y.is_finite() || y <= 0.0 {
+        return None;
+    }
+
+    Some([x / y, 1.0, (1.0 - x - y) / y])
+}
+
+/// The D65 white point used by XYZ, which is the root of the conversion tree.
+const D65: [Float; 2] = [0.3127, 0.3290];
+
+#[rustfmt::skip]
+const BRADFORD: [[Float; 3]; 3] = [
+    [  0.8951,  0.2664, -0.1614 ],
+    [ -0.7502,  1.7135,  0.0367 ],
+    [  0.0389, -0.0685,  1.0296 ],
+];
+
+/// Compute the Bradford chromatic adaptation transform from the source to the
+/// target white point, both given as XYZ coordinates.
+fn bradford(source: &[Float; 3], target: &[Float; 3]) -> Option<[[Float; 3]; 3]> {
+    let [s1, s2, s3] = multiply(&BRADFORD, source);
+    let [t1, t2, t3] = multiply(&BRADFORD, target);
+    if s1 == 0.0 || s2 == 0.0 || s3 == 0.0 {
+        return None;
+    }
+
+    let scale = [
+        [t1 / s1, 0.0, 0.0],
+        [0.0, t2 / s2, 0.0],
+        [0.0, 0.0, t3 / s3],
+    ];
+
+    let inverse = invert(&BRADFORD)?;
+    Some(multiply_matrices(
+        &inverse,
+        &multiply_matrices(&scale, &BRADFORD),
+    ))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A user-defined RGB color space.
+///
+/// An RGB color space is fully determined by the xy chromaticities of its
+/// three primaries, the xy chromaticity of its white point, and its transfer
+/// function. Upon creation, this struct computes the matrices for converting
+/// between linear coordinates and XYZ. If the white point is not D65, those
+/// matrices incorporate the Bradford chromatic adaptation transform, since
+/// this crate uses XYZ with the D65 standard illuminant as the root of all
+/// conversions.
+///
+/// Since [`ColorSpace`] is a closed enumeration, a color in a user-defined RGB
+/// color space uses XYZ as its color space but also remembers the RGB color
+/// space. Hence [`Color::in_gamut`](crate::Color::in_gamut),
+/// [`Color::to_gamut`](crate::Color::to_gamut), and
+/// [`Color::contrast_against`](crate::Color::contrast_against) respect the
+/// user-defined color space's gamut. Use
+/// [`Color::from_rgb_space`](crate::Color::from_rgb_space) and
+/// [`Color::to_rgb_space`](crate::Color::to_rgb_space) to create such colors
+/// and [`Color::to`](crate::Color::to) to convert them to built-in color
+/// spaces.
+///
+/// # Examples
+///
+/// The example below recreates sRGB from its primaries, white point, and
+/// transfer function. It then converts a color to sRGB and Display P3 as well
+/// as Display P3's green primary to the recreated sRGB, where it is out of
+/// gamut.
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, RgbSpace, RgbSpaceError, TransferFunction};
+/// let srgb = RgbSpace::new(
+///     [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+///     [0.3127, 0.3290],
+///     TransferFunction::SRGB,
+/// )?;
+///
+/// let orange = Color::from_rgb_space(&srgb, [1.0, 0.5, 0.0]);
+/// assert_eq!(orange.space(), ColorSpace::Xyz);
+/// assert_eq!(orange.to(ColorSpace::Srgb), Color::srgb(1.0, 0.5, 0.0));
+///
+/// let also_orange = orange.to(ColorSpace::DisplayP3);
+/// assert!(also_orange.in_gamut());
+///
+/// let green = Color::p3(0, 1, 0).to_rgb_space(&srgb);
+/// assert!(!green.in_gamut());
+/// assert!(green.to_gamut().in_gamut());
+/// assert_eq!(green.to_gamut().to_hex_format(), "#00fb29");
+/// # Ok::<(), RgbSpaceError>(())
+/// ```
+/// <div class=color-swatch>
+/// <div style="background-color: color(srgb 1 0.5 0);"></div>
+/// <div style="background-color: color(display-p3 0 1 0);"></div>
+/// <div style="background-color: #00fb29;"></div>
+/// </div>
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Clone, Debug)]
+pub struct RgbSpace {
+    primaries: [[Float; 2]; 3],
+    white_point: [Float; 2],
+    transfer: TransferFunction,
+    to_xyz: [[Float; 3]; 3],
+    from_xyz: [[Float; 3]; 3],
+}
+
+impl RgbSpace {
+    fn create(
+        primaries: [[Float; 2]; 3],
+        white_point: [Float; 2],
+        transfer: TransferFunction,
+    ) -> Result<Self, RgbSpaceError> {
+        let [r, g, b] = primaries;
+        let [r, g, b] = [
+            xy_to_xyz(&r).ok_or(RgbSpaceError::InvalidChromaticity)?,
+            xy_to_xyz(&g).ok_or(RgbSpaceError::InvalidChromaticity)?,
+            xy_to_xyz(&b).ok_or(RgbSpaceError::InvalidChromaticity)?,
+        ];
+        let white = xy_to_xyz(&white_point).ok_or(RgbSpaceError::InvalidChromaticity)?;
+
+        // Scale the primaries so that they sum to the white point.
+        let columns = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+        let inverse = invert(&columns).ok_or(RgbSpaceError::CollinearPrimaries)?;
+        let [sr, sg, sb] = multiply(&inverse, &white);
+        let to_native_xyz = [
+            [sr * r[0], sg * g[0], sb * b[0]],
+            [sr * r[1], sg * g[1], sb * b[1]],
+            [sr * r[2], sg * g[2], sb * b[2]],
+        ];
+
+        // Adapt to D65 as necessary.
+        let to_xyz = if white_point == D65 {
+            to_native_xyz
+        } else {
+            // SAFETY: D65 is a valid chromaticity.
+            let d65 = xy_to_xyz(&D65).unwrap();
+            let adaptation = bradford(&white, &d65).ok_or(RgbSpaceError::InvalidChromaticity)?;
+            multiply_matrices(&adaptation, &to_native_xyz)
+        };
+        let from_xyz = invert(&to_xyz).ok_or(RgbSpaceError::CollinearPrimaries)?;
+
+        Ok(Self {
+            primaries,
+            white_point,
+            transfer,
+            to_xyz,
+            from_xyz,
+        })
+    }
+
+    /// Map the coordinates into the gamut of this color space while
+    /// preserving HCT hue and tone.
+    pub(crate) fn to_gamut_hct(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        let coordinates = coordinates.map(|c| if c.is_nan() { 0.0 } else { c });
+        if self.in_gamut(coordinates) {
+            return coordinates;
+        }
+
+        let [hue, chroma, tone] =
+            convert(ColorSpace::Xyz, ColorSpace::Hct, &self.to_xyz(coordinates));
+        find_max_chroma(self, chroma, 0.0001, |chroma| {
+            self.from_xyz(convert(
+                ColorSpace::Hct,
+                ColorSpace::Xyz,
+                &[hue, chroma, tone],
+            ))
+        })
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl RgbSpace {
+    /// Create a new RGB color space.
+    ///
+    /// The primaries are the xy chromaticities of red, green, and blue, in
+    /// that order. The white point is the xy chromaticity of the color space's
+    /// white. This method fails if any chromaticity has a non-positive y
+    /// coordinate or if the primaries are collinear.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(
+        primaries: [[Float; 2]; 3],
+        white_point: [Float; 2],
+        transfer: TransferFunction,
+    ) -> Result<Self, RgbSpaceError> {
+        Self::create(primaries, white_point, transfer)
+    }
+
+    /// Create a new RGB color space.
+    ///
+    /// The primaries are the xy chromaticities of red, green, and blue, in
+    /// that order. The white point is the xy chromaticity of the color space's
+    /// white. This method fails if any chromaticity has a non-positive y
+    /// coordinate or if the primaries are collinear.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(
+        primaries: [[Float; 2]; 3],
+        white_point: [Float; 2],
+        transfer: TransferFunction,
+    ) -> Result<Self, RgbSpaceError> {
+        Self::create(primaries, white_point, transfer)
+    }
+
+    /// Get the xy chromaticities of this color space's primaries.
+    pub fn primaries(&self) -> [[Float; 2]; 3] {
+        self.primaries
+    }
+
+    /// Get the xy chromaticity of this color space's white point.
+    pub fn white_point(&self) -> [Float; 2] {
+        self.white_point
+    }
+
+    /// Get this color space's transfer function.
+    pub fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+
+    /// Convert the coordinates in this color space to linear coordinates.
+    pub fn to_linear(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        coordinates.map(|c| self.transfer.to_linear(c))
+    }
+
+    /// Convert the linear coordinates to coordinates in this color space.
+    pub fn from_linear(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        coordinates.map(|c| self.transfer.from_linear(c))
+    }
+
+    /// Convert the coordinates in this color space to XYZ.
+    pub fn to_xyz(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        multiply(&self.to_xyz, &self.to_linear(coordinates))
+    }
+
+    /// Convert the XYZ coordinates to coordinates in this color space.
+    pub fn from_xyz(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        self.from_linear(multiply(&self.from_xyz, &coordinates))
+    }
+
+    /// Determine whether the coordinates are in gamut for this color space.
+    pub fn in_gamut(&self, coordinates: [Float; 3]) -> bool {
+        coordinates.iter().all(|c| (0.0..=1.0).contains(c))
+    }
+
+    /// Clip the coordinates to the gamut of this color space.
+    pub fn clip(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        coordinates.map(|c| c.clamp(0.0, 1.0))
+    }
+
+    /// Map the coordinates into the gamut of this color space.
+    ///
+    /// This method uses the same CSS Color 4 algorithm as
+    /// [`Color::to_gamut`](crate::Color::to_gamut).
+    pub fn to_gamut(&self, coordinates: [Float; 3]) -> [Float; 3] {
+        map_to_gamut(self, &coordinates.map(|c| if c.is_nan() { 0.0 } else { c }))
+    }
+
+    /// Map the coordinates into the gamut of this color space with the given
+    /// algorithm.
+    ///
+    /// This method uses the same algorithms as
+    /// [`Color::to_gamut_with`](crate::Color::to_gamut_with).
+    pub fn to_gamut_with(&self, mapping: GamutMapping, coordinates: [Float; 3]) -> [Float; 3] {
+        map_to_gamut_with(
+            mapping,
+            self,
+            &coordinates.map(|c| if c.is_nan() { 0.0 } else { c }),
+        )
+    }
+
+    /// Compute the contrast luminance for the coordinates in this color space.
+    ///
+    /// Just like for sRGB and Display P3, the contrast luminance weighs the
+    /// linear coordinates by the Y row of the matrix converting linear
+    /// coordinates to XYZ. However, whereas the built-in color spaces
+    /// linearize with APCA's fixed exponent of 2.4, which approximates sRGB's
+    /// transfer function, this method linearizes with this color space's
+    /// transfer function. After all, a linear or pure-gamma color space has
+    /// little in common with sRGB's encoding.
+    pub fn to_contrast_luminance(&self, coordinates: [Float; 3]) -> Float {
+        let [r, g, b] = self.to_linear(coordinates);
+        let [c1, c2, c3] = self.to_xyz[1];
+        r.mul_add(c1, g.mul_add(c2, b * c3))
+    }
+}
+
+impl Gamut for RgbSpace {
+    fn to_oklab(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        convert(
+            ColorSpace::Xyz,
+            ColorSpace::Oklab,
+            &self.to_xyz(*coordinates),
+        )
+    }
+
+    fn to_oklch(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        convert(
+            ColorSpace::Xyz,
+            ColorSpace::Oklch,
+            &self.to_xyz(*coordinates),
+        )
+    }
+
+    fn oklch_to_space(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        self.from_xyz(convert(ColorSpace::Oklch, ColorSpace::Xyz, coordinates))
+    }
+
+    fn in_gamut(&self, coordinates: &[Float; 3]) -> bool {
+        RgbSpace::in_gamut(self, *coordinates)
+    }
+
+    fn clip(&self, coordinates: &[Float; 3]) -> [Float; 3] {
+        RgbSpace::clip(self, *coordinates)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RgbSpace, TransferCurve, TransferFunction};
+    use crate::core::test_util::close_enough;
+    use crate::core::{convert, to_contrast, ColorSpace};
+    use crate::{Color, RgbSpaceError};
+
+    #[test]
+    fn test_rgb_space() {
+        // sRGB and ProPhoto RGB, with the latter using D50.
+        let srgb = RgbSpace::new(
+            [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+            [0.3127, 0.3290],
+            TransferFunction::SRGB,
+        )
+        .unwrap();
+        let prophoto = RgbSpace::new(
+            [
+                [0.734699, 0.265301],
+                [0.159597, 0.840403],
+                [0.036598, 0.000105],
+            ],
+            [0.3457, 0.3585],
+            TransferFunction::Piecewise {
+                gamma: 1.8,
+                a: 1.0,
+                b: 0.0,
+                c: 1.0 / 16.0,
+                d: 16.0 / 512.0,
+            },
+        )
+        .unwrap();
+
+        for coordinates in [[1.0, 0.5, 0.0], [0.2, 0.4, 0.6], [0.9, 0.9, 0.9]] {
+            let xyz = convert(ColorSpace::Srgb, ColorSpace::Xyz, &coordinates);
+            assert!(close_enough(&srgb.to_xyz(coordinates), &xyz, false));
+            assert!(close_enough(&srgb.from_xyz(xyz), &coordinates, false));
+
+            let xyz = convert(ColorSpace::ProPhotoRgb, ColorSpace::Xyz, &coordinates);
+            assert!(close_enough(&prophoto.to_xyz(coordinates), &xyz, false));
+            assert!(close_enough(&prophoto.from_xyz(xyz), &coordinates, false));
+        }
+
+        // Rec. 2020 primaries with PQ and HLG match Rec. 2100.
+        let primaries = [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]];
+        let white = [0.3127, 0.3290];
+        let pq = RgbSpace::new(primaries, white, TransferFunction::PQ).unwrap();
+        let hlg = RgbSpace::new(primaries, white, TransferFunction::HLG).unwrap();
+
+        for coordinates in [[0.5, 0.25, 0.0], [0.2, 0.4, 0.6], [0.58, 0.58, 0.58]] {
+            let xyz = convert(ColorSpace::Rec2100Pq, ColorSpace::Xyz, &coordinates);
+            assert!(close_enough(&pq.to_xyz(coordinates), &xyz, false));
+            let xyz = convert(ColorSpace::Rec2100Hlg, ColorSpace::Xyz, &coordinates);
+            assert!(close_enough(&hlg.to_xyz(coordinates), &xyz, false));
+        }
+
+        // Contrast luminance linearizes with the transfer function.
+        let linear = RgbSpace::new(
+            [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+            [0.3127, 0.3290],
+            TransferFunction::Gamma { gamma: 1.0 },
+        )
+        .unwrap();
+        for coordinates in [[0.5, 0.5, 0.5], [0.2, 0.4, 0.6]] {
+            let xyz = linear.to_xyz(coordinates);
+            assert!((linear.to_contrast_luminance(coordinates) - xyz[1]).abs() < 1e-12);
+        }
+        assert!((linear.to_contrast_luminance([0.5, 0.5, 0.5]) - 0.5).abs() < 1e-12);
+
+        // A custom transfer function behaves like the equivalent built-in one.
+        let gamma = TransferFunction::Gamma { gamma: 2.0 };
+        let custom = TransferFunction::Custom {
+            to_linear: TransferCurve(|value| value * value.abs()),
+            from_linear: TransferCurve(|value| value.abs().sqrt().copysign(value)),
+        };
+        for value in [-0.5, 0.0, 0.25, 0.8, 1.0] {
+            assert!((custom.to_linear(value) - gamma.to_linear(value)).abs() < 1e-12);
+            assert!((custom.from_linear(value) - gamma.from_linear(value)).abs() < 1e-12);
+        }
+
+        // Colors remember user-defined RGB color spaces until converted.
+        let gray = Color::from_rgb_space(&linear, [0.5, 0.5, 0.5]);
+        assert!(gray.rgb_space().is_some());
+        assert!(gray.with_alpha(0.5).rgb_space().is_some());
+        assert!(gray.to(ColorSpace::Srgb).rgb_space().is_none());
+        assert_eq!(gray, Color::new(ColorSpace::Xyz, *gray.as_ref()));
+        assert!(close_enough(
+            &gray.rgb_space_coordinates().unwrap(),
+            &[0.5, 0.5, 0.5],
+            false
+        ));
+
+        // Contrast uses the user-defined color space's transfer function.
+        let black = Color::from_rgb_space(&linear, [0.0, 0.0, 0.0]);
+        assert!((gray.contrast_against(&black) - to_contrast(0.5, 0.0)).abs() < 1e-12);
+
+        // Collinear primaries and invalid chromaticities are errors.
+        assert_eq!(
+            RgbSpace::new(
+                [[0.1, 0.1], [0.2, 0.2], [0.3, 0.3]],
+                [0.3127, 0.3290],
+                TransferFunction::SRGB
+            )
+            .unwrap_err(),
+            RgbSpaceError::CollinearPrimaries
+        );
+        assert_eq!(
+            RgbSpace::new(
+                [[0.64, 0.33], [0.30, 0.60], [0.15, 0.0]],
+                [0.3127, 0.3290],
+                TransferFunction::SRGB
+            )
+            .unwrap_err(),
+            RgbSpaceError::InvalidChromaticity
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
+use crate::core::gamut::{cusp, max_chroma, GamutBoundary};
 use crate::Float;
 
 /// The enumeration of supported color spaces.
@@ -14,13 +15,63 @@ use crate::Float;
 ///     default color space for the web.
 ///   * [Display P3](https://en.wikipedia.org/wiki/DCI-P3), which is
 ///     well-positioned to become sRGB's successor.
+///   * [A98 RGB](https://en.wikipedia.org/wiki/Adobe_RGB_color_space), i.e.,
+///     Adobe RGB (1998), which is widely used for photography and print.
 ///   * [Rec. 2020](https://en.wikipedia.org/wiki/Rec._2020), which is the
 ///     standard color space for ultra-high-definition (UDH) video and, when it
 ///     comes to display hardware, currently aspirational.
+///   * [ProPhoto RGB](https://en.wikipedia.org/wiki/ProPhoto_RGB_color_space),
+///     which is used for archiving and editing photographs. Unlike the other
+///     RGB color spaces, it uses the D50 standard illuminant and some of its
+///     primaries are imaginary colors.
 ///
-/// For all three color spaces as well as all three linear versions, in-gamut
+/// For all five color spaces as well as all five linear versions, in-gamut
 /// coordinates range from 0 to 1, inclusive.
 ///
+/// # HDR
+///
+/// This crate further supports several color spaces for high dynamic range
+/// (HDR) content. [Rec. 2100](https://en.wikipedia.org/wiki/Rec._2100) uses
+/// the same primaries as Rec. 2020 but replaces the transfer function with
+/// either the perceptual quantizer (PQ) or hybrid log-gamma (HLG). For both
+/// `Rec2100Pq` and `Rec2100Hlg`, in-gamut coordinates range `0..=1` and linear
+/// 1.0 corresponds to media white at 203 cd/m², as recommended by ITU-R
+/// BT.2408. As a result, PQ-encoded coordinates for SDR colors do not exceed
+/// 0.581 and HLG-encoded coordinates do not exceed 0.75.
+///
+/// [ICtCp](https://en.wikipedia.org/wiki/ICtCp) and
+/// [Jzazbz](https://doi.org/10.1364/OE.25.015131) are perceptually uniform
+/// color spaces designed for HDR content. JzCzhz is Jzazbz's polar form. All
+/// three treat XYZ 1.0 as media white at 203 cd/m².
+///
+/// | Color space | Lightness | Colorness 1 | Colorness 2 |
+/// | :---------- | :-------: | :---------: | :---------: |
+/// | Ictcp       | I         | Ct          | Cp          |
+/// | Jzazbz      | Jz        | az          | bz          |
+/// | Jzczhz      | Jz        | Cz          | hz          |
+///
+/// All three color spaces are unbounded. For SDR colors, intensity I ranges
+/// `0..=0.581` and lightness Jz ranges `0..=0.222`. Chroma Cz must be
+/// non-negative.
+///
+/// # HSL, HSV, and HWB
+///
+/// This crate further supports the cylindrical transformations of sRGB,
+/// [HSL and HSV](https://en.wikipedia.org/wiki/HSL_and_HSV) as well as
+/// [HWB](https://en.wikipedia.org/wiki/HWB_color_model). While not
+/// perceptually uniform, they have long been used for color pickers and, in
+/// case of HSL and HWB, are supported by CSS through the `hsl()` and `hwb()`
+/// functions. All three color spaces share sRGB's gamut.
+///
+/// | Color space | Hue | Colorness 1 | Colorness 2 |
+/// | :---------- | :-: | :---------: | :---------: |
+/// | Hsl         | hº  | Saturation  | Lightness   |
+/// | Hsv         | hº  | Saturation  | Value       |
+/// | Hwb         | hº  | Whiteness   | Blackness   |
+///
+/// Unlike for the other polar color spaces, the hue comes first. Consistent
+/// with CSS, the other two coordinates of in-gamut colors range `0..=100`.
+///
 /// # Ok(l/lr)(ab/ch)
 ///
 /// This crate supports the
@@ -66,12 +117,89 @@ use crate::Float;
 /// that a and b need [to be scaled by a factor of around
 /// 2.1](https://github.com/w3c/csswg-drafts/issues/6642#issuecomment-945714988).
 ///
+/// # Okhsv/Okhsl
+///
+/// Björn Ottosson also designed [Okhsv and
+/// Okhsl](https://bottosson.github.io/posts/colorpicker/), two cylindrical
+/// color spaces derived from Oklab for use in color pickers. Unlike Oklch with
+/// its unbounded chroma, both color spaces have a saturation that ranges
+/// `0..=1` for colors in sRGB's gamut. To achieve that, they rely on an
+/// approximation of the sRGB gamut's cusp, i.e., the color with maximum chroma
+/// for a given hue. As a consequence, both color spaces have the same gamut as
+/// sRGB.
+///
+/// | Color space | Hue | Colorness 1 | Colorness 2 |
+/// | :---------- | :-: | :---------: | :---------: |
+/// | Okhsv       | hº  | Saturation  | Value       |
+/// | Okhsl       | hº  | Saturation  | Lightness   |
+///
+/// As for HSL, HSV, and HWB, the hue comes first. It is the same hue as for
+/// Oklch. Saturation, value, and lightness range `0..=1`. Okhsl's lightness
+/// is the same as Oklrab's revised lightness Lr.
+///
+/// # CIELAB/CIELCh
+///
+/// This crate also supports
+/// [CIELAB](https://en.wikipedia.org/wiki/CIELAB_color_space) and its polar
+/// form CIELCh, which remain widely used in print and design tools and, through
+/// the `lab()` and `lch()` functions, also in CSS. Like CSS, this crate uses
+/// both color spaces with the D50 standard illuminant.
+///
+/// | Color space | Lightness | Colorness 1 | Colorness 2 |
+/// | :---------- | :-------: | :---------: | :---------: |
+/// | Lab         | L         | a           | b           |
+/// | Lch         | L         | C           | hº          |
+///
+/// For both color spaces, the lightness ranges `0..=100`. The a/b coordinates
+/// are not restricted but pragmatically bounded `-125..=125`. Chroma must be
+/// non-negative and is pragmatically bounded `0..=150`.
+///
+/// # CAM16-UCS
+///
+/// This crate also supports the uniform color space
+/// [CAM16-UCS](https://doi.org/10.1002/col.22131), which is derived from the
+/// CAM16 color appearance model. Unlike Oklab and CIELAB, CAM16 takes viewing
+/// conditions into account. `Cam16Ucs` uses the default
+/// [`ViewingConditions`](crate::ViewingConditions), which are the same as for
+/// Material Design's HCT. To use other viewing conditions, such as a dark
+/// surround, use [`ViewingConditions`](crate::ViewingConditions) directly.
+///
+/// | Color space | Lightness | Colorness 1 | Colorness 2 |
+/// | :---------- | :-------: | :---------: | :---------: |
+/// | Cam16Ucs    | J'        | a'          | b'          |
+///
+/// The lightness J' ranges `0..=100`. The a'/b' coordinates are not
+/// restricted but pragmatically bounded `-50..=50`.
+///
+/// # HCT
+///
+/// Material Design's
+/// [HCT](https://material.io/blog/science-of-color-design) combines the hue
+/// and chroma of CAM16 under the default viewing conditions with the tone,
+/// i.e., CIELAB's lightness L*. Since tone depends on luminance only, colors
+/// with the same tone have the same contrast against other colors. That makes
+/// HCT well-suited to generating tonal palettes, which vary tone while keeping
+/// hue and chroma constant. See [`TonalPalette`](crate::TonalPalette).
+///
+/// | Color space | Hue | Colorness 1 | Colorness 2 |
+/// | :---------- | :-: | :---------: | :---------: |
+/// | Hct         | hº  | Chroma      | Tone        |
+///
+/// As for HSL and Okhsv, the hue comes first. Chroma must be non-negative and
+/// is pragmatically bounded `0..=150`. Tone ranges `0..=100`. Since there is
+/// no closed-form conversion from HCT, conversions to XYZ numerically solve for
+/// the CAM16 lightness.
+///
 /// # XYZ
 ///
 /// [XYZ](https://en.wikipedia.org/wiki/CIE_1931_color_space) serves as
 /// foundational color space. Notably, all conversions between unrelated color
 /// spaces go through XYZ. This crate uses XYZ with the [D65 standard
-/// illuminant](https://en.wikipedia.org/wiki/Standard_illuminant), *not* D50.
+/// illuminant](https://en.wikipedia.org/wiki/Standard_illuminant), *not* D50,
+/// as its root. It also supports XYZ with the D50 standard illuminant, which
+/// serves as intermediate color space for CIELAB/CIELCh and ProPhoto RGB.
+/// Conversions between the two versions of XYZ use the [Bradford chromatic
+/// adaptation transform](http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html).
 #[doc = include_str!("../style.html")]
 #[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -82,19 +210,51 @@ pub enum ColorSpace {
     LinearDisplayP3,
     Rec2020,
     LinearRec2020,
+    Rec2100Pq,
+    Rec2100Hlg,
+    A98Rgb,
+    LinearA98Rgb,
+    ProPhotoRgb,
+    LinearProPhotoRgb,
+    Hsl,
+    Hsv,
+    Hwb,
     Oklab,
     Oklch,
     Oklrab,
     Oklrch,
+    Okhsv,
+    Okhsl,
+    Lab,
+    Lch,
+    Cam16Ucs,
+    Hct,
+    Ictcp,
+    Jzazbz,
+    Jzczhz,
     Xyz,
+    XyzD50,
 }
 
 #[cfg_attr(feature = "pyffi", pymethods)]
 impl ColorSpace {
-    /// Determine whether this color space is polar. Oklch and Oklrch currently
-    /// are the only polar color spaces.
+    /// Determine whether this color space is polar. Oklch, Oklrch, Okhsv,
+    /// Okhsl, Lch, HSL, HSV, HWB, HCT, and JzCzhz currently are the only polar
+    /// color spaces.
     pub const fn is_polar(&self) -> bool {
-        matches!(*self, Self::Oklch | Self::Oklrch)
+        self.hue_index().is_some()
+    }
+
+    /// Determine the index of the hue coordinate for polar color spaces. For
+    /// Oklch, Oklrch, Lch, and JzCzhz, the hue is the third coordinate, whereas
+    /// for HSL, HSV, HWB, Okhsv, Okhsl, and HCT, it is the first coordinate.
+    pub const fn hue_index(&self) -> Option<usize> {
+        use ColorSpace::*;
+        match *self {
+            Oklch | Oklrch | Lch | Jzczhz => Some(2),
+            Hsl | Hsv | Hwb | Okhsv | Okhsl | Hct => Some(0),
+            _ => None,
+        }
     }
 
     /// Determine whether this color space is RGB, that is, has red, green, and
@@ -104,7 +264,17 @@ impl ColorSpace {
         use ColorSpace::*;
         matches!(
             *self,
-            Srgb | LinearSrgb | DisplayP3 | LinearDisplayP3 | Rec2020 | LinearRec2020
+            Srgb | LinearSrgb
+                | DisplayP3
+                | LinearDisplayP3
+                | Rec2020
+                | LinearRec2020
+                | Rec2100Pq
+                | Rec2100Hlg
+                | A98Rgb
+                | LinearA98Rgb
+                | ProPhotoRgb
+                | LinearProPhotoRgb
         )
     }
 
@@ -114,13 +284,88 @@ impl ColorSpace {
         matches!(*self, Oklab | Oklch | Oklrab | Oklrch)
     }
 
-    /// Determine whether this color space is bounded. XYZ and the Oklab
+    /// Determine whether this color space is one of the CIELAB variations.
+    pub const fn is_lab(&self) -> bool {
+        matches!(*self, Self::Lab | Self::Lch)
+    }
+
+    /// Determine whether this color space is a cylindrical model of sRGB's
+    /// gamut, i.e., HSL, HSV, HWB, Okhsv, or Okhsl. All five have the same
+    /// gamut as sRGB.
+    pub const fn is_cylindrical(&self) -> bool {
+        use ColorSpace::*;
+        matches!(*self, Hsl | Hsv | Hwb | Okhsv | Okhsl)
+    }
+
+    /// Determine whether this color space is bounded. XYZ, the Oklab
+    /// variations, the CIELAB variations, CAM16-UCS, HCT, ICtCp, and the Jzazbz
     /// variations are *unbounded* and hence can model any color, whereas the
-    /// RGB color spaces are *bounded* and hence colors may be in-gamut or
-    /// out-of-gamut. Conveniently, the coordinates of in-gamut RGB colors range
-    /// `0..=1`.
+    /// RGB color spaces and their cylindrical transformations are *bounded* and
+    /// hence colors may be in-gamut or out-of-gamut. Conveniently, the
+    /// coordinates of in-gamut RGB colors range `0..=1`.
     pub const fn is_bounded(&self) -> bool {
-        self.is_rgb()
+        self.is_rgb() || self.is_cylindrical()
+    }
+
+    /// Determine the maximum chroma for the lightness and hue in this color
+    /// space's gamut.
+    ///
+    /// This method returns the largest Oklch chroma, for which the color with
+    /// the given Oklch lightness and hue is in gamut for this color space. For
+    /// HSL, HSV, HWB, Okhsv, and Okhsl, that is sRGB's gamut. This method
+    /// returns `None` for unbounded color spaces and zero for lightness values
+    /// outside `0..1`. Use [`ColorSpace::gamut_boundary`] to answer many such
+    /// queries more quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let chroma = ColorSpace::Srgb.max_chroma(0.7, 145.0).unwrap();
+    /// assert!((chroma - 0.2202).abs() < 0.0001);
+    ///
+    /// let green = Color::oklch(0.7, chroma, 145.0).to(ColorSpace::Srgb);
+    /// assert!(green.in_gamut());
+    ///
+    /// let p3_chroma = ColorSpace::DisplayP3.max_chroma(0.7, 145.0).unwrap();
+    /// assert!(chroma < p3_chroma);
+    /// assert_eq!(ColorSpace::Oklab.max_chroma(0.7, 145.0), None);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: oklch(0.7 0.2202 145);"></div>
+    /// </div>
+    pub fn max_chroma(&self, lightness: Float, hue: Float) -> Option<Float> {
+        max_chroma(*self, lightness, hue)
+    }
+
+    /// Determine the cusp for the hue in this color space's gamut.
+    ///
+    /// The cusp is the most chromatic in-gamut color for the given Oklch hue.
+    /// This method returns its Oklch lightness and chroma. It returns `None`
+    /// for unbounded color spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::ColorSpace;
+    /// let [lightness, chroma] = ColorSpace::Srgb.cusp(29.234).unwrap();
+    /// assert!((lightness - 0.628).abs() < 0.001);
+    /// assert!((chroma - 0.258).abs() < 0.001);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: oklch(0.628 0.258 29.234);"></div>
+    /// </div>
+    pub fn cusp(&self, hue: Float) -> Option<[Float; 2]> {
+        cusp(*self, hue)
+    }
+
+    /// Create a cached table of this color space's gamut boundary.
+    ///
+    /// This method returns `None` for unbounded color spaces. Since it
+    /// performs tens of thousands of searches, it should be invoked once and
+    /// the result reused.
+    pub fn gamut_boundary(&self) -> Option<GamutBoundary> {
+        GamutBoundary::new(*self)
     }
 
     /// Create a human-readable representation for this color space.
@@ -142,11 +387,30 @@ impl std::fmt::Display for ColorSpace {
             LinearDisplayP3 => "linear Display P3",
             Rec2020 => "Rec. 2020",
             LinearRec2020 => "linear Rec. 2020",
+            Rec2100Pq => "Rec. 2100 PQ",
+            Rec2100Hlg => "Rec. 2100 HLG",
+            A98Rgb => "A98 RGB",
+            LinearA98Rgb => "linear A98 RGB",
+            ProPhotoRgb => "ProPhoto RGB",
+            LinearProPhotoRgb => "linear ProPhoto RGB",
+            Hsl => "HSL",
+            Hsv => "HSV",
+            Hwb => "HWB",
             Oklab => "Oklab",
             Oklrab => "Oklrab",
             Oklch => "Oklch",
             Oklrch => "Oklrch",
+            Okhsv => "Okhsv",
+            Okhsl => "Okhsl",
+            Lab => "CIELAB",
+            Lch => "CIELCh",
+            Cam16Ucs => "CAM16-UCS",
+            Hct => "HCT",
+            Ictcp => "ICtCp",
+            Jzazbz => "Jzazbz",
+            Jzczhz => "JzCzhz",
             Xyz => "XYZ D65",
+            XyzD50 => "XYZ D50",
         };
 
         f.write_str(s)
@@ -158,8 +422,11 @@ impl std::fmt::Display for ColorSpace {
 /// This function ensures that coordinates are well-formed. In particular, it
 /// replaces not-a-number coordinates with zero. For the Oklab variations, it
 /// also ensures that (revised) lightness is in `0..=1` and chroma is in `0..`.
-/// For semantic consistency, if the hue in Oklch/Oklrch is not-a-number, it
-/// also replaces chroma with zero.
+/// Likewise, for the CIELAB variations and HCT, it ensures that lightness or
+/// tone is in `0..=100` and chroma is in `0..`. For JzCzhz, it ensures that
+/// chroma is in `0..`. For semantic consistency, if the hue in a polar color
+/// space other than HWB is not-a-number, it also replaces chroma or saturation
+/// with zero.
 #[inline]
 pub(crate) fn normalize(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
     let [mut c1, mut c2, mut c3] = *coordinates;
@@ -172,16 +439,25 @@ pub(crate) fn normalize(space: ColorSpace, coordinates: &[Float; 3]) -> [Float;
     }
     if c3.is_nan() {
         c3 = 0.0;
-        if space.is_polar() {
+    }
+
+    // Chroma and saturation both are the second coordinate.
+    if let Some(index) = space.hue_index() {
+        if coordinates[index].is_nan() && space != ColorSpace::Hwb {
             c2 = 0.0;
         }
     }
 
-    if space.is_ok() {
-        c1 = c1.clamp(0.0, 1.0);
+    if space.is_ok() || space.is_lab() {
+        c1 = c1.clamp(0.0, if space.is_ok() { 1.0 } else { 100.0 });
         if space.is_polar() {
             c2 = c2.max(0.0);
         }
+    } else if space == ColorSpace::Hct {
+        c2 = c2.max(0.0);
+        c3 = c3.clamp(0.0, 100.0);
+    } else if space == ColorSpace::Jzczhz {
+        c2 = c2.max(0.0);
     }
 
     [c1, c2, c3]
@@ -1,18 +1,17 @@
 #[cfg(feature = "pyffi")]
 use pyo3::{exceptions::PyValueError, prelude::*};
 
-use crate::{ColorSpace, Float};
+use crate::core::{
+    convert, from_24bit, from_css_name, from_x11_name, in_gamut, mix, normalize, to_24bit, to_gamut,
+};
+use crate::{ColorSpace, Float, HueInterpolation};
 
-/// An erroneous color format.
-///
-/// The enumeration started out with additional information but PyO3 only
-/// supports unit variants without associated state. Thankfully, the attendant
-/// loss of information is rather limited.
-#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int))]
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum ColorFormatError {
-    /// A color format that does not start with a known prefix such as `#` or
-    /// `rgb:`.
+/// The kind of erroneous color format.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorFormatErrorKind {
+    /// A color format that does not start with a known prefix such as `#`,
+    /// `rgb:`, or `color(` and also is not a known color name.
     UnknownFormat,
 
     /// A color format with unexpected characters or an unexpected number of
@@ -32,24 +31,22 @@ pub enum ColorFormatError {
     /// `color(unknown 1 1 1)` uses an unknown color space.
     UnknownColorSpace,
 
-    /// A color format that is missing the coordinate with the given index. For
-    /// example, `rgb:0` is missing the second and third coordinate, whereas
-    /// `rgb:0//0` is missing the second coordinate only.
+    /// A color format that is missing a coordinate. For example, `rgb:0` is
+    /// missing the second and third coordinate, whereas `rgb:0//0` is missing
+    /// the second coordinate only.
     MissingCoordinate,
 
-    /// A color format that has too many digits in the coordinate with the given
-    /// index. For example, `rgb:12345/1/22` has too many digits in the first
-    /// coordinate.
+    /// A color format that has too many digits in a coordinate. For example,
+    /// `rgb:12345/1/22` has too many digits in the first coordinate.
     OversizedCoordinate,
 
-    /// A color format that has a malformed hexadecimal number as coordinate
-    /// with the given index. For example, `#efg` has a malformed third
-    /// coordinate.
+    /// A color format that has a malformed hexadecimal number as coordinate.
+    /// For example, `#efg` has a malformed third coordinate.
     MalformedHex,
 
-    /// A color format that has a malformed floating point number as coordinate
-    /// with the given index. For example, `color(srgb 1.0 0..1 0.0)` has a
-    /// malformed second coordinate.
+    /// A color format that has a malformed floating point number as
+    /// coordinate. For example, `color(srgb 1.0 0..1 0.0)` has a malformed
+    /// second coordinate.
     MalformedFloat,
 
     /// A color format with more than three coordinates. For example,
@@ -57,18 +54,18 @@ pub enum ColorFormatError {
     TooManyCoordinates,
 }
 
-impl std::fmt::Display for ColorFormatError {
-    /// Format a description of this color format error.
+impl std::fmt::Display for ColorFormatErrorKind {
+    /// Format a description of this kind of color format error.
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        use ColorFormatError::*;
+        use ColorFormatErrorKind::*;
 
         match *self {
             UnknownFormat => write!(
                 f,
-                "color format should start with `#`, `color()`, `oklab()`, `oklch()`, or `rgb:`"
+                "color format should start with `#`, an X Windows prefix such as `rgb:`, or a CSS color function such as `rgb()`, or be a color name"
             ),
             UnexpectedCharacters => {
-                write!(f, "color format should contain only valid ASCII characters")
+                write!(f, "color format should have expected characters and length but does not")
             }
             NoOpeningParenthesis => write!(
                 f,
@@ -102,318 +99,2049 @@ impl std::fmt::Display for ColorFormatError {
     }
 }
 
+/// An erroneous color format.
+///
+/// Besides the [kind](ColorFormatError::kind) of error, a color format error
+/// records the [input](ColorFormatError::input), the [byte
+/// span](ColorFormatError::span) of the offending characters within the input,
+/// the index of the [coordinate](ColorFormatError::coordinate), if any, as
+/// well as a description of the [expected](ColorFormatError::expected) and the
+/// [found](ColorFormatError::found) token. The coordinate index is 3 for the
+/// alpha. The error's display shows all that information, with carets
+/// pointing at the offending characters:
+///
+/// ```
+/// # use prettypretty::{Color, ColorFormatErrorKind};
+/// # use std::str::FromStr;
+/// let error = Color::from_str("color(srgb 1 0..1 0)").unwrap_err();
+/// assert_eq!(error.kind(), ColorFormatErrorKind::MalformedFloat);
+/// assert_eq!(error.span(), 13..17);
+/// assert_eq!(error.coordinate(), Some(1));
+/// assert_eq!(error.found(), "0..1");
+/// assert_eq!(
+///     format!("{}", error),
+///     "color format coordinates should be floating point numbers but are not (second coordinate)\n\
+///     \x20   color(srgb 1 0..1 0)\n\
+///     \x20                ^^^^ expected number, percentage, or `none`, found `0..1`"
+/// );
+/// ```
+///
+/// In Python, color format errors are `ValueError` exceptions with `kind`,
+/// `input`, `span`, `coordinate`, `expected`, and `found` attributes.
+#[cfg_attr(feature = "pyffi", pyclass(extends = PyValueError, frozen))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorFormatError {
+    kind: ColorFormatErrorKind,
+    input: String,
+    span: std::ops::Range<usize>,
+    coordinate: Option<usize>,
+    expected: Option<&'static str>,
+}
+
+impl ColorFormatError {
+    /// Access the kind of error.
+    pub fn kind(&self) -> ColorFormatErrorKind {
+        self.kind
+    }
+
+    /// Access the input.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Access the byte span of the offending characters within the input.
+    ///
+    /// If the error is due to missing characters, the span is empty and marks
+    /// the position where the characters are missing.
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// Access the index of the offending coordinate. The alpha has index 3.
+    pub fn coordinate(&self) -> Option<usize> {
+        self.coordinate
+    }
+
+    /// Access a description of the expected token.
+    pub fn expected(&self) -> Option<&'static str> {
+        self.expected
+    }
+
+    /// Access the offending characters.
+    pub fn found(&self) -> &str {
+        &self.input[self.span.clone()]
+    }
+}
+
+#[cfg(feature = "pyffi")]
+#[pymethods]
+impl ColorFormatError {
+    /// Get the kind of error.
+    #[getter(kind)]
+    pub fn py_kind(&self) -> ColorFormatErrorKind {
+        self.kind
+    }
+
+    /// Get the input.
+    #[getter(input)]
+    pub fn py_input(&self) -> &str {
+        &self.input
+    }
+
+    /// Get the byte span of the offending characters as a pair.
+    #[getter(span)]
+    pub fn py_span(&self) -> (usize, usize) {
+        (self.span.start, self.span.end)
+    }
+
+    /// Get the index of the offending coordinate.
+    #[getter(coordinate)]
+    pub fn py_coordinate(&self) -> Option<usize> {
+        self.coordinate
+    }
+
+    /// Get a description of the expected token.
+    #[getter(expected)]
+    pub fn py_expected(&self) -> Option<&'static str> {
+        self.expected
+    }
+
+    /// Get the offending characters.
+    #[getter(found)]
+    pub fn py_found(&self) -> &str {
+        self.found()
+    }
+
+    /// Convert this color format error to its debug representation.
+    pub fn __repr__(&self) -> String {
+        let coordinate = self
+            .coordinate
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "None".to_string());
+        let expected = self
+            .expected
+            .map(|e| format!("{:?}", e))
+            .unwrap_or_else(|| "None".to_string());
+
+        format!(
+            "ColorFormatError(kind=ColorFormatErrorKind.{:?}, input={:?}, span=({}, {}), coordinate={}, expected={})",
+            self.kind, self.input, self.span.start, self.span.end, coordinate, expected
+        )
+    }
+
+    /// Convert this color format error to a human-readable description.
+    pub fn __str__(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl std::fmt::Display for ColorFormatError {
+    /// Format a description of this color format error.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        match self.coordinate {
+            Some(0) => f.write_str(" (first coordinate)")?,
+            Some(1) => f.write_str(" (second coordinate)")?,
+            Some(2) => f.write_str(" (third coordinate)")?,
+            Some(3) => f.write_str(" (alpha)")?,
+            _ => (),
+        }
+
+        // Carets are aligned by character, not byte.
+        let indent = self.input[..self.span.start].chars().count();
+        let width = self.found().chars().count().max(1);
+        write!(
+            f,
+            "\n    {}\n    {}{}",
+            self.input,
+            " ".repeat(indent),
+            "^".repeat(width)
+        )?;
+
+        if let Some(expected) = self.expected {
+            if self.span.is_empty() {
+                write!(f, " expected {}", expected)?;
+            } else {
+                write!(f, " expected {}, found `{}`", expected, self.found())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl std::error::Error for ColorFormatError {}
 
 #[cfg(feature = "pyffi")]
 impl From<ColorFormatError> for PyErr {
     /// Convert a color format error to a Python exception.
     fn from(value: ColorFormatError) -> Self {
-        PyValueError::new_err(value.to_string())
+        Python::with_gil(|py| match Bound::new(py, value) {
+            Ok(error) => PyErr::from_value_bound(error.into_any()),
+            Err(error) => error,
+        })
+    }
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// The description of the expected token for numbers that are not finite.
+const FINITE_NUMBER: &str = "finite number";
+
+/// The maximum nesting depth of relative colors and color mixes as well as of
+/// expressions. It keeps the recursive descent parser from overflowing the
+/// stack.
+const MAX_NESTING: usize = 32;
+
+/// A color format error while parsing. Since the parsing functions operate on
+/// slices of the input, this internal representation tracks the offending
+/// characters as a slice as well. [`parse`] converts it into a
+/// [`ColorFormatError`] with byte span.
+#[derive(Debug, PartialEq)]
+struct Failure<'a> {
+    kind: ColorFormatErrorKind,
+    found: &'a str,
+    coordinate: Option<usize>,
+    expected: Option<&'static str>,
+}
+
+impl<'a> Failure<'a> {
+    /// Create a new failure for the offending characters, which must be a
+    /// slice of the input.
+    fn new(kind: ColorFormatErrorKind, found: &'a str) -> Self {
+        Self {
+            kind,
+            found,
+            coordinate: None,
+            expected: None,
+        }
+    }
+
+    /// Create a new failure for missing characters at the end of the slice,
+    /// which must be a slice of the input.
+    fn missing_after(kind: ColorFormatErrorKind, s: &'a str) -> Self {
+        Self::new(kind, &s[s.len()..])
+    }
+
+    /// Create a new failure for the offending characters, which open one
+    /// level of nesting too many, i.e., exceed [`MAX_NESTING`].
+    fn too_deep(found: &'a str) -> Self {
+        Self::new(ColorFormatErrorKind::UnexpectedCharacters, found)
+            .expecting("less deeply nested color or expression")
+    }
+
+    /// Update the index of the offending coordinate.
+    fn at(mut self, coordinate: usize) -> Self {
+        self.coordinate = Some(coordinate);
+        self
+    }
+
+    /// Update the description of the expected token. Since it is more
+    /// specific, this method does not replace [`FINITE_NUMBER`].
+    fn expecting(mut self, expected: &'static str) -> Self {
+        if self.expected != Some(FINITE_NUMBER) {
+            self.expected = Some(expected);
+        }
+        self
+    }
+
+    /// Convert to a color format error. The input is the original string,
+    /// whereas the base is the string with the offending characters, which
+    /// starts at the given offset within the input.
+    fn into_error(self, input: &str, base: &str, offset: usize) -> ColorFormatError {
+        let start = (self.found.as_ptr() as usize)
+            .saturating_sub(base.as_ptr() as usize)
+            .min(base.len());
+        let end = (start + self.found.len()).min(base.len());
+
+        ColorFormatError {
+            kind: self.kind,
+            input: input.to_string(),
+            span: offset + start..offset + end,
+            coordinate: self.coordinate,
+            expected: self.expected,
+        }
     }
 }
 
 // ====================================================================================================================
 
+/// Parse a floating point number. Unlike Rust's implementation, this function
+/// rejects `inf` and `nan`, which are not valid in either CSS or X Windows
+/// formats, as well as numbers that overflow to infinity.
+fn parse_number(s: &str) -> Result<Float, Failure<'_>> {
+    let failure = || Failure::new(ColorFormatErrorKind::MalformedFloat, s).expecting("number");
+
+    if s.is_empty()
+        || !s
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '+' | '-' | 'e'))
+    {
+        return Err(failure());
+    }
+
+    let number: Float = s.parse().map_err(|_| failure())?;
+    if !number.is_finite() {
+        return Err(failure().expecting(FINITE_NUMBER));
+    }
+    Ok(number)
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
 /// Parse a 24-bit color in hashed hexadecimal format. If successful, this
-/// function returns the three coordinates as unsigned bytes. It transparently
-/// handles single-digit coordinates.
-fn parse_hashed(s: &str) -> Result<[u8; 3], ColorFormatError> {
+/// function returns the three coordinates and the alpha as unsigned bytes. It
+/// transparently handles single-digit coordinates and defaults to an opaque
+/// alpha of 0xff if the format has only three coordinates.
+fn parse_hashed(s: &str) -> Result<[u8; 4], Failure<'_>> {
+    use ColorFormatErrorKind::*;
+
     if !s.starts_with('#') {
-        return Err(ColorFormatError::UnknownFormat);
-    } else if s.len() != 4 && s.len() != 7 {
-        return Err(ColorFormatError::UnexpectedCharacters);
+        return Err(Failure::new(UnknownFormat, s).expecting("`#`"));
+    } else if ![4, 5, 7, 9].contains(&s.len()) {
+        return Err(
+            Failure::new(UnexpectedCharacters, s).expecting("3, 4, 6, or 8 hexadecimal digits")
+        );
     }
 
-    fn parse_coordinate(s: &str, index: usize) -> Result<u8, ColorFormatError> {
-        let factor = s.len() / 3;
-        let t = s
-            .get(1 + factor * index..1 + factor * (index + 1))
-            .ok_or(ColorFormatError::UnexpectedCharacters)?;
-        let n = u8::from_str_radix(t, 16).map_err(|_| ColorFormatError::MalformedHex)?;
+    let count = if s.len() == 5 || s.len() == 9 { 4 } else { 3 };
+    let factor = (s.len() - 1) / count;
+
+    let parse_coordinate = |index: usize| -> Result<u8, Failure> {
+        let t = s.get(1 + factor * index..1 + factor * (index + 1)).ok_or(
+            Failure::new(UnexpectedCharacters, s)
+                .at(index)
+                .expecting("hexadecimal digits"),
+        )?;
+        let n = u8::from_str_radix(t, 16).map_err(|_| {
+            Failure::new(MalformedHex, t)
+                .at(index)
+                .expecting("hexadecimal digits")
+        })?;
 
         Ok(if factor == 1 { 16 * n + n } else { n })
+    };
+
+    let c1 = parse_coordinate(0)?;
+    let c2 = parse_coordinate(1)?;
+    let c3 = parse_coordinate(2)?;
+    let alpha = if count == 4 {
+        parse_coordinate(3)?
+    } else {
+        0xff
+    };
+    Ok([c1, c2, c3, alpha])
+}
+
+/// Parse a 36-bit or 48-bit color in hashed hexadecimal format. X Windows
+/// accepts these formats, i.e., `#RRRGGGBBB` and `#RRRRGGGGBBBB`, in addition
+/// to the 12-bit and 24-bit formats. If successful, this function returns
+/// three pairs with the number of hexadecimal digits and the numeric value for
+/// each coordinate, just like [`parse_x`].
+fn parse_hashed_x(s: &str) -> Result<[(u8, u16); 3], Failure<'_>> {
+    use ColorFormatErrorKind::*;
+
+    if !s.starts_with('#') {
+        return Err(Failure::new(UnknownFormat, s).expecting("`#`"));
+    } else if ![10, 13].contains(&s.len()) {
+        return Err(Failure::new(UnexpectedCharacters, s).expecting("9 or 12 hexadecimal digits"));
     }
 
-    let c1 = parse_coordinate(s, 0)?;
-    let c2 = parse_coordinate(s, 1)?;
-    let c3 = parse_coordinate(s, 2)?;
-    Ok([c1, c2, c3])
+    let factor = (s.len() - 1) / 3;
+    let parse_coordinate = |index: usize| -> Result<(u8, u16), Failure> {
+        let t = s.get(1 + factor * index..1 + factor * (index + 1)).ok_or(
+            Failure::new(UnexpectedCharacters, s)
+                .at(index)
+                .expecting("hexadecimal digits"),
+        )?;
+        let n = u16::from_str_radix(t, 16).map_err(|_| {
+            Failure::new(MalformedHex, t)
+                .at(index)
+                .expecting("hexadecimal digits")
+        })?;
+        Ok((factor as u8, n))
+    };
+
+    Ok([
+        parse_coordinate(0)?,
+        parse_coordinate(1)?,
+        parse_coordinate(2)?,
+    ])
 }
 
 // --------------------------------------------------------------------------------------------------------------------
 
+/// Split the string into three slash-separated coordinates.
+fn split_coordinates<'a>(s: &'a str, expected: &'static str) -> Result<[&'a str; 3], Failure<'a>> {
+    let mut coordinates = [""; 3];
+    let mut iter = s.split('/');
+
+    for (index, coordinate) in coordinates.iter_mut().enumerate() {
+        let t = iter.next().unwrap_or(&s[s.len()..]);
+        if t.is_empty() {
+            return Err(Failure::new(ColorFormatErrorKind::MissingCoordinate, t)
+                .at(index)
+                .expecting(expected));
+        }
+        *coordinate = t;
+    }
+
+    if let Some(t) = iter.next() {
+        // Point at the slash before the first extra coordinate and everything after.
+        let start = t.as_ptr() as usize - s.as_ptr() as usize - 1;
+        return Err(
+            Failure::new(ColorFormatErrorKind::TooManyCoordinates, &s[start..])
+                .expecting("end of color"),
+        );
+    }
+
+    Ok(coordinates)
+}
+
 /// Parse a color in X Windows format. If successful, this function returns
 /// three pairs with the number of hexadecimal digits and the numeric value for
 /// each coordinate.
-fn parse_x(s: &str) -> Result<[(u8, u16); 3], ColorFormatError> {
-    if !s.starts_with("rgb:") {
-        return Err(ColorFormatError::UnknownFormat);
-    }
+fn parse_x(s: &str) -> Result<[(u8, u16); 3], Failure<'_>> {
+    use ColorFormatErrorKind::*;
 
-    fn parse_coordinate(s: Option<&str>, _: usize) -> Result<(u8, u16), ColorFormatError> {
-        let t = s.ok_or(ColorFormatError::MissingCoordinate)?;
-        if t.is_empty() {
-            return Err(ColorFormatError::MissingCoordinate);
-        } else if t.len() > 4 {
-            return Err(ColorFormatError::OversizedCoordinate);
+    let Some(rest) = s.strip_prefix("rgb:") else {
+        return Err(Failure::new(UnknownFormat, s).expecting("`rgb:`"));
+    };
+
+    let mut coordinates = [(0, 0); 3];
+    let texts = split_coordinates(rest, "1-4 hexadecimal digits")?;
+    for (index, (coordinate, t)) in coordinates.iter_mut().zip(texts).enumerate() {
+        if t.len() > 4 {
+            return Err(Failure::new(OversizedCoordinate, t)
+                .at(index)
+                .expecting("1-4 hexadecimal digits"));
         }
 
-        let n = u16::from_str_radix(t, 16).map_err(|_| ColorFormatError::MalformedHex)?;
-        Ok((t.len() as u8, n))
+        let n = u16::from_str_radix(t, 16).map_err(|_| {
+            Failure::new(MalformedHex, t)
+                .at(index)
+                .expecting("1-4 hexadecimal digits")
+        })?;
+        *coordinate = (t.len() as u8, n);
     }
 
-    // SAFETY: unwrap() is safe because we tested for just that prefix above.
-    let mut iter = s.strip_prefix("rgb:").unwrap().split('/');
-    let c1 = parse_coordinate(iter.next(), 0)?;
-    let c2 = parse_coordinate(iter.next(), 1)?;
-    let c3 = parse_coordinate(iter.next(), 2)?;
-    if iter.next().is_some() {
-        return Err(ColorFormatError::TooManyCoordinates);
-    }
+    Ok(coordinates)
+}
+
+/// The prefixes of the X Color Management System's floating point formats
+/// and the color spaces they denote. Since XParseColor treats device RGB
+/// intensities as sRGB, `rgbi:` denotes sRGB. Since CIEuvY and CIExyY are
+/// not supported as color spaces, they denote XYZ. CIELab denotes CIELAB with
+/// D50 as white point.
+const XCMS_PREFIXES: [(&str, ColorSpace); 5] = [
+    ("rgbi:", ColorSpace::Srgb),
+    ("ciexyz:", ColorSpace::Xyz),
+    ("cieuvy:", ColorSpace::Xyz),
+    ("ciexyy:", ColorSpace::Xyz),
+    ("cielab:", ColorSpace::Lab),
+];
+
+/// Parse a color in one of the X Color Management System's floating point
+/// formats, i.e., `rgbi:`, `ciexyz:`, `cieuvy:`, `ciexyy:`, or `cielab:`. If
+/// successful, this function returns the color space and coordinates. It
+/// converts CIEuvY and CIExyY coordinates to XYZ.
+fn parse_xcms(s: &str) -> Result<(ColorSpace, [Float; 3]), Failure<'_>> {
+    let (prefix, space, rest) = XCMS_PREFIXES
+        .iter()
+        .filter_map(|&(prefix, space)| s.strip_prefix(prefix).map(|r| (prefix, space, r)))
+        .next()
+        .ok_or(
+            Failure::new(ColorFormatErrorKind::UnknownFormat, s)
+                .expecting("X Color Management System prefix"),
+        )?;
+
+    let [t1, t2, t3] = split_coordinates(rest, "number")?;
+    let c1 = parse_number(t1).map_err(|f| f.at(0))?;
+    let c2 = parse_number(t2).map_err(|f| f.at(1))?;
+    let c3 = parse_number(t3).map_err(|f| f.at(2))?;
+
+    let coordinates = match prefix {
+        "cieuvy:" => {
+            // u' and v' are CIE 1976 chromaticity coordinates.
+            let [u, v, y] = [c1, c2, c3];
+            if v == 0.0 {
+                [0.0, 0.0, 0.0]
+            } else {
+                [
+                    y * 9.0 * u / (4.0 * v),
+                    y,
+                    y * (12.0 - 3.0 * u - 20.0 * v) / (4.0 * v),
+                ]
+            }
+        }
+        "ciexyy:" => {
+            let [x, y, luminance] = [c1, c2, c3];
+            if y == 0.0 {
+                [0.0, 0.0, 0.0]
+            } else {
+                [x * luminance / y, luminance, (1.0 - x - y) * luminance / y]
+            }
+        }
+        _ => [c1, c2, c3],
+    };
 
-    Ok([c1, c2, c3])
+    Ok((space, coordinates))
 }
 
-const COLOR_SPACES: [(&str, ColorSpace); 10] = [
+// The names of color spaces for the `color()` function. Since CSS Color 4
+// settled on `srgb-linear`, this crate also accepts that name.
+const COLOR_SPACES: [(&str, ColorSpace); 26] = [
     ("srgb", ColorSpace::Srgb),
+    ("srgb-linear", ColorSpace::LinearSrgb),
     ("linear-srgb", ColorSpace::LinearSrgb),
     ("display-p3", ColorSpace::DisplayP3),
     ("--linear-display-p3", ColorSpace::LinearDisplayP3),
     ("rec2020", ColorSpace::Rec2020),
     ("--linear-rec2020", ColorSpace::LinearRec2020),
+    ("rec2100-pq", ColorSpace::Rec2100Pq),
+    ("rec2100-hlg", ColorSpace::Rec2100Hlg),
+    ("a98-rgb", ColorSpace::A98Rgb),
+    ("--linear-a98-rgb", ColorSpace::LinearA98Rgb),
+    ("prophoto-rgb", ColorSpace::ProPhotoRgb),
+    ("--linear-prophoto-rgb", ColorSpace::LinearProPhotoRgb),
+    ("--hsv", ColorSpace::Hsv),
     ("--oklrab", ColorSpace::Oklrab),
     ("--oklrch", ColorSpace::Oklrch),
-    ("xyz", ColorSpace::Xyz),
+    ("--okhsv", ColorSpace::Okhsv),
+    ("--okhsl", ColorSpace::Okhsl),
+    ("--cam16-ucs", ColorSpace::Cam16Ucs),
+    ("--hct", ColorSpace::Hct),
+    ("--ictcp", ColorSpace::Ictcp),
+    ("--jzazbz", ColorSpace::Jzazbz),
+    ("--jzczhz", ColorSpace::Jzczhz),
     ("xyz-d65", ColorSpace::Xyz),
+    ("xyz-d50", ColorSpace::XyzD50),
+    ("xyz", ColorSpace::Xyz),
 ];
 
-/// Parse a subset of valid CSS color formats. This function recognizes only the
-/// `oklab()`, `oklch()`, and `color()` functions. The color space for the
-/// latter must be `srgb`, `linear-srgb`, `display-p3`, `rec2020`, `xyz`, or one
-/// of the non-standard color spaces `--linear-display-p3`, `--linear-rec2020`,
-/// `--oklrab`, and `--oklrch`. Coordinates must not have units including `%`.
-fn parse_css(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
-    use ColorSpace::*;
+/// The CSS functions for colors. Since functions are matched by prefix,
+/// `rgba` and `hsla` must come before `rgb` and `hsl`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CssFunction {
+    Rgb,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Color,
+}
 
-    // Munge CSS function name
-    let (space, rest) = s
-        .strip_prefix("oklab")
-        .map(|r| (Some(Oklab), r))
-        .or_else(|| s.strip_prefix("oklch").map(|r| (Some(Oklch), r)))
-        .or_else(|| s.strip_prefix("color").map(|r| (None, r)))
-        .ok_or(ColorFormatError::UnknownFormat)?;
+impl CssFunction {
+    /// Determine the color space of this function. The `color()` function
+    /// names its color space as first argument and hence has none.
+    const fn space(&self) -> Option<ColorSpace> {
+        match *self {
+            Self::Rgb => Some(ColorSpace::Srgb),
+            Self::Hsl => Some(ColorSpace::Hsl),
+            Self::Hwb => Some(ColorSpace::Hwb),
+            Self::Lab => Some(ColorSpace::Lab),
+            Self::Lch => Some(ColorSpace::Lch),
+            Self::Oklab => Some(ColorSpace::Oklab),
+            Self::Oklch => Some(ColorSpace::Oklch),
+            Self::Color => None,
+        }
+    }
+}
 
-    // Munge parentheses after trimming leading whitespace
-    let rest = rest
-        .trim_start()
-        .strip_prefix('(')
-        .ok_or(ColorFormatError::NoOpeningParenthesis)
-        .and_then(|rest| {
-            rest.strip_suffix(')')
-                .ok_or(ColorFormatError::NoClosingParenthesis)
-        })?;
+const CSS_FUNCTIONS: [(&str, CssFunction); 10] = [
+    ("rgba", CssFunction::Rgb),
+    ("rgb", CssFunction::Rgb),
+    ("hsla", CssFunction::Hsl),
+    ("hsl", CssFunction::Hsl),
+    ("hwb", CssFunction::Hwb),
+    ("oklab", CssFunction::Oklab),
+    ("oklch", CssFunction::Oklch),
+    ("lab", CssFunction::Lab),
+    ("lch", CssFunction::Lch),
+    ("color", CssFunction::Color),
+];
 
-    let (space, body) = if let Some(s) = space {
-        (s, rest) // Pass through
-    } else {
-        // Munge color space
-        let rest = rest.trim_start();
-        COLOR_SPACES
-            .iter()
-            .filter_map(|(p, s)| rest.strip_prefix(p).map(|r| (*s, r)))
-            .next() // Take first (and only) result
-            .ok_or(ColorFormatError::UnknownColorSpace)?
-    };
+/// A CSS token for a coordinate or alpha.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Token {
+    Number(Float),
+    Percentage(Float),
+    Degrees(Float),
+    None,
+}
 
-    #[inline]
-    fn parse_coordinate(s: Option<&str>, _: usize) -> Result<Float, ColorFormatError> {
-        s.ok_or(ColorFormatError::MissingCoordinate)
-            .and_then(|t| t.parse().map_err(|_| ColorFormatError::MalformedFloat))
-    }
+impl Token {
+    /// Parse the token. This function recognizes `none`, numbers,
+    /// percentages, and angles with `deg`, `grad`, `rad`, or `turn` units,
+    /// converting the latter to degrees.
+    fn parse(s: &str) -> Result<Self, Failure<'_>> {
+        let number = |n| parse_number(n).map_err(|f| Failure { found: s, ..f });
 
-    // Munge coordinates. Iterator eats all leading or trailing white space.
-    let mut iter = body.split_whitespace();
-    let c1 = parse_coordinate(iter.next(), 0)?;
-    let c2 = parse_coordinate(iter.next(), 1)?;
-    let c3 = parse_coordinate(iter.next(), 2)?;
-    if iter.next().is_some() {
-        return Err(ColorFormatError::TooManyCoordinates);
+        if s == "none" {
+            Ok(Self::None)
+        } else if let Some(n) = s.strip_suffix('%') {
+            number(n).map(Self::Percentage)
+        } else if let Some(n) = s.strip_suffix("deg") {
+            number(n).map(Self::Degrees)
+        } else if let Some(n) = s.strip_suffix("grad") {
+            number(n).map(|n| Self::Degrees(n * 0.9))
+        } else if let Some(n) = s.strip_suffix("rad") {
+            number(n).map(|n| Self::Degrees(n.to_degrees()))
+        } else if let Some(n) = s.strip_suffix("turn") {
+            number(n).map(|n| Self::Degrees(n * 360.0))
+        } else {
+            number(s).map(Self::Number)
+        }
     }
 
-    Ok((space, [c1, c2, c3]))
+    /// Parse the token or evaluate the `calc()`, `min()`, `max()`, or
+    /// `clamp()` expression. Since absolute colors have no channel keywords,
+    /// expressions are limited to numbers, percentages, and angles for hues.
+    /// Percentages resolve against the given reference, and expressions
+    /// always evaluate to numbers. Failures for tokens describe the expected
+    /// token with the given description.
+    fn parse_or_evaluate<'a>(
+        s: &'a str,
+        percent: Option<Float>,
+        is_hue: bool,
+        expected: &'static str,
+    ) -> Result<Self, Failure<'a>> {
+        if s.contains('(') {
+            Calc::evaluate(s, &[], percent, is_hue).map(Self::Number)
+        } else {
+            Self::parse(s).map_err(|f| f.expecting(expected))
+        }
+    }
 }
 
-// --------------------------------------------------------------------------------------------------------------------
+/// Determine the value of 100% for the coordinate with the given index. This
+/// function returns `None` if the coordinate does not accept percentages.
+fn percent_reference(space: ColorSpace, index: usize) -> Option<Float> {
+    use ColorSpace::*;
 
-/// Parse the string into a color.
-///
-/// This function recognizes hashed hexadecimal, XParseColor, and CSS formats
-/// for colors. In particular, it recognizes the three and six digit hashed
-/// hexadecimal format, the XParseColor format with `rgb:` prefix, and the
-/// modern syntax for the `color()`, `oklab()`, and `oklch()` CSS functions with
-/// space-separated arguments. Before trying to parse either of these formats,
-/// this function trims leading and trailing white space and converts ASCII
-/// letters to lowercase. However, a valid color string may still contain
-/// Unicode white space characters and hence needn't be all ASCII.
-pub(crate) fn parse(s: &str) -> Result<(ColorSpace, [Float; 3]), ColorFormatError> {
-    let lowercase = s.trim().to_ascii_lowercase(); // Keep around for fn scope
-    let s = lowercase.as_str();
-
-    if s.starts_with('#') {
-        let [c1, c2, c3] = parse_hashed(s)?;
-        Ok((
-            ColorSpace::Srgb,
-            [
-                c1 as Float / 255.0,
-                c2 as Float / 255.0,
-                c3 as Float / 255.0,
-            ],
-        ))
-    } else if s.starts_with("rgb:") {
-        fn scale(len_and_value: (u8, u16)) -> Float {
-            len_and_value.1 as Float / (16_i32.pow(len_and_value.0 as u32) - 1) as Float
-        }
+    if space.hue_index() == Some(index) {
+        return None;
+    }
 
-        let [c1, c2, c3] = parse_x(s)?;
-        Ok((ColorSpace::Srgb, [scale(c1), scale(c2), scale(c3)]))
+    match (space, index) {
+        (Hsl | Hsv | Hwb, _) => Some(100.0),
+        (Oklab | Oklrab | Oklch | Oklrch, 0) => Some(1.0),
+        (Oklab | Oklrab | Oklch | Oklrch, _) => Some(0.4),
+        (Okhsv | Okhsl, _) => Some(1.0),
+        (Lab | Lch, 0) => Some(100.0),
+        (Lab, _) => Some(125.0),
+        (Lch, _) => Some(150.0),
+        (Cam16Ucs, 0) | (Hct, 2) => Some(100.0),
+        (Cam16Ucs | Hct | Ictcp | Jzazbz | Jzczhz, _) => None,
+        (Xyz | XyzD50, _) => Some(1.0),
+        _ if space.is_rgb() => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Describe the tokens accepted for the coordinate with the given index.
+fn expected_token(space: ColorSpace, index: usize) -> &'static str {
+    if space.hue_index() == Some(index) {
+        "number, angle, or `none`"
+    } else if percent_reference(space, index).is_some() {
+        "number, percentage, or `none`"
     } else {
-        parse_css(s)
+        "number or `none`"
     }
 }
 
-// --------------------------------------------------------------------------------------------------------------------
+/// Resolve the token with the given text for the coordinate with the given
+/// index.
+fn resolve_coordinate<'a>(
+    space: ColorSpace,
+    index: usize,
+    token: Token,
+    text: &'a str,
+) -> Result<Float, Failure<'a>> {
+    let is_hue = space.hue_index() == Some(index);
+    let failure = || {
+        Failure::new(ColorFormatErrorKind::MalformedFloat, text)
+            .at(index)
+            .expecting(expected_token(space, index))
+    };
 
-fn css_prefix(space: ColorSpace) -> &'static str {
-    use ColorSpace::*;
-    match space {
-        Srgb => "color(srgb ",
-        LinearSrgb => "color(linear-srgb ",
-        DisplayP3 => "color(display-p3 ",
-        LinearDisplayP3 => "color(--linear-display-p3 ",
-        Rec2020 => "color(rec2020 ",
-        LinearRec2020 => "color(--linear-rec2020 ",
-        Oklab => "oklab(",
-        Oklch => "oklch(",
-        Oklrab => "color(--oklrab ",
-        Oklrch => "color(--oklrch ",
-        Xyz => "color(xyz ",
+    match token {
+        Token::Number(n) => Ok(n),
+        Token::Percentage(p) => percent_reference(space, index)
+            .map(|reference| p / 100.0 * reference)
+            .ok_or_else(failure),
+        Token::Degrees(d) if is_hue => Ok(d),
+        Token::Degrees(_) => Err(failure()),
+        Token::None => Ok(Float::NAN),
     }
 }
 
-/// Format the color as a string.
+/// Resolve the token with the given text for the alpha. CSS clamps the alpha
+/// to `0..=1`.
+fn resolve_alpha(token: Token, text: &str) -> Result<Float, Failure<'_>> {
+    match token {
+        Token::Number(n) => Ok(n.clamp(0.0, 1.0)),
+        Token::Percentage(p) => Ok((p / 100.0).clamp(0.0, 1.0)),
+        Token::Degrees(_) => Err(Failure::new(ColorFormatErrorKind::MalformedFloat, text)
+            .at(3)
+            .expecting("number, percentage, or `none`")),
+        Token::None => Ok(Float::NAN),
+    }
+}
+
+/// Parse the name of the color space for the `color()` function at the
+/// beginning of the string. If successful, this function returns the color
+/// space and the rest of the string.
+fn parse_color_space(s: &str) -> Result<(ColorSpace, &str), Failure<'_>> {
+    let s = s.trim_start();
+    let end = s
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(s.len());
+    let space = COLOR_SPACES
+        .iter()
+        .find(|(name, _)| *name == &s[..end])
+        .map(|(_, space)| *space)
+        .ok_or(
+            Failure::new(ColorFormatErrorKind::UnknownColorSpace, &s[..end])
+                .expecting("color space"),
+        )?;
+    Ok((space, &s[end..]))
+}
+
+/// Parse a color in CSS format.
 ///
-/// This function formats the given cooordinates for the given color space as a
-/// CSS color with the `color()`, `oklab()`, or `oklch()` function and
-/// space-separated arguments. It respects the formatter's precision, defaulting
-/// to 5 digits past the decimal. Since degrees for Oklch/Oklrch are up to two
-/// orders of magnitude larger than other coordinates, this method uses a
-/// precision smaller by 2 for degrees. CSS currently does not support the
-/// `--linear-display-p3`, `--linear-rec2020`, `--oklrab`, and `--oklrch` color
-/// spaces, which is why this function formats them, as shown, with two leading
-/// dashes, just like custom properties.
-pub(crate) fn format(
-    space: ColorSpace,
-    coordinates: &[Float; 3],
-    f: &mut std::fmt::Formatter<'_>,
-) -> std::fmt::Result {
-    write!(f, "{}", css_prefix(space))?;
+/// This function recognizes the `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`,
+/// `lab()`, `lch()`, `oklab()`, `oklch()`, and `color()` functions of [CSS
+/// Color 4](https://www.w3.org/TR/css-color-4/). Coordinates may be numbers,
+/// percentages, or `none`, and hues may also be angles with `deg`, `grad`,
+/// `rad`, or `turn` units. Coordinates may also be `calc()`, `min()`,
+/// `max()`, or `clamp()` expressions, which evaluate to numbers. The
+/// coordinates may be followed by a slash and the alpha. The `rgb()`,
+/// `rgba()`, `hsl()`, and `hsla()` functions also accept the legacy syntax
+/// with comma-separated coordinates and alpha, which does not allow for `none`
+/// or expressions.
+///
+/// The color space for `color()` must be `srgb`, `srgb-linear` (or
+/// `linear-srgb`), `display-p3`, `a98-rgb`, `rec2020`, `prophoto-rgb`, `xyz`,
+/// `xyz-d65`, `xyz-d50`, the draft CSS Color HDR color spaces `rec2100-pq` and
+/// `rec2100-hlg`, or one of the non-standard color spaces
+/// `--linear-display-p3`, `--linear-a98-rgb`, `--linear-rec2020`,
+/// `--linear-prophoto-rgb`, `--hsv`, `--oklrab`, `--oklrch`, `--okhsv`,
+/// `--okhsl`, `--cam16-ucs`, `--hct`, `--ictcp`, `--jzazbz`, and `--jzczhz`.
+///
+/// All functions also accept CSS Color 5's relative color syntax, which
+/// starts with `from` and the origin color. See [`parse_relative`]. The depth
+/// is the number of colors enclosing this one.
+fn parse_css<'a>(
+    s: &'a str,
+    resolve: Resolver<'_>,
+    depth: usize,
+) -> Result<(ColorSpace, [Float; 3], Float), Failure<'a>> {
+    use ColorFormatErrorKind::*;
 
-    let mut factor = (10.0 as Float).powi(f.precision().unwrap_or(5) as i32);
-    for (index, coordinate) in coordinates.iter().enumerate() {
-        if space.is_polar() && index == 2 {
-            factor /= 100.0;
-        }
+    // Munge CSS function name
+    let (function, rest) = CSS_FUNCTIONS
+        .iter()
+        .filter_map(|(p, f)| s.strip_prefix(p).map(|r| (*f, r)))
+        .next() // Take first result
+        .ok_or(Failure::new(UnknownFormat, s).expecting("color format or name"))?;
 
-        if coordinate.is_nan() {
-            f.write_str("none")?;
-        } else {
-            // CSS mandates NO trailing zeros whatsoever. But formatting
-            // floats with a precision produces trailing zeros. Rounding
-            // avoids them, for the most part. If fractional part is zero,
-            // we do need an explicit precision---of zero!
-            let c = (coordinate * factor).round() / factor;
-            if c == c.trunc() {
-                write!(f, "{:.0}", c)?;
-            } else {
-                write!(f, "{}", c)?;
+    // Munge parentheses after trimming leading whitespace
+    let rest = rest.trim_start();
+    let rest = rest
+        .strip_prefix('(')
+        .ok_or(Failure::new(NoOpeningParenthesis, &rest[..0]).expecting("`(`"))?;
+    let rest = rest
+        .strip_suffix(')')
+        .ok_or(Failure::missing_after(NoClosingParenthesis, rest).expecting("`)`"))?;
+
+    // Relative color syntax
+    let trimmed = rest.trim_start();
+    if let Some(body) = trimmed.strip_prefix("from") {
+        if body.starts_with(char::is_whitespace) {
+            if MAX_NESTING <= depth {
+                return Err(Failure::too_deep(&trimmed[.."from".len()]));
             }
+            return parse_relative(function, body, resolve, depth + 1);
         }
+    }
 
-        if index < 2 {
-            f.write_str(" ")?;
+    let (space, body) = match function.space() {
+        Some(space) => (space, rest),
+        None => parse_color_space(rest)?,
+    };
+
+    let comma = find_top_level(body, ',');
+    let is_legacy = comma.is_some();
+    if let Some(comma) = comma {
+        if !matches!(function, CssFunction::Rgb | CssFunction::Hsl) {
+            return Err(Failure::new(UnexpectedCharacters, &body[comma..comma + 1])
+                .expecting("white space"));
         }
     }
 
-    f.write_str(")")
-}
+    // Tokenize coordinates and alpha
+    let (tokens, alpha) =
+        if is_legacy {
+            let mut tokens = Vec::with_capacity(4);
+            for (index, part) in split_arguments(body).into_iter().enumerate() {
+                let mut iter = split_components(part).into_iter();
+                let text = iter.next().ok_or(
+                    Failure::new(MissingCoordinate, part)
+                        .at(index)
+                        .expecting("number or percentage"),
+                )?;
+                if let Some(extra) = iter.next() {
+                    return Err(Failure::new(UnexpectedCharacters, extra)
+                        .at(index)
+                        .expecting("`,`"));
+                }
 
-// ====================================================================================================================
+                let token = Token::parse(text).map_err(|f| {
+                    f.at(index)
+                        .expecting(if index == 0 && function == CssFunction::Hsl {
+                            "number or angle"
+                        } else {
+                            "number or percentage"
+                        })
+                })?;
+                if token == Token::None {
+                    return Err(Failure::new(UnexpectedCharacters, text)
+                        .at(index)
+                        .expecting("number or percentage"));
+                }
+                if index == 4 {
+                    return Err(Failure::new(TooManyCoordinates, text).expecting("`)`"));
+                }
+                tokens.push((token, text));
+            }
 
-#[cfg(test)]
-mod test {
-    use super::{parse, parse_css, parse_hashed, parse_x, ColorFormatError};
-    use crate::ColorSpace::*;
-    use crate::Float;
+            if tokens.len() < 3 {
+                return Err(Failure::missing_after(MissingCoordinate, body)
+                    .at(tokens.len())
+                    .expecting("`,`"));
+            }
+            let alpha = tokens.get(3).copied();
+            tokens.truncate(3);
+            (tokens, alpha)
+        } else {
+            let (body, alpha) = match find_top_level(body, '/') {
+                Some(slash) => {
+                    let alpha = &body[slash + 1..];
+                    let mut iter = split_components(alpha).into_iter();
+                    let text = iter.next().ok_or(
+                        Failure::missing_after(MissingCoordinate, alpha)
+                            .at(3)
+                            .expecting("alpha"),
+                    )?;
+                    if let Some(extra) = iter.next() {
+                        return Err(Failure::new(TooManyCoordinates, extra).expecting("`)`"));
+                    }
+                    let token = Token::parse_or_evaluate(
+                        text,
+                        Some(1.0),
+                        false,
+                        "number, percentage, or `none`",
+                    )
+                    .map_err(|f| f.at(3))?;
+                    (&body[..slash], Some((token, text)))
+                }
+                None => (body, None),
+            };
 
-    #[test]
-    fn test_parse_hashed() -> Result<(), ColorFormatError> {
-        assert_eq!(parse_hashed("#123")?, [0x11_u8, 0x22, 0x33]);
-        assert_eq!(parse_hashed("#112233")?, [0x11_u8, 0x22, 0x33]);
-        assert_eq!(parse_hashed("fff"), Err(ColorFormatError::UnknownFormat));
-        assert_eq!(
-            parse_hashed("#ff"),
-            Err(ColorFormatError::UnexpectedCharacters)
-        );
-        assert_eq!(
-            parse_hashed("#💩00"),
-            Err(ColorFormatError::UnexpectedCharacters)
-        );
+            // Splitting eats all leading or trailing white space.
+            let mut tokens = Vec::with_capacity(3);
+            for (index, text) in split_components(body).into_iter().enumerate() {
+                if index == 3 {
+                    return Err(Failure::new(TooManyCoordinates, text)
+                        .expecting(if alpha.is_some() { "`/`" } else { "`/` or `)`" }));
+                }
+                let percent = if function == CssFunction::Rgb {
+                    Some(255.0)
+                } else {
+                    percent_reference(space, index)
+                };
+                let is_hue = space.hue_index() == Some(index);
+                let token =
+                    Token::parse_or_evaluate(text, percent, is_hue, expected_token(space, index))
+                        .map_err(|f| f.at(index))?;
+                tokens.push((token, text));
+            }
+            if tokens.len() < 3 {
+                return Err(Failure::missing_after(MissingCoordinate, body.trim_end())
+                    .at(tokens.len())
+                    .expecting("coordinate"));
+            }
+            (tokens, alpha)
+        };
 
-        let result = parse_hashed("#0g0");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+    // Resolve coordinates
+    let mut coordinates = [0.0; 3];
+    if function == CssFunction::Rgb {
+        // Legacy syntax requires either all numbers or all percentages.
+        if is_legacy {
+            let is_number = matches!(tokens[0].0, Token::Number(_));
+            if let Some((index, (_, text))) = tokens.iter().enumerate().find(|(_, (t, _))| {
+                matches!(t, Token::Number(_)) != is_number
+                    || !matches!(t, Token::Number(_) | Token::Percentage(_))
+            }) {
+                return Err(Failure::new(UnexpectedCharacters, text)
+                    .at(index)
+                    .expecting(if is_number { "number" } else { "percentage" }));
+            }
+        }
 
-        let result = parse_hashed("#00g");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+        for (index, (coordinate, (token, text))) in
+            coordinates.iter_mut().zip(tokens.iter()).enumerate()
+        {
+            *coordinate = match *token {
+                Token::Number(n) => n / 255.0,
+                Token::Percentage(p) => p / 100.0,
+                Token::Degrees(_) => {
+                    return Err(Failure::new(MalformedFloat, text)
+                        .at(index)
+                        .expecting("number, percentage, or `none`"))
+                }
+                Token::None => Float::NAN,
+            };
+        }
+    } else {
+        // Legacy syntax requires percentages for saturation and lightness.
+        if is_legacy {
+            if let Some((index, (_, text))) = tokens
+                .iter()
+                .enumerate()
+                .skip(1)
+                .find(|(_, (t, _))| !matches!(t, Token::Percentage(_)))
+            {
+                return Err(Failure::new(UnexpectedCharacters, text)
+                    .at(index)
+                    .expecting("percentage"));
+            }
+        }
 
-        Ok(())
+        for (index, (coordinate, (token, text))) in
+            coordinates.iter_mut().zip(tokens.iter()).enumerate()
+        {
+            *coordinate = resolve_coordinate(space, index, *token, text)?;
+        }
     }
 
-    #[test]
-    fn test_parse_x() -> Result<(), ColorFormatError> {
-        assert_eq!(
-            parse_x("rgb:a/bb/ccc")?,
-            [(1_u8, 0xa_u16), (2, 0xbb), (3, 0xccc)]
-        );
+    let alpha = alpha
+        .map(|(token, text)| resolve_alpha(token, text))
+        .transpose()?
+        .unwrap_or(1.0);
+    Ok((space, coordinates, alpha))
+}
+
+/// The color spaces for `color-mix()`, which accepts the names of the CSS
+/// functions besides those of `color()`.
+const MIX_SPACES: [(&str, ColorSpace); 6] = [
+    ("hsl", ColorSpace::Hsl),
+    ("hwb", ColorSpace::Hwb),
+    ("lab", ColorSpace::Lab),
+    ("lch", ColorSpace::Lch),
+    ("oklab", ColorSpace::Oklab),
+    ("oklch", ColorSpace::Oklch),
+];
+
+/// The hue interpolation strategies for `color-mix()`.
+const HUE_STRATEGIES: [(&str, HueInterpolation); 4] = [
+    ("shorter", HueInterpolation::Shorter),
+    ("longer", HueInterpolation::Longer),
+    ("increasing", HueInterpolation::Increasing),
+    ("decreasing", HueInterpolation::Decreasing),
+];
+
+/// Split the arguments of a CSS function at commas that are not nested within
+/// parentheses.
+fn split_arguments(s: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                arguments.push(&s[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    arguments.push(&s[start..]);
+    arguments
+}
+
+/// Parse the color and optional percentage of a `color-mix()` argument. The
+/// percentage may come before or after the color.
+#[allow(clippy::type_complexity)]
+fn parse_mix_argument<'a>(
+    s: &'a str,
+    resolve: Resolver<'_>,
+    depth: usize,
+) -> Result<((ColorSpace, [Float; 3], Float), Option<(Float, &'a str)>), Failure<'a>> {
+    use ColorFormatErrorKind::*;
+
+    let s = s.trim();
+    let (color, percentage) = match s.rsplit_once(char::is_whitespace) {
+        Some((color, percentage)) if percentage.ends_with('%') => (color, Some(percentage)),
+        _ => match s.split_once(char::is_whitespace) {
+            Some((percentage, color)) if percentage.ends_with('%') => (color, Some(percentage)),
+            _ => (s, None),
+        },
+    };
+
+    let color = color.trim();
+    if color.is_empty() {
+        return Err(Failure::missing_after(MissingCoordinate, s).expecting("color"));
+    }
+
+    let percentage = match percentage {
+        Some(text) => match Token::parse(text) {
+            Ok(Token::Percentage(p)) if (0.0..=100.0).contains(&p) => Some((p / 100.0, text)),
+            _ => {
+                return Err(
+                    Failure::new(MalformedFloat, text).expecting("percentage between 0% and 100%")
+                )
+            }
+        },
+        None => None,
+    };
+
+    Ok((parse_lowercase(color, resolve, depth)?, percentage))
+}
+
+/// Parse a color mix in CSS format.
+///
+/// This function recognizes the `color-mix()` function of [CSS Color
+/// 5](https://www.w3.org/TR/css-color-5/#color-mix). Its first argument is
+/// `in` followed by the name of the interpolation color space, which may be
+/// any color space accepted by `color()` or the name of a CSS color function,
+/// and, for polar color spaces, an optional hue interpolation strategy
+/// followed by `hue`. The second and third argument each are a color with an
+/// optional percentage. The depth is the number of colors enclosing this one.
+fn parse_color_mix<'a>(
+    s: &'a str,
+    resolve: Resolver<'_>,
+    depth: usize,
+) -> Result<(ColorSpace, [Float; 3], Float), Failure<'a>> {
+    use ColorFormatErrorKind::*;
+
+    // Munge parentheses after trimming leading whitespace
+    let rest = s["color-mix".len()..].trim_start();
+    if rest.starts_with('(') && MAX_NESTING <= depth {
+        return Err(Failure::too_deep(&rest[..1]));
+    }
+    let rest = rest
+        .strip_prefix('(')
+        .ok_or(Failure::new(NoOpeningParenthesis, &rest[..0]).expecting("`(`"))?;
+    let rest = rest
+        .strip_suffix(')')
+        .ok_or(Failure::missing_after(NoClosingParenthesis, rest).expecting("`)`"))?;
+
+    let arguments = split_arguments(rest);
+    if arguments.len() < 3 {
+        return Err(Failure::missing_after(MissingCoordinate, rest).expecting("`,`"));
+    } else if 3 < arguments.len() {
+        return Err(Failure::new(TooManyCoordinates, arguments[3]).expecting("`)`"));
+    }
+
+    // Munge interpolation method
+    let mut words = arguments[0].split_whitespace();
+    match words.next() {
+        Some("in") => (),
+        Some(word) => return Err(Failure::new(UnexpectedCharacters, word).expecting("`in`")),
+        None => {
+            return Err(Failure::missing_after(UnexpectedCharacters, arguments[0]).expecting("`in`"))
+        }
+    }
+
+    let name = words
+        .next()
+        .ok_or(Failure::missing_after(UnknownColorSpace, arguments[0]).expecting("color space"))?;
+    let space = MIX_SPACES
+        .iter()
+        .chain(COLOR_SPACES.iter())
+        .find(|(n, _)| *n == name)
+        .map(|(_, space)| *space)
+        .ok_or(Failure::new(UnknownColorSpace, name).expecting("color space"))?;
+
+    let strategy = match words.next() {
+        None => HueInterpolation::Shorter,
+        Some(word) if space.hue_index().is_none() => {
+            return Err(Failure::new(UnexpectedCharacters, word).expecting("`,`"))
+        }
+        Some(word) => {
+            let strategy = HUE_STRATEGIES
+                .iter()
+                .find(|(n, _)| *n == word)
+                .map(|(_, strategy)| *strategy)
+                .ok_or(Failure::new(UnexpectedCharacters, word).expecting("hue interpolation"))?;
+            match words.next() {
+                Some("hue") => (),
+                Some(word) => {
+                    return Err(Failure::new(UnexpectedCharacters, word).expecting("`hue`"))
+                }
+                None => {
+                    return Err(Failure::missing_after(
+                        UnexpectedCharacters,
+                        arguments[0].trim_end(),
+                    )
+                    .expecting("`hue`"))
+                }
+            }
+            strategy
+        }
+    };
+    if let Some(word) = words.next() {
+        return Err(Failure::new(UnexpectedCharacters, word).expecting("`,`"));
+    }
+
+    // Parse colors and mix them
+    let (color1, percentage1) = parse_mix_argument(arguments[1], resolve, depth + 1)?;
+    let (color2, percentage2) = parse_mix_argument(arguments[2], resolve, depth + 1)?;
+
+    let (coordinates, alpha) = mix(
+        (color1.0, &color1.1, color1.2),
+        percentage1.map(|(p, _)| p),
+        (color2.0, &color2.1, color2.2),
+        percentage2.map(|(p, _)| p),
+        space,
+        strategy,
+    )
+    .ok_or_else(|| {
+        let text = percentage2.or(percentage1).map(|(_, t)| t).unwrap_or(rest);
+        Failure::new(MalformedFloat, text).expecting("percentages that do not add up to 0%")
+    })?;
+
+    Ok((space, coordinates, alpha))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A function resolving references to colors, such as `ansi.blue`, that are
+/// not otherwise recognized by the parser.
+pub(crate) type Resolver<'r> = &'r dyn Fn(&str) -> Option<(ColorSpace, [Float; 3], Float)>;
+
+/// Find the first occurrence of the character that is not nested within
+/// parentheses.
+fn find_top_level(s: &str, target: char) -> Option<usize> {
+    let mut depth = 0_usize;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c == target && depth == 0 => return Some(index),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Find the first component of the string, which must not start with white
+/// space. Unlike [`split_components`], this function stops scanning at the
+/// first white space that is not nested within parentheses.
+fn first_component(s: &str) -> Option<&str> {
+    let mut depth = 0_usize;
+
+    for (index, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => return Some(&s[..index]),
+            _ => (),
+        }
+    }
+
+    (!s.is_empty()).then_some(s)
+}
+
+/// Split the string at white space that is not nested within parentheses.
+fn split_components(s: &str) -> Vec<&str> {
+    let mut components = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = None;
+
+    for (index, c) in s.char_indices() {
+        if c.is_whitespace() && depth == 0 {
+            if let Some(begin) = start.take() {
+                components.push(&s[begin..index]);
+            }
+            continue;
+        }
+
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+        start.get_or_insert(index);
+    }
+
+    if let Some(begin) = start {
+        components.push(&s[begin..]);
+    }
+    components
+}
+
+/// Determine the channel keywords for relative colors in the color space.
+fn channel_keywords(space: ColorSpace) -> [&'static str; 3] {
+    use ColorSpace::*;
+
+    match space {
+        Hsl | Okhsl => ["h", "s", "l"],
+        Hsv | Okhsv => ["h", "s", "v"],
+        Hwb => ["h", "w", "b"],
+        Lab | Oklab | Oklrab => ["l", "a", "b"],
+        Lch | Oklch | Oklrch => ["l", "c", "h"],
+        Cam16Ucs => ["j", "a", "b"],
+        Hct => ["h", "c", "t"],
+        Ictcp => ["i", "ct", "cp"],
+        Jzazbz => ["jz", "az", "bz"],
+        Jzczhz => ["jz", "cz", "hz"],
+        Xyz | XyzD50 => ["x", "y", "z"],
+        _ => ["r", "g", "b"],
+    }
+}
+
+/// An evaluator for coordinates and alpha written as expressions. It
+/// implements a subset of CSS `calc()` with `+`, `-`, `*`, `/`, parentheses,
+/// and the `calc()`, `min()`, `max()`, and `clamp()` functions. Operands are
+/// numbers, percentages, angles for hues, and, for relative colors, channel
+/// keywords. Parentheses, functions, and signs may nest at most
+/// [`MAX_NESTING`] levels deep.
+struct Calc<'a, 'k> {
+    text: &'a str,
+    position: usize,
+    depth: usize,
+    keywords: &'k [(&'static str, Float)],
+    percent: Option<Float>,
+    is_hue: bool,
+}
+
+impl<'a, 'k> Calc<'a, 'k> {
+    /// Evaluate the text. Percentages resolve against the given reference and
+    /// angles are accepted only for hues.
+    fn evaluate(
+        text: &'a str,
+        keywords: &'k [(&'static str, Float)],
+        percent: Option<Float>,
+        is_hue: bool,
+    ) -> Result<Float, Failure<'a>> {
+        let mut calc = Self {
+            text,
+            position: 0,
+            depth: 0,
+            keywords,
+            percent,
+            is_hue,
+        };
+
+        let value = calc.sum()?;
+        calc.skip_whitespace();
+        if calc.position < text.len() {
+            return Err(calc.failure("operator"));
+        }
+        if !value.is_finite() {
+            return Err(
+                Failure::new(ColorFormatErrorKind::MalformedFloat, text).expecting(FINITE_NUMBER)
+            );
+        }
+        Ok(value)
+    }
+
+    /// Peek at the next character.
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    /// Skip any white space.
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consume the expected character after optional white space.
+    fn consume(&mut self, expected: char, description: &'static str) -> Result<(), Failure<'a>> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.failure(description))
+        }
+    }
+
+    /// Enter one more level of nesting, which starts with the character at the
+    /// current position, and consume that character.
+    fn enter(&mut self) -> Result<(), Failure<'a>> {
+        if MAX_NESTING <= self.depth {
+            let rest = &self.text[self.position..];
+            let end = rest.chars().next().map_or(0, char::len_utf8);
+            return Err(Failure::too_deep(&rest[..end]));
+        }
+        self.depth += 1;
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Create a failure for the characters at the current position, up to the
+    /// next white space.
+    fn failure(&self, expected: &'static str) -> Failure<'a> {
+        let rest = &self.text[self.position..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        Failure::new(ColorFormatErrorKind::MalformedFloat, &rest[..end]).expecting(expected)
+    }
+
+    /// Evaluate a sum or difference.
+    fn sum(&mut self) -> Result<Float, Failure<'a>> {
+        let mut value = self.product()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('+') => {
+                    self.position += 1;
+                    value += self.product()?;
+                }
+                Some('-') => {
+                    self.position += 1;
+                    value -= self.product()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// Evaluate a product or quotient.
+    fn product(&mut self) -> Result<Float, Failure<'a>> {
+        let mut value = self.factor()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('*') => {
+                    self.position += 1;
+                    value *= self.factor()?;
+                }
+                Some('/') => {
+                    self.position += 1;
+                    value /= self.factor()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// Evaluate a factor, i.e., a signed operand.
+    fn factor(&mut self) -> Result<Float, Failure<'a>> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('-') => {
+                self.enter()?;
+                let value = -self.factor()?;
+                self.depth -= 1;
+                Ok(value)
+            }
+            Some('+') => {
+                self.enter()?;
+                let value = self.factor()?;
+                self.depth -= 1;
+                Ok(value)
+            }
+            Some('(') => {
+                self.enter()?;
+                let value = self.sum()?;
+                self.consume(')', "`)`")?;
+                self.depth -= 1;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() => self.identifier(),
+            _ => Err(self.failure("number, channel keyword, or function")),
+        }
+    }
+
+    /// Evaluate a number with optional unit.
+    fn number(&mut self) -> Result<Float, Failure<'a>> {
+        let bytes = self.text.as_bytes();
+        let start = self.position;
+        let mut end = start;
+        while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+            end += 1;
+        }
+        if end < bytes.len() && bytes[end] == b'e' {
+            let mut exponent = end + 1;
+            if exponent < bytes.len() && matches!(bytes[exponent], b'+' | b'-') {
+                exponent += 1;
+            }
+            if exponent < bytes.len() && bytes[exponent].is_ascii_digit() {
+                end = exponent;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+            }
+        }
+
+        let number = parse_number(&self.text[start..end])?;
+        let unit_end = self.text[end..]
+            .find(|c: char| !c.is_ascii_alphabetic() && c != '%')
+            .map(|n| end + n)
+            .unwrap_or(self.text.len());
+        let unit = &self.text[end..unit_end];
+        let failure = || {
+            Failure::new(
+                ColorFormatErrorKind::MalformedFloat,
+                &self.text[start..unit_end],
+            )
+        };
+        self.position = unit_end;
+
+        match unit {
+            "" => Ok(number),
+            "%" => self
+                .percent
+                .map(|reference| number / 100.0 * reference)
+                .ok_or_else(|| failure().expecting("number")),
+            "deg" | "grad" | "rad" | "turn" if !self.is_hue => {
+                Err(failure().expecting("number or percentage"))
+            }
+            "deg" => Ok(number),
+            "grad" => Ok(number * 0.9),
+            "rad" => Ok(number.to_degrees()),
+            "turn" => Ok(number * 360.0),
+            _ => Err(failure().expecting("number, percentage, or angle")),
+        }
+    }
+
+    /// Evaluate a channel keyword or function.
+    fn identifier(&mut self) -> Result<Float, Failure<'a>> {
+        let start = self.position;
+        let end = self.text[start..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .map(|n| start + n)
+            .unwrap_or(self.text.len());
+        let name = &self.text[start..end];
+        self.position = end;
+
+        if self.peek() != Some('(') {
+            return self
+                .keywords
+                .iter()
+                .find(|(keyword, _)| *keyword == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| {
+                    Failure::new(ColorFormatErrorKind::MalformedFloat, name)
+                        .expecting("channel keyword")
+                });
+        }
+
+        self.enter()?;
+        let mut arguments = vec![self.sum()?];
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.position += 1;
+                arguments.push(self.sum()?);
+            } else {
+                break;
+            }
+        }
+        self.consume(')', "`,` or `)`")?;
+        self.depth -= 1;
+
+        let failure = || {
+            Failure::new(
+                ColorFormatErrorKind::MalformedFloat,
+                &self.text[start..self.position],
+            )
+        };
+        match (name, arguments.as_slice()) {
+            ("calc", [value]) => Ok(*value),
+            ("min", values) => Ok(values.iter().copied().fold(Float::INFINITY, Float::min)),
+            ("max", values) => Ok(values.iter().copied().fold(Float::NEG_INFINITY, Float::max)),
+            ("clamp", [min, value, max]) => Ok(value.min(*max).max(*min)),
+            ("calc", _) => Err(failure().expecting("one argument")),
+            ("clamp", _) => Err(failure().expecting("three arguments")),
+            _ => Err(Failure::new(ColorFormatErrorKind::MalformedFloat, name)
+                .expecting("`calc`, `min`, `max`, or `clamp`")),
+        }
+    }
+}
+
+/// Parse the body of a CSS color function with relative color syntax, i.e.,
+/// the arguments following `from`.
+///
+/// This function implements [CSS Color
+/// 5](https://www.w3.org/TR/css-color-5/#relative-colors)'s relative color
+/// syntax. The origin color may be any color accepted by the parser, including
+/// references resolved by the resolver. It is converted to the function's
+/// color space, or, for `color()`, the color space following the origin color.
+/// Each coordinate and the optional alpha may then be `none`, a number,
+/// percentage, or angle, a channel keyword, or a `calc()` expression. Channel
+/// keywords are the names of the color space's coordinates, e.g., `l`, `c`,
+/// and `h` for Oklch, and `alpha`. In `rgb()`, `r`, `g`, and `b` range
+/// `0..=255`, just like numbers. Missing components of the origin color
+/// resolve to zero. When the alpha is omitted, it is the origin color's alpha.
+/// The depth is the number of colors enclosing the origin color.
+fn parse_relative<'a>(
+    function: CssFunction,
+    body: &'a str,
+    resolve: Resolver<'_>,
+    depth: usize,
+) -> Result<(ColorSpace, [Float; 3], Float), Failure<'a>> {
+    use ColorFormatErrorKind::*;
+
+    if let Some(comma) = find_top_level(body, ',') {
+        return Err(
+            Failure::new(UnexpectedCharacters, &body[comma..comma + 1]).expecting("white space")
+        );
+    }
+
+    // Munge origin color and color space
+    let body = body.trim_start();
+    let origin = first_component(body)
+        .ok_or(Failure::missing_after(MissingCoordinate, body).expecting("origin color"))?;
+    let rest = &body[origin.len()..];
+    let (origin_space, origin_coordinates, origin_alpha) = parse_lowercase(origin, resolve, depth)?;
+
+    let (space, rest) = match function.space() {
+        Some(space) => (space, rest),
+        None => parse_color_space(rest)?,
+    };
+
+    // Determine channel keywords
+    let scale = if function == CssFunction::Rgb {
+        255.0
+    } else {
+        1.0
+    };
+    let coordinates = convert(origin_space, space, &origin_coordinates);
+    let value = |n: Float| if n.is_nan() { 0.0 } else { n };
+    let [k1, k2, k3] = channel_keywords(space);
+    let keywords = [
+        (k1, value(coordinates[0]) * scale),
+        (k2, value(coordinates[1]) * scale),
+        (k3, value(coordinates[2]) * scale),
+        ("alpha", value(origin_alpha)),
+    ];
+
+    // Tokenize coordinates and alpha
+    let (rest, alpha) = match find_top_level(rest, '/') {
+        Some(slash) => {
+            let alpha = &rest[slash + 1..];
+            let mut components = split_components(alpha).into_iter();
+            let text = components.next().ok_or(
+                Failure::missing_after(MissingCoordinate, alpha)
+                    .at(3)
+                    .expecting("alpha"),
+            )?;
+            if let Some(extra) = components.next() {
+                return Err(Failure::new(TooManyCoordinates, extra).expecting("`)`"));
+            }
+            (&rest[..slash], Some(text))
+        }
+        None => (rest, None),
+    };
+
+    let components = split_components(rest);
+    if components.len() < 3 {
+        return Err(Failure::missing_after(MissingCoordinate, rest.trim_end())
+            .at(components.len())
+            .expecting("coordinate"));
+    } else if 3 < components.len() {
+        return Err(Failure::new(TooManyCoordinates, components[3])
+            .expecting(if alpha.is_some() { "`/`" } else { "`/` or `)`" }));
+    }
+
+    // Evaluate coordinates and alpha
+    let mut result = [0.0; 3];
+    for (index, text) in components.into_iter().enumerate() {
+        result[index] = if text == "none" {
+            Float::NAN
+        } else {
+            let percent = if function == CssFunction::Rgb {
+                Some(255.0)
+            } else {
+                percent_reference(space, index)
+            };
+            let is_hue = space.hue_index() == Some(index);
+            Calc::evaluate(text, &keywords, percent, is_hue).map_err(|f| f.at(index))? / scale
+        };
+    }
+
+    let alpha = match alpha {
+        None => origin_alpha,
+        Some("none") => Float::NAN,
+        Some(text) => Calc::evaluate(text, &keywords, Some(1.0), false)
+            .map_err(|f| f.at(3))?
+            .clamp(0.0, 1.0),
+    };
+
+    Ok((space, result, alpha))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// Parse the lowercase and trimmed string into a color. The resolver is
+/// consulted for strings that are neither hashed, X Windows, nor named colors.
+/// The depth is the number of colors enclosing this one.
+fn parse_lowercase<'a>(
+    s: &'a str,
+    resolve: Resolver<'_>,
+    depth: usize,
+) -> Result<(ColorSpace, [Float; 3], Float), Failure<'a>> {
+    fn scale(len_and_value: (u8, u16)) -> Float {
+        len_and_value.1 as Float / (16_i32.pow(len_and_value.0 as u32) - 1) as Float
+    }
+
+    if s.starts_with('#') && 9 < s.len() {
+        let [c1, c2, c3] = parse_hashed_x(s)?;
+        Ok((ColorSpace::Srgb, [scale(c1), scale(c2), scale(c3)], 1.0))
+    } else if s.starts_with('#') {
+        let [c1, c2, c3, alpha] = parse_hashed(s)?;
+        Ok((
+            ColorSpace::Srgb,
+            [
+                c1 as Float / 255.0,
+                c2 as Float / 255.0,
+                c3 as Float / 255.0,
+            ],
+            alpha as Float / 255.0,
+        ))
+    } else if s.starts_with("rgb:") {
+        let [c1, c2, c3] = parse_x(s)?;
+        Ok((ColorSpace::Srgb, [scale(c1), scale(c2), scale(c3)], 1.0))
+    } else if XCMS_PREFIXES
+        .iter()
+        .any(|(prefix, _)| s.starts_with(prefix))
+    {
+        let (space, coordinates) = parse_xcms(s)?;
+        Ok((space, coordinates, 1.0))
+    } else if s == "transparent" {
+        Ok((ColorSpace::Srgb, [0.0, 0.0, 0.0], 0.0))
+    } else if let Some(rgb) = from_css_name(s).or_else(|| from_x11_name(s)) {
+        Ok((ColorSpace::Srgb, from_24bit(rgb[0], rgb[1], rgb[2]), 1.0))
+    } else if let Some(color) = resolve(s) {
+        Ok(color)
+    } else if s.starts_with("color-mix") {
+        parse_color_mix(s, resolve, depth)
+    } else {
+        parse_css(s, resolve, depth)
+    }
+}
+
+/// Parse the string into a color.
+///
+/// This function recognizes hashed hexadecimal, XParseColor, and CSS formats
+/// for colors. In particular, it recognizes the three, four, six, and eight
+/// digit hashed hexadecimal format as well as XParseColor's nine and twelve
+/// digit hashed hexadecimal format, XParseColor's formats with `rgb:`,
+/// `rgbi:`, `ciexyz:`, `cieuvy:`, `ciexyy:`, and `cielab:` prefixes, and the
+/// CSS Color 4 syntax for the `rgb()`, `rgba()`, `hsl()`, `hsla()`, `hwb()`,
+/// `lab()`, `lch()`, `oklab()`, `oklch()`, and `color()` functions, including
+/// the legacy syntax with comma-separated arguments, percentages, angles,
+/// `none`, `calc()` expressions, and optional alpha. It also recognizes CSS
+/// Color 5's `color-mix()` function and relative color syntax with `from`.
+/// Hence, it accepts colors copied from a browser's developer tools. Finally,
+/// it recognizes the CSS named colors, `transparent`, and, for names that are
+/// not CSS named colors, the X11 color names. Before trying to parse either of
+/// these formats, this function trims leading and trailing white space and
+/// converts ASCII letters to lowercase.
+/// However, a valid color string may still contain Unicode white space
+/// characters and hence needn't be all ASCII.
+///
+/// Relative colors and color mixes may nest at most 32 levels deep, and so may
+/// parentheses, functions, and signs within a `calc()` expression.
+///
+/// If successful, this function returns the color space, the coordinates, and
+/// the alpha, which defaults to 1.0. Otherwise, it returns a color format
+/// error with the byte span of the offending characters within the original,
+/// untrimmed string.
+pub(crate) fn parse(s: &str) -> Result<(ColorSpace, [Float; 3], Float), ColorFormatError> {
+    parse_with(s, &|_| None)
+}
+
+/// Parse the string into a color while resolving references.
+///
+/// This function works just like [`parse`], except that it also consults the
+/// resolver for strings that are neither hashed, X Windows, nor named colors,
+/// including the origin colors of relative colors and the colors of
+/// `color-mix()`. The resolver receives the lowercase reference, e.g.,
+/// `ansi.blue`, and returns the color space, coordinates, and alpha of the
+/// referenced color, if any.
+pub(crate) fn parse_with(
+    s: &str,
+    resolve: Resolver<'_>,
+) -> Result<(ColorSpace, [Float; 3], Float), ColorFormatError> {
+    let trimmed = s.trim();
+    let offset = trimmed.as_ptr() as usize - s.as_ptr() as usize;
+    let lowercase = trimmed.to_ascii_lowercase(); // Keep around for fn scope
+
+    parse_lowercase(&lowercase, resolve, 0).map_err(|f| f.into_error(s, &lowercase, offset))
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+fn css_prefix(space: ColorSpace) -> &'static str {
+    use ColorSpace::*;
+    match space {
+        Srgb => "color(srgb ",
+        LinearSrgb => "color(linear-srgb ",
+        DisplayP3 => "color(display-p3 ",
+        LinearDisplayP3 => "color(--linear-display-p3 ",
+        Rec2020 => "color(rec2020 ",
+        LinearRec2020 => "color(--linear-rec2020 ",
+        Rec2100Pq => "color(rec2100-pq ",
+        Rec2100Hlg => "color(rec2100-hlg ",
+        A98Rgb => "color(a98-rgb ",
+        LinearA98Rgb => "color(--linear-a98-rgb ",
+        ProPhotoRgb => "color(prophoto-rgb ",
+        LinearProPhotoRgb => "color(--linear-prophoto-rgb ",
+        Hsl => "hsl(",
+        Hsv => "color(--hsv ",
+        Hwb => "hwb(",
+        Oklab => "oklab(",
+        Oklch => "oklch(",
+        Oklrab => "color(--oklrab ",
+        Oklrch => "color(--oklrch ",
+        Okhsv => "color(--okhsv ",
+        Okhsl => "color(--okhsl ",
+        Lab => "lab(",
+        Lch => "lch(",
+        Cam16Ucs => "color(--cam16-ucs ",
+        Hct => "color(--hct ",
+        Ictcp => "color(--ictcp ",
+        Jzazbz => "color(--jzazbz ",
+        Jzczhz => "color(--jzczhz ",
+        Xyz => "color(xyz ",
+        XyzD50 => "color(xyz-d50 ",
+    }
+}
+
+/// Format the color as a string.
+///
+/// This function formats the given cooordinates for the given color space as a
+/// CSS color with the `color()`, `hsl()`, `hwb()`, `oklab()`, `oklch()`,
+/// `lab()`, or `lch()` function and space-separated arguments. It respects the
+/// formatter's precision, defaulting to 5 digits past the decimal. Since
+/// degrees for polar color spaces are up to two orders of magnitude larger than
+/// other coordinates, this method uses a precision smaller by 2 for degrees.
+/// CSS currently does not support the `--linear-display-p3`,
+/// `--linear-a98-rgb`, `--linear-rec2020`, `--linear-prophoto-rgb`, `--hsv`,
+/// `--oklrab`, `--oklrch`, `--okhsv`, `--okhsl`, `--cam16-ucs`, `--hct`,
+/// `--ictcp`, `--jzazbz`, and `--jzczhz` color spaces, which is why this
+/// function formats them, as shown, with two leading dashes, just like custom
+/// properties. If the alpha is not 1.0, this function also formats the alpha
+/// after a slash.
+pub(crate) fn format(
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+    alpha: Float,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    write!(f, "{}", css_prefix(space))?;
+
+    let precision = (10.0 as Float).powi(f.precision().unwrap_or(5) as i32);
+    for (index, coordinate) in coordinates.iter().enumerate() {
+        let factor = if space.hue_index() == Some(index) {
+            precision / 100.0
+        } else {
+            precision
+        };
+
+        write_coordinate(*coordinate, factor, f)?;
+        if index < 2 {
+            f.write_str(" ")?;
+        }
+    }
+
+    if alpha != 1.0 {
+        f.write_str(" / ")?;
+        write_coordinate(alpha, precision, f)?;
+    }
+
+    f.write_str(")")
+}
+
+// --------------------------------------------------------------------------------------------------------------------
+
+/// A format for serializing colors.
+///
+/// [`Color::to_format`](crate::Color::to_format) serializes colors in any of
+/// these formats. Except for [`ColorFormat::Css`], they all serialize sRGB
+/// colors and hence gamut-map colors that are out of sRGB's gamut. Since
+/// `hsl()` and `hwb()` cannot express out-of-gamut colors either, that
+/// includes HSL and HWB colors serialized as [`ColorFormat::Css`].
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorFormat {
+    /// The hashed hexadecimal format, e.g., `#ff9300`. Translucent colors
+    /// also include the alpha, e.g., `#ff930080`.
+    Hex,
+    /// The legacy `rgb()` format with integer coordinates between 0 and 255,
+    /// e.g., `rgb(255, 147, 0)`. Translucent colors use `rgba()` instead,
+    /// e.g., `rgba(255, 147, 0, 0.5)`.
+    Rgb,
+    /// The legacy `hsl()` format, e.g., `hsl(34.588, 100%, 50%)`. Translucent
+    /// colors use `hsla()` instead.
+    Hsl,
+    /// The X Windows format with four hexadecimal digits per coordinate, e.g.,
+    /// `rgb:ffff/9393/0000`. Since the format cannot express alpha, it is
+    /// dropped.
+    X,
+    /// The CSS format for the color's color space, e.g., `oklch(0.5 0.1
+    /// 120)`, which is also used by `Display`.
+    Css,
+}
+
+/// Convert the coordinates to gamut-mapped sRGB.
+fn to_srgb_gamut(space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
+    let srgb = convert(space, ColorSpace::Srgb, &normalize(space, coordinates));
+    to_gamut(ColorSpace::Srgb, &srgb)
+}
+
+/// Write the coordinate, rounded by the factor.
+fn write_coordinate(
+    coordinate: Float,
+    factor: Float,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    if coordinate.is_nan() {
+        f.write_str("none")
+    } else {
+        // CSS mandates NO trailing zeros whatsoever. But formatting
+        // floats with a precision produces trailing zeros. Rounding
+        // avoids them, for the most part. If fractional part is zero,
+        // we do need an explicit precision---of zero!
+        let c = (coordinate * factor).round() / factor;
+        if c == c.trunc() {
+            write!(f, "{:.0}", c)
+        } else {
+            write!(f, "{}", c)
+        }
+    }
+}
+
+/// Format the color in the given format.
+///
+/// For [`ColorFormat::Hsl`] and [`ColorFormat::Css`], this function respects
+/// the formatter's precision just like [`format`]. It ignores the precision
+/// for all other formats.
+pub(crate) fn format_as(
+    color_format: ColorFormat,
+    space: ColorSpace,
+    coordinates: &[Float; 3],
+    alpha: Float,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    // The legacy and hexadecimal formats do not support none.
+    let opacity = if alpha.is_nan() {
+        0.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    };
+    let precision = (10.0 as Float).powi(f.precision().unwrap_or(5) as i32);
+
+    match color_format {
+        ColorFormat::Hex => {
+            let [r, g, b] = to_24bit(ColorSpace::Srgb, &to_srgb_gamut(space, coordinates));
+            write!(f, "#{:02x}{:02x}{:02x}", r, g, b)?;
+            if opacity < 1.0 {
+                write!(f, "{:02x}", (opacity * 255.0).round() as u8)?;
+            }
+            Ok(())
+        }
+        ColorFormat::Rgb => {
+            let [r, g, b] = to_24bit(ColorSpace::Srgb, &to_srgb_gamut(space, coordinates));
+            if opacity < 1.0 {
+                write!(f, "rgba({}, {}, {}, ", r, g, b)?;
+                write_coordinate(opacity, precision, f)?;
+                f.write_str(")")
+            } else {
+                write!(f, "rgb({}, {}, {})", r, g, b)
+            }
+        }
+        ColorFormat::Hsl => {
+            let srgb = to_srgb_gamut(space, coordinates);
+            let [h, s, l] = convert(ColorSpace::Srgb, ColorSpace::Hsl, &srgb);
+            f.write_str(if opacity < 1.0 { "hsla(" } else { "hsl(" })?;
+            // The legacy syntax does not support none.
+            write_coordinate(if h.is_nan() { 0.0 } else { h }, precision / 100.0, f)?;
+            f.write_str(", ")?;
+            write_coordinate(s, precision / 100.0, f)?;
+            f.write_str("%, ")?;
+            write_coordinate(l, precision / 100.0, f)?;
+            f.write_str("%")?;
+            if opacity < 1.0 {
+                f.write_str(", ")?;
+                write_coordinate(opacity, precision, f)?;
+            }
+            f.write_str(")")
+        }
+        ColorFormat::X => {
+            let [r, g, b] = to_srgb_gamut(space, coordinates)
+                .map(|c| (c.clamp(0.0, 1.0) * 65_535.0).round() as u16);
+            write!(f, "rgb:{:04x}/{:04x}/{:04x}", r, g, b)
+        }
+        ColorFormat::Css => {
+            if matches!(space, ColorSpace::Hsl | ColorSpace::Hwb) && !in_gamut(space, coordinates) {
+                let srgb = to_srgb_gamut(space, coordinates);
+                format(space, &convert(ColorSpace::Srgb, space, &srgb), alpha, f)
+            } else {
+                format(space, coordinates, alpha, f)
+            }
+        }
+    }
+}
+
+// ====================================================================================================================
+
+#[cfg(test)]
+mod test {
+    use super::{
+        format_as, parse, parse_hashed, parse_hashed_x, parse_with, parse_x, parse_xcms,
+        ColorFormat, ColorFormatError, ColorFormatErrorKind, Failure, MAX_NESTING,
+    };
+    use ColorFormatErrorKind::*;
+
+    fn parse_css(s: &str) -> Result<(crate::ColorSpace, [Float; 3], Float), Failure<'_>> {
+        super::parse_css(s, &|_| None, 0)
+    }
+
+    impl From<Failure<'_>> for ColorFormatErrorKind {
+        fn from(value: Failure<'_>) -> Self {
+            value.kind
+        }
+    }
+
+    impl From<ColorFormatError> for ColorFormatErrorKind {
+        fn from(value: ColorFormatError) -> Self {
+            value.kind
+        }
+    }
+
+    fn kind<T>(result: Result<T, Failure<'_>>) -> Result<T, ColorFormatErrorKind> {
+        result.map_err(|f| f.kind)
+    }
+    use crate::ColorSpace::*;
+    use crate::Float;
+
+    #[test]
+    fn test_parse_hashed() -> Result<(), ColorFormatErrorKind> {
+        assert_eq!(parse_hashed("#123")?, [0x11_u8, 0x22, 0x33, 0xff]);
+        assert_eq!(parse_hashed("#112233")?, [0x11_u8, 0x22, 0x33, 0xff]);
+        assert_eq!(parse_hashed("#1234")?, [0x11_u8, 0x22, 0x33, 0x44]);
+        assert_eq!(parse_hashed("#11223344")?, [0x11_u8, 0x22, 0x33, 0x44]);
+        assert_eq!(kind(parse_hashed("fff")), Err(UnknownFormat));
+        assert_eq!(kind(parse_hashed("#ff")), Err(UnexpectedCharacters));
+        assert_eq!(kind(parse_hashed("#💩00")), Err(UnexpectedCharacters));
+
+        let result = parse_hashed("#0g0");
+        assert!(matches!(
+            result,
+            Err(Failure {
+                kind: MalformedHex,
+                ..
+            })
+        ));
+
+        let result = parse_hashed("#00g");
+        assert!(matches!(
+            result,
+            Err(Failure {
+                kind: MalformedHex,
+                ..
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_x() -> Result<(), ColorFormatErrorKind> {
+        assert_eq!(
+            parse_x("rgb:a/bb/ccc")?,
+            [(1_u8, 0xa_u16), (2, 0xbb), (3, 0xccc)]
+        );
         assert_eq!(
             parse_x("rgb:0123/4567/89ab")?,
             [(4_u8, 0x123_u16), (4, 0x4567), (4, 0x89ab)]
         );
+        assert_eq!(kind(parse_x("rgbi:0.1/0.1/0.1")), Err(UnknownFormat));
+        assert_eq!(kind(parse_x("rgb:0")), Err(MissingCoordinate));
+        assert_eq!(kind(parse_x("rgb:0//2")), Err(MissingCoordinate));
+        assert_eq!(kind(parse_x("rgb:1/12345/1")), Err(OversizedCoordinate));
+        assert_eq!(kind(parse_x("rgb:1/2/3/4")), Err(TooManyCoordinates));
+
+        let result = parse_x("rgb:f/g/f");
+        assert!(matches!(
+            result,
+            Err(Failure {
+                kind: MalformedHex,
+                ..
+            })
+        ));
+
         assert_eq!(
-            parse_x("rgbi:0.1/0.1/0.1"),
-            Err(ColorFormatError::UnknownFormat)
+            parse_hashed_x("#123456789")?,
+            [(3_u8, 0x123_u16), (3, 0x456), (3, 0x789)]
         );
-        assert_eq!(parse_x("rgb:0"), Err(ColorFormatError::MissingCoordinate));
         assert_eq!(
-            parse_x("rgb:0//2"),
-            Err(ColorFormatError::MissingCoordinate)
+            parse_hashed_x("#0123456789ab")?,
+            [(4_u8, 0x123_u16), (4, 0x4567), (4, 0x89ab)]
         );
         assert_eq!(
-            parse_x("rgb:1/12345/1"),
-            Err(ColorFormatError::OversizedCoordinate)
+            kind(parse_hashed_x("#0123456789a")),
+            Err(UnexpectedCharacters)
         );
+        assert_eq!(parse("#fff000fff")?, (Srgb, [1.0, 0.0, 1.0], 1.0));
         assert_eq!(
-            parse_x("rgb:1/2/3/4"),
-            Err(ColorFormatError::TooManyCoordinates)
+            parse("#ffff00008000")?,
+            (Srgb, [1.0, 0.0, 0.5000076295109483], 1.0)
         );
 
-        let result = parse_x("rgb:f/g/f");
-        assert!(matches!(result, Err(ColorFormatError::MalformedHex)));
+        assert_eq!(parse_xcms("rgbi:0.1/0.2/1")?, (Srgb, [0.1, 0.2, 1.0]));
+        assert_eq!(
+            parse("CIEXYZ:0.25/0.5/0.75")?,
+            (Xyz, [0.25, 0.5, 0.75], 1.0)
+        );
+        assert_eq!(
+            parse("CIELab:50/-20/30.5")?,
+            (Lab, [50.0, -20.0, 30.5], 1.0)
+        );
+        assert_eq!(parse_xcms("ciexyy:0.25/0.5/0.5")?, (Xyz, [0.25, 0.5, 0.25]));
+        assert_eq!(
+            parse_xcms("cieuvy:0.2/0.45/1")?,
+            (Xyz, [1.0, 1.0, 1.3333333333333335])
+        );
+        assert_eq!(parse_xcms("ciexyy:0.25/0/0.5")?, (Xyz, [0.0, 0.0, 0.0]));
+        assert_eq!(kind(parse_xcms("ciexyz:1/1")), Err(MissingCoordinate));
+        assert_eq!(kind(parse_xcms("cielab:1/1/1/1")), Err(TooManyCoordinates));
+        assert_eq!(kind(parse_xcms("rgbi:1/nan/1")), Err(MalformedFloat));
 
+        assert_eq!(parse("RebeccaPurple")?, (Srgb, [0.4, 0.2, 0.6], 1.0));
+        assert_eq!(parse("transparent")?, (Srgb, [0.0, 0.0, 0.0], 0.0));
+        assert_eq!(
+            parse(" Dark Slate Gray ")?,
+            (
+                Srgb,
+                [0.1843137254901961, 0.30980392156862746, 0.30980392156862746],
+                1.0
+            )
+        );
         assert_eq!(
             parse("   RGB:00/55/aa   ")?,
             (
                 Srgb,
-                [0.0 as Float, 0.33333333333333333, 0.6666666666666666]
+                [0.0 as Float, 0.3333333333333333, 0.6666666666666666],
+                1.0
             )
         );
 
@@ -422,55 +2150,593 @@ mod test {
 
     #[test]
     fn test_parse_css() {
-        assert_eq!(parse_css("oklab(0 0 0)"), Ok((Oklab, [0.0, 0.0, 0.0])));
+        assert_eq!(parse_css("oklab(0 0 0)"), Ok((Oklab, [0.0, 0.0, 0.0], 1.0)));
         assert_eq!(
             parse_css("color(xyz   1  1  1)"),
-            Ok((Xyz, [1.0, 1.0, 1.0]))
+            Ok((Xyz, [1.0, 1.0, 1.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(xyz-d65 1 1 1)"),
+            Ok((Xyz, [1.0, 1.0, 1.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(xyz-d50 0.5 0.5 0.5)"),
+            Ok((XyzD50, [0.5, 0.5, 0.5], 1.0))
+        );
+        assert_eq!(
+            parse_css("lab(50 -20 30)"),
+            Ok((Lab, [50.0, -20.0, 30.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("lch(50 30 120)"),
+            Ok((Lch, [50.0, 30.0, 120.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(a98-rgb 0.5 0.25 1)"),
+            Ok((A98Rgb, [0.5, 0.25, 1.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(prophoto-rgb 0.5 0.25 1)"),
+            Ok((ProPhotoRgb, [0.5, 0.25, 1.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(--linear-prophoto-rgb 0.5 0.25 1)"),
+            Ok((LinearProPhotoRgb, [0.5, 0.25, 1.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("hsl(217 81 55)"),
+            Ok((Hsl, [217.0, 81.0, 55.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("hwb(217 19 8)"),
+            Ok((Hwb, [217.0, 19.0, 8.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(--hsv 217 79 92)"),
+            Ok((Hsv, [217.0, 79.0, 92.0], 1.0))
         );
         assert_eq!(
             parse_css("color(  --oklrch   1  1  1)"),
-            Ok((Oklrch, [1.0, 1.0, 1.0]))
+            Ok((Oklrch, [1.0, 1.0, 1.0], 1.0))
         );
         assert_eq!(
             parse_css("color  (  --linear-display-p3   1  1.123  0.3333   )"),
-            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333]))
+            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333], 1.0))
+        );
+        assert_eq!(
+            parse_css("oklch(0.5 0.1 120 / 0.25)"),
+            Ok((Oklch, [0.5, 0.1, 120.0], 0.25))
+        );
+        assert_eq!(
+            parse_css("color(srgb 1 0 0/0.5)"),
+            Ok((Srgb, [1.0, 0.0, 0.0], 0.5))
+        );
+        assert!(matches!(
+            parse_css("lab(50 -20 30 / none)"),
+            Ok((Lab, _, alpha)) if alpha.is_nan()
+        ));
+        assert_eq!(
+            kind(parse_css("color(srgb 1 1 1 / 1 1)")),
+            Err(TooManyCoordinates)
+        );
+        assert_eq!(
+            parse_css("rgb(255 127.5 0)"),
+            Ok((Srgb, [1.0, 0.5, 0.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("rgb(255, 0, 51)"),
+            Ok((Srgb, [1.0, 0.0, 0.2], 1.0))
         );
         assert_eq!(
-            parse_css("whatever(1 1 1)"),
-            Err(ColorFormatError::UnknownFormat)
+            parse_css("rgba(100%, 50%, 0%, 0.5)"),
+            Ok((Srgb, [1.0, 0.5, 0.0], 0.5))
         );
         assert_eq!(
-            parse_css("colorsrgb 1 1 1)"),
-            Err(ColorFormatError::NoOpeningParenthesis)
+            parse_css("rgb(100% 50% 0% / 25%)"),
+            Ok((Srgb, [1.0, 0.5, 0.0], 0.25))
         );
         assert_eq!(
-            parse_css("color(srgb 1 1 1"),
-            Err(ColorFormatError::NoClosingParenthesis)
+            parse_css("hsla(0.5turn, 50%, 25%, 2)"),
+            Ok((Hsl, [180.0, 50.0, 25.0], 1.0))
         );
         assert_eq!(
-            parse_css("color(nemo 1 1 1)"),
-            Err(ColorFormatError::UnknownColorSpace)
+            parse_css("hsl(200grad 50% 25%)"),
+            Ok((Hsl, [180.0, 50.0, 25.0], 1.0))
         );
         assert!(matches!(
-            parse_css("color(srgb abc 1 1)"),
-            Err(ColorFormatError::MalformedFloat)
+            parse_css("hwb(3.141592653589793rad 0% 0%)"),
+            Ok((Hwb, [h, 0.0, 0.0], 1.0)) if (h - 180.0).abs() < 1e-12
         ));
         assert_eq!(
-            parse_css("color(srgb 1)"),
-            Err(ColorFormatError::MissingCoordinate)
+            parse_css("lab(50% 100% -100%)"),
+            Ok((Lab, [50.0, 125.0, -125.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("oklch(50% 50% 90deg)"),
+            Ok((Oklch, [0.5, 0.2, 90.0], 1.0))
+        );
+        assert_eq!(
+            parse_css("color(srgb-linear 100% 0 50%)"),
+            Ok((LinearSrgb, [1.0, 0.0, 0.5], 1.0))
+        );
+        assert!(matches!(
+            parse_css("oklch(0.5 none 90)"),
+            Ok((Oklch, [_, c, _], 1.0)) if c.is_nan()
+        ));
+        assert_eq!(
+            kind(parse_css("rgb(255, 50%, 0)")),
+            Err(UnexpectedCharacters)
+        );
+        assert_eq!(
+            kind(parse_css("hsl(120, 50, 50)")),
+            Err(UnexpectedCharacters)
+        );
+        assert_eq!(
+            kind(parse_css("rgb(none, 0, 0)")),
+            Err(UnexpectedCharacters)
+        );
+        assert_eq!(
+            kind(parse_css("oklab(0.5, 0, 0)")),
+            Err(UnexpectedCharacters)
+        );
+        assert_eq!(kind(parse_css("oklch(0.5 0.1 90%)")), Err(MalformedFloat));
+        assert_eq!(kind(parse_css("lab(50deg 0 0)")), Err(MalformedFloat));
+        assert_eq!(kind(parse_css("rgb(inf 0 0)")), Err(MalformedFloat));
+        let error = parse("rgb(1e400 0 0)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "1e400");
+        assert_eq!(error.coordinate(), Some(0));
+        assert_eq!(error.expected(), Some("finite number"));
+        let error = parse("rgb(calc(1e400 - 1e400) 0 0)").unwrap_err();
+        assert_eq!(error.expected(), Some("finite number"));
+        assert_eq!(
+            parse("CIEXYZ:1e400/0/0").unwrap_err().kind(),
+            MalformedFloat
+        );
+        assert_eq!(kind(parse_css("whatever(1 1 1)")), Err(UnknownFormat));
+        assert_eq!(
+            kind(parse_css("colorsrgb 1 1 1)")),
+            Err(NoOpeningParenthesis)
+        );
+        assert_eq!(
+            kind(parse_css("color(srgb 1 1 1")),
+            Err(NoClosingParenthesis)
         );
+        assert_eq!(kind(parse_css("color(nemo 1 1 1)")), Err(UnknownColorSpace));
+        assert_eq!(kind(parse_css("color(srgb abc 1 1)")), Err(MalformedFloat));
+        assert_eq!(kind(parse_css("color(srgb 1)")), Err(MissingCoordinate));
         assert_eq!(
-            parse_css("color(srgb 1 1 1 1)"),
-            Err(ColorFormatError::TooManyCoordinates)
+            kind(parse_css("color(srgb 1 1 1 1)")),
+            Err(TooManyCoordinates)
         );
 
+        // Absolute colors accept calc() et al. without channel keywords.
+        assert_eq!(
+            parse("rgb(calc(10 / 2) 0 0)"),
+            Ok((Srgb, [5.0 / 255.0, 0.0, 0.0], 1.0))
+        );
+        assert_eq!(
+            parse("oklch(calc(50% + 0.1) min(0.1, 0.2) calc(0.5turn / 2) / clamp(0, 1.5, 1))"),
+            Ok((Oklch, [0.6, 0.1, 90.0], 1.0))
+        );
+        let error = parse("rgb(calc(10 / 2) 0 0 0)").unwrap_err();
+        assert_eq!(error.kind(), TooManyCoordinates);
+        assert_eq!(error.found(), "0");
+        let error = parse("rgb(calc(r / 2) 0 0)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "r");
+        assert_eq!(error.expected(), Some("channel keyword"));
+        let error = parse("rgb(calc(10 / 0) 0 0)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.expected(), Some("finite number"));
+        let error = parse("rgb(calc(10 / 2), 0, 0)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "calc(10 / 2)");
+        assert_eq!(error.coordinate(), Some(0));
+
         assert_eq!(
             parse("   COLOR(  --linear-display-p3   1  1.123  0.3333   )    "),
-            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333]))
+            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333], 1.0))
         );
         assert_eq!(
             parse("  color( --Linear-Display-P3  1  1.123  0.3333 )  "),
-            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333]))
+            Ok((LinearDisplayP3, [1.0, 1.123, 0.3333], 1.0))
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let error = parse("  Color(srgb 1 0..1 0)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.input(), "  Color(srgb 1 0..1 0)");
+        assert_eq!(error.span(), 15..19);
+        assert_eq!(error.coordinate(), Some(1));
+        assert_eq!(error.expected(), Some("number, percentage, or `none`"));
+        assert_eq!(error.found(), "0..1");
+
+        let error = parse("oklch(0.5 0.1 90%)").unwrap_err();
+        assert_eq!(error.found(), "90%");
+        assert_eq!(error.coordinate(), Some(2));
+        assert_eq!(error.expected(), Some("number, angle, or `none`"));
+
+        let error = parse("rgba(255, 0, 0, 1turn)").unwrap_err();
+        assert_eq!(error.found(), "1turn");
+        assert_eq!(error.coordinate(), Some(3));
+
+        let error = parse("rgb(255, 50%, 0)").unwrap_err();
+        assert_eq!(error.kind(), UnexpectedCharacters);
+        assert_eq!(error.found(), "50%");
+        assert_eq!(error.coordinate(), Some(1));
+        assert_eq!(error.expected(), Some("number"));
+
+        let error = parse("oklab(1 2 3").unwrap_err();
+        assert_eq!(error.kind(), NoClosingParenthesis);
+        assert_eq!(error.span(), 11..11);
+        assert_eq!(error.found(), "");
+
+        let error = parse("color(srgb 1 1)").unwrap_err();
+        assert_eq!(error.kind(), MissingCoordinate);
+        assert_eq!(error.span(), 14..14);
+        assert_eq!(error.coordinate(), Some(2));
+
+        let error = parse("color(srgb 1 1 1 1)").unwrap_err();
+        assert_eq!(error.kind(), TooManyCoordinates);
+        assert_eq!(error.found(), "1");
+        assert_eq!(error.span(), 17..18);
+
+        let error = parse("color(nemo 1 1 1)").unwrap_err();
+        assert_eq!(error.found(), "nemo");
+
+        let error = parse("rgb:1/12345/1").unwrap_err();
+        assert_eq!(error.kind(), OversizedCoordinate);
+        assert_eq!(error.span(), 6..11);
+        assert_eq!(error.coordinate(), Some(1));
+
+        let error = parse("rgb:1/2/3/4/5").unwrap_err();
+        assert_eq!(error.kind(), TooManyCoordinates);
+        assert_eq!(error.found(), "/4/5");
+
+        let error = parse("rgb:1//3").unwrap_err();
+        assert_eq!(error.kind(), MissingCoordinate);
+        assert_eq!(error.span(), 6..6);
+
+        let error = parse("#💩00").unwrap_err();
+        assert_eq!(error.kind(), UnexpectedCharacters);
+        assert_eq!(error.found(), "#💩00");
+
+        let error = parse("#efg").unwrap_err();
+        assert_eq!(error.kind(), MalformedHex);
+        assert_eq!(error.found(), "g");
+        assert_eq!(error.coordinate(), Some(2));
+
+        let error = parse("ciexyz:1/x/1").unwrap_err();
+        assert_eq!(error.span(), 9..10);
+        assert_eq!(error.coordinate(), Some(1));
+
+        let error = parse("💩(1 1 1)").unwrap_err();
+        assert_eq!(error.kind(), UnknownFormat);
+        assert_eq!(
+            format!("{}", error),
+            format!(
+                "{}\n    💩(1 1 1)\n    ^^^^^^^^ expected color format or name, found `💩(1 1 1)`",
+                UnknownFormat
+            )
+        );
+
+        assert_eq!(
+            format!("{}", parse("lab(50 0 0 /)").unwrap_err()),
+            "color format should have 3 coordinates but is missing one (alpha)\n    \
+            lab(50 0 0 /)\n                ^ expected alpha"
+        );
+    }
+
+    #[test]
+    fn test_parse_color_mix() {
+        assert_eq!(
+            parse("color-mix(in srgb, red, blue)"),
+            Ok((Srgb, [0.5, 0.0, 0.5], 1.0))
+        );
+        assert_eq!(
+            parse("color-mix(in srgb, 25% rgb(100% 0 0), blue)"),
+            Ok((Srgb, [0.25, 0.0, 0.75], 1.0))
+        );
+        assert_eq!(
+            parse("color-mix(in srgb-linear, #000 30%, #fff 30%)"),
+            Ok((LinearSrgb, [0.5, 0.5, 0.5], 0.6))
+        );
+        assert_eq!(
+            parse("Color-Mix( in srgb , color-mix(in srgb, red, lime) , blue 50% )"),
+            Ok((Srgb, [0.25, 0.25, 0.5], 1.0))
+        );
+
+        // Hue interpolation applies to polar color spaces only.
+        let (space, [_, _, h], _) =
+            parse("color-mix(in oklch longer hue, oklch(0.5 0.1 10), oklch(0.5 0.1 30))").unwrap();
+        assert_eq!(space, Oklch);
+        assert!((h - 200.0).abs() < 1e-9);
+        assert_eq!(
+            parse("color-mix(in oklch, oklch(0.5 none 10), oklch(0.7 0.1 30))"),
+            Ok((Oklch, [0.6, 0.1, 20.0], 1.0))
+        );
+
+        let error = parse("color-mix(in srgb longer hue, red, blue)").unwrap_err();
+        assert_eq!(error.kind(), UnexpectedCharacters);
+        assert_eq!(error.found(), "longer");
+
+        let error = parse("color-mix(in nemo, red, blue)").unwrap_err();
+        assert_eq!(error.kind(), UnknownColorSpace);
+        assert_eq!(error.found(), "nemo");
+
+        let error = parse("color-mix(in srgb, red 120%, blue)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "120%");
+
+        let error = parse("color-mix(in srgb, red 0%, blue 0%)").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.span(), 32..34);
+
+        let error = parse("color-mix(in srgb, red)").unwrap_err();
+        assert_eq!(error.kind(), MissingCoordinate);
+
+        let error = parse("color-mix(in srgb, red, color(srgb 1 x 0))").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "x");
+        assert_eq!(error.coordinate(), Some(1));
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        let close = |result: Result<(crate::ColorSpace, [Float; 3], Float), ColorFormatError>,
+                     space: crate::ColorSpace,
+                     coordinates: [Float; 3],
+                     alpha: Float| {
+            let (actual_space, actual, actual_alpha) = result.unwrap();
+            assert_eq!(actual_space, space);
+            for (a, e) in actual.iter().zip(coordinates.iter()) {
+                assert!(
+                    (a.is_nan() && e.is_nan()) || (a - e).abs() < 1e-9,
+                    "{:?} vs {:?}",
+                    actual,
+                    coordinates
+                );
+            }
+            assert!((actual_alpha - alpha).abs() < 1e-9);
+        };
+
+        close(
+            parse("oklch(from oklch(0.5 0.1 30) l c calc(h + 180))"),
+            Oklch,
+            [0.5, 0.1, 210.0],
+            1.0,
+        );
+        close(
+            parse("rgb(from #ff0000 calc(r / 2) g b / 0.5)"),
+            Srgb,
+            [127.5 / 255.0, 0.0, 0.0],
+            0.5,
+        );
+        close(
+            parse("hsl(from red h s calc(l * 0.5))"),
+            Hsl,
+            [0.0, 100.0, 25.0],
+            1.0,
+        );
+        close(
+            parse("OKLCH(from oklch(0.5 0.1 30 / 0.3) calc(l + 10%) max(c, 0.2) 0.5turn)"),
+            Oklch,
+            [0.6, 0.2, 180.0],
+            0.3,
+        );
+        close(
+            parse("oklch(from oklch(0.5 0.1 30) clamp(0.6, l, 0.8) min(c, 0.05, 1) none / alpha)"),
+            Oklch,
+            [0.6, 0.05, Float::NAN],
+            1.0,
+        );
+        close(
+            parse("oklab(from oklab(0.5 0.1 -0.1) l calc((a - 0.1) * 2) calc(b * -1))"),
+            Oklab,
+            [0.5, 0.0, 0.1],
+            1.0,
+        );
+        close(
+            parse("color(from color(srgb 1 0.5 0) srgb-linear r g b)"),
+            LinearSrgb,
+            [1.0, 0.21404114048223255, 0.0],
+            1.0,
+        );
+        close(
+            parse("oklch(from color-mix(in oklch, oklch(0.5 0.1 10), oklch(0.7 0.1 30)) l c h)"),
+            Oklch,
+            [0.6, 0.1, 20.0],
+            1.0,
+        );
+
+        let resolve = |s: &str| match s {
+            "ansi.blue" => Some((Srgb, [0.0, 0.0, 1.0], 1.0)),
+            _ => None,
+        };
+        close(
+            parse_with("rgb(from ansi.blue b g r)", &resolve),
+            Srgb,
+            [1.0, 0.0, 0.0],
+            1.0,
+        );
+        close(
+            parse_with("Ansi.Blue", &resolve),
+            Srgb,
+            [0.0, 0.0, 1.0],
+            1.0,
+        );
+
+        let error = parse("rgb(from ansi.blue r g b)").unwrap_err();
+        assert_eq!(error.kind(), UnknownFormat);
+        assert_eq!(error.found(), "ansi.blue");
+
+        let error = parse("oklch(from red l c calc(h + x))").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+        assert_eq!(error.found(), "x");
+        assert_eq!(error.coordinate(), Some(2));
+        assert_eq!(error.expected(), Some("channel keyword"));
+
+        let error = parse("oklch(from red l 1deg h)").unwrap_err();
+        assert_eq!(error.found(), "1deg");
+        assert_eq!(error.coordinate(), Some(1));
+
+        let error = parse("oklch(from red l c calc(h / 0))").unwrap_err();
+        assert_eq!(error.kind(), MalformedFloat);
+
+        let error = parse("oklch(from red l c)").unwrap_err();
+        assert_eq!(error.kind(), MissingCoordinate);
+        assert_eq!(error.coordinate(), Some(2));
+
+        let error = parse("oklch(from red l c h h)").unwrap_err();
+        assert_eq!(error.kind(), TooManyCoordinates);
+        assert_eq!(error.found(), "h");
+
+        let error = parse("oklch(from red, l, c, h)").unwrap_err();
+        assert_eq!(error.kind(), UnexpectedCharacters);
+        assert_eq!(error.found(), ",");
+
+        let error = parse("color(from red nemo r g b)").unwrap_err();
+        assert_eq!(error.kind(), UnknownColorSpace);
+        assert_eq!(error.found(), "nemo");
+    }
+
+    #[test]
+    fn test_nesting() {
+        let relative = |n| format!("{}red{}", "rgb(from ".repeat(n), " r g b)".repeat(n));
+        assert_eq!(
+            parse(&relative(MAX_NESTING)),
+            Ok((Srgb, [1.0, 0.0, 0.0], 1.0))
+        );
+        for n in [MAX_NESTING + 1, 600] {
+            let error = parse(&relative(n)).unwrap_err();
+            assert_eq!(error.kind(), UnexpectedCharacters);
+            assert_eq!(error.span(), 9 * MAX_NESTING + 4..9 * MAX_NESTING + 8);
+            assert_eq!(error.found(), "from");
+        }
+
+        // calc() itself counts as one level.
+        let parenthesized = |n| format!("rgb(calc({}255{}) 0 0)", "(".repeat(n), ")".repeat(n));
+        assert_eq!(
+            parse(&parenthesized(MAX_NESTING - 1)),
+            Ok((Srgb, [1.0, 0.0, 0.0], 1.0))
+        );
+        for n in [MAX_NESTING, 100_000] {
+            let error = parse(&parenthesized(n)).unwrap_err();
+            assert_eq!(error.kind(), UnexpectedCharacters);
+            assert_eq!(error.span(), 8 + MAX_NESTING..9 + MAX_NESTING);
+            assert_eq!(error.found(), "(");
+            assert_eq!(error.coordinate(), Some(0));
+        }
+
+        let negated = |n| format!("rgb(calc({}255) 0 0)", "-".repeat(n));
+        assert_eq!(
+            parse(&negated(MAX_NESTING - 2)),
+            Ok((Srgb, [1.0, 0.0, 0.0], 1.0))
+        );
+        for n in [MAX_NESTING, 100_000] {
+            let error = parse(&negated(n)).unwrap_err();
+            assert_eq!(error.kind(), UnexpectedCharacters);
+            assert_eq!(error.span(), 8 + MAX_NESTING..9 + MAX_NESTING);
+            assert_eq!(error.found(), "-");
+        }
+    }
+
+    #[test]
+    fn test_color_mix_nesting() {
+        let mixed = |n| {
+            format!(
+                "{}red{}",
+                "color-mix(in srgb, ".repeat(n),
+                ", red)".repeat(n)
+            )
+        };
+        assert_eq!(parse(&mixed(MAX_NESTING)), Ok((Srgb, [1.0, 0.0, 0.0], 1.0)));
+        for n in [MAX_NESTING + 1, 1_000] {
+            let error = parse(&mixed(n)).unwrap_err();
+            assert_eq!(error.kind(), UnexpectedCharacters);
+            assert_eq!(error.span(), 19 * MAX_NESTING + 9..19 * MAX_NESTING + 10);
+            assert_eq!(error.found(), "(");
+        }
+
+        // Relative colors and color mixes share the same limit.
+        let error = parse(&format!(
+            "{}rgb(from red r g b){}",
+            "color-mix(in srgb, ".repeat(MAX_NESTING),
+            ", red)".repeat(MAX_NESTING)
+        ))
+        .unwrap_err();
+        assert_eq!(error.found(), "from");
+    }
+
+    struct Formatted(
+        ColorFormat,
+        crate::ColorSpace,
+        [crate::Float; 3],
+        crate::Float,
+    );
+
+    impl std::fmt::Display for Formatted {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            format_as(self.0, self.1, &self.2, self.3, f)
+        }
+    }
+
+    #[test]
+    fn test_format_as() {
+        use crate::ColorSpace::*;
+
+        let orange = [1.0, 0.5, 0.0];
+        assert_eq!(
+            Formatted(ColorFormat::Hex, Srgb, orange, 1.0).to_string(),
+            "#ff8000"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Hex, Srgb, orange, 0.5).to_string(),
+            "#ff800080"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Rgb, Srgb, orange, 1.0).to_string(),
+            "rgb(255, 128, 0)"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Rgb, Srgb, orange, 0.25).to_string(),
+            "rgba(255, 128, 0, 0.25)"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Hsl, Srgb, orange, 1.0).to_string(),
+            "hsl(30, 100%, 50%)"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Hsl, Srgb, [0.5, 0.5, 0.5], 0.5).to_string(),
+            "hsla(0, 0%, 50%, 0.5)"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::X, Srgb, orange, 0.5).to_string(),
+            "rgb:ffff/8000/0000"
+        );
+
+        // Out-of-gamut colors are gamut-mapped before formatting as sRGB.
+        let green = [0.0, 1.0, 0.0];
+        assert_eq!(
+            Formatted(ColorFormat::Hex, DisplayP3, green, 1.0).to_string(),
+            "#00fb29"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Css, DisplayP3, green, 1.0).to_string(),
+            "color(display-p3 0 1 0)"
+        );
+        assert_eq!(
+            Formatted(ColorFormat::Css, Hsl, [120.0, 110.0, 40.0], 1.0).to_string(),
+            "hsl(120 100 42)"
+        );
+
+        // Missing alpha is fully transparent for legacy formats.
+        assert_eq!(
+            Formatted(ColorFormat::Hex, Srgb, orange, crate::Float::NAN).to_string(),
+            "#ff800000"
         );
     }
 }
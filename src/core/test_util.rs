@@ -1,10 +1,8 @@
 use crate::Float;
 
 pub(crate) fn close_enough_numbers(value1: Float, value2: Float, is_hue: bool) -> bool {
-    if value1.is_nan() {
-        if is_hue {
-            return value2.is_nan();
-        }
+    if value1.is_nan() && is_hue {
+        return value2.is_nan();
     }
 
     let decimals = if is_hue {
@@ -31,5 +29,5 @@ pub(crate) fn close_enough(
         }
     }
 
-    return true;
+    true
 }
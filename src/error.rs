@@ -48,3 +48,74 @@ impl From<OutOfBoundsError> for PyErr {
         pyo3::exceptions::PyIndexError::new_err(value.to_string())
     }
 }
+
+/// An invalid RGB color space error.
+///
+/// This error indicates that the chromaticities for creating a user-defined
+/// [`RgbSpace`](crate::RgbSpace) do not describe a valid color space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RgbSpaceError {
+    /// A chromaticity is not finite or has a non-positive y coordinate.
+    InvalidChromaticity,
+    /// The primaries are collinear and hence do not span a color space.
+    CollinearPrimaries,
+}
+
+impl std::fmt::Display for RgbSpaceError {
+    /// Format this RGB color space error.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidChromaticity => {
+                f.write_str("chromaticity should be finite with positive y coordinate")
+            }
+            Self::CollinearPrimaries => f.write_str("primaries should not be collinear"),
+        }
+    }
+}
+
+impl std::error::Error for RgbSpaceError {}
+
+#[cfg(feature = "pyffi")]
+impl From<RgbSpaceError> for PyErr {
+    /// Convert an RGB color space error to a Python exception.
+    fn from(value: RgbSpaceError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(value.to_string())
+    }
+}
+
+/// An invalid gradient error.
+///
+/// This error indicates that the colors and positions for creating a
+/// [`Gradient`](crate::Gradient) do not describe a valid gradient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientError {
+    /// There are no colors.
+    NoColors,
+    /// The number of positions differs from the number of colors.
+    MismatchedPositions,
+    /// A position is not finite.
+    InvalidPosition,
+}
+
+impl std::fmt::Display for GradientError {
+    /// Format this gradient error.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NoColors => f.write_str("gradient should have at least one color"),
+            Self::MismatchedPositions => {
+                f.write_str("gradient should have as many positions as colors")
+            }
+            Self::InvalidPosition => f.write_str("gradient positions should be finite"),
+        }
+    }
+}
+
+impl std::error::Error for GradientError {}
+
+#[cfg(feature = "pyffi")]
+impl From<GradientError> for PyErr {
+    /// Convert a gradient error to a Python exception.
+    fn from(value: GradientError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(value.to_string())
+    }
+}
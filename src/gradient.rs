@@ -0,0 +1,968 @@
+#[cfg(feature = "pyffi")]
+use pyo3::prelude::*;
+
+use crate::core::{delta_e_ok, premultiply, prepare_to_interpolate_sequence, unpremultiply};
+use crate::{
+    Color, ColorSpace, Float, GradientError, HueInterpolation, Interpolator, OutOfBoundsError,
+};
+
+// ====================================================================================================================
+// Easing
+// ====================================================================================================================
+
+/// An easing function.
+///
+/// An easing function maps the linear progress between two color stops of a
+/// [`Gradient`] to the fraction used for interpolating between the two stops'
+/// colors. It maps 0 to 0 and 1 to 1.
+///
+/// # Linear, Ease, EaseIn, EaseOut, EaseInOut
+///
+/// These easing functions correspond to CSS's [keywords of the same
+/// name](https://www.w3.org/TR/css-easing-1/#cubic-bezier-easing-functions),
+/// with all but linear easing being cubic Bézier curves.
+///
+/// # CubicBezier
+///
+/// A cubic Bézier curve from (0, 0) to (1, 1) with control points (`x1`,
+/// `y1`) and (`x2`, `y2`), just like CSS's `cubic-bezier()`. The x
+/// coordinates are clamped to `0..=1`.
+///
+/// # Midpoint
+///
+/// The easing for a CSS [color
+/// hint](https://www.w3.org/TR/css-images-4/#color-stop-syntax), which places
+/// the midpoint between the two colors at `hint`, as a fraction of the
+/// distance between the two stops.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::Easing;
+/// assert_eq!(Easing::Linear().apply(0.25), 0.25);
+/// assert!((Easing::Midpoint { hint: 0.25 }.apply(0.25) - 0.5).abs() < 1e-9);
+/// assert!(Easing::EaseIn().apply(0.5) < 0.5);
+/// assert!(Easing::EaseOut().apply(0.5) > 0.5);
+/// ```
+#[cfg_attr(feature = "pyffi", pyclass(eq, frozen))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Easing {
+    Linear(),
+    Ease(),
+    EaseIn(),
+    EaseOut(),
+    EaseInOut(),
+    CubicBezier {
+        x1: Float,
+        y1: Float,
+        x2: Float,
+        y2: Float,
+    },
+    Midpoint {
+        hint: Float,
+    },
+}
+
+/// Evaluate the one-dimensional cubic Bézier curve with the given control
+/// points at the given parameter.
+fn bezier(p1: Float, p2: Float, t: Float) -> Float {
+    let c = 3.0 * p1;
+    let b = 3.0 * (p2 - p1) - c;
+    let a = 1.0 - c - b;
+    ((a * t + b) * t + c) * t
+}
+
+/// Evaluate the derivative of the one-dimensional cubic Bézier curve.
+fn bezier_slope(p1: Float, p2: Float, t: Float) -> Float {
+    let c = 3.0 * p1;
+    let b = 3.0 * (p2 - p1) - c;
+    let a = 1.0 - c - b;
+    (3.0 * a * t + 2.0 * b) * t + c
+}
+
+/// Solve the cubic Bézier curve for the given x coordinate and return the y
+/// coordinate.
+///
+/// This function first tries a few rounds of Newton's method and then falls
+/// back on bisection, which always converges because x is monotonic.
+fn solve_bezier(x1: Float, y1: Float, x2: Float, y2: Float, x: Float) -> Float {
+    let x1 = x1.clamp(0.0, 1.0);
+    let x2 = x2.clamp(0.0, 1.0);
+
+    let mut t = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, t) - x;
+        if error.abs() < 1e-7 {
+            return bezier(y1, y2, t);
+        }
+        let slope = bezier_slope(x1, x2, t);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        t -= error / slope;
+    }
+
+    let mut min = 0.0;
+    let mut max = 1.0;
+    t = x;
+    while max - min > 1e-7 {
+        if bezier(x1, x2, t) < x {
+            min = t;
+        } else {
+            max = t;
+        }
+        t = (min + max) / 2.0;
+    }
+
+    bezier(y1, y2, t)
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Easing {
+    /// Apply this easing function to the linear progress.
+    ///
+    /// The progress is clamped to `0..=1`.
+    pub fn apply(&self, progress: Float) -> Float {
+        let t = progress.clamp(0.0, 1.0);
+        if t == 0.0 || t == 1.0 {
+            return t;
+        }
+
+        match *self {
+            Self::Linear() => t,
+            Self::Ease() => solve_bezier(0.25, 0.1, 0.25, 1.0, t),
+            Self::EaseIn() => solve_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Self::EaseOut() => solve_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Self::EaseInOut() => solve_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Self::CubicBezier { x1, y1, x2, y2 } => solve_bezier(x1, y1, x2, y2, t),
+            Self::Midpoint { hint } => {
+                if hint <= 0.0 {
+                    1.0
+                } else if 1.0 <= hint {
+                    0.0
+                } else {
+                    t.powf((0.5 as Float).ln() / hint.ln())
+                }
+            }
+        }
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl Default for Easing {
+    /// Get the default easing function, [`Easing::Linear`].
+    fn default() -> Self {
+        Self::Linear()
+    }
+}
+
+// ====================================================================================================================
+// Gradient
+// ====================================================================================================================
+
+/// The segment between two color stops.
+#[derive(Clone, Debug)]
+struct Segment {
+    space: ColorSpace,
+    strategy: HueInterpolation,
+    easing: Easing,
+    interpolator: Interpolator,
+}
+
+/// A multi-stop color gradient.
+///
+/// A gradient has one or more color stops, each with a color and a position.
+/// Consistent with [CSS Images
+/// 4](https://www.w3.org/TR/css-images-4/#color-stop-fixup), a position
+/// smaller than a preceding position is treated as that position. Between
+/// two stops, a gradient interpolates in the segment's color space with the
+/// segment's hue interpolation strategy and easing function. Since it uses
+/// [`Interpolator`], it interpolates translucent colors with premultiplied
+/// alpha. Before the first stop, a gradient returns the first stop's color.
+/// After the last stop, it returns the last stop's color. Where two stops
+/// have the same position, the color changes abruptly.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, Easing, Gradient, GradientError, HueInterpolation};
+/// let mut gradient = Gradient::with_positions(
+///     vec![Color::srgb(1, 0, 0), Color::srgb(1, 1, 0), Color::srgb(0, 0.5, 0)],
+///     vec![0.0, 0.2, 1.0],
+///     ColorSpace::Oklch,
+///     HueInterpolation::Shorter,
+/// )?;
+/// assert_eq!(gradient.at(0.2), Color::srgb(1, 1, 0).to(ColorSpace::Oklch));
+///
+/// gradient.set_easing(1, Easing::Midpoint { hint: 0.25 }).unwrap();
+/// let sparkline = gradient.sample(5).collect::<Vec<_>>();
+/// assert_eq!(sparkline.len(), 5);
+/// assert_eq!(sparkline[0], Color::srgb(1, 0, 0).to(ColorSpace::Oklch));
+/// assert_eq!(sparkline[4], Color::srgb(0, 0.5, 0).to(ColorSpace::Oklch));
+/// # Ok::<(), GradientError>(())
+/// ```
+/// <div class=color-swatch>
+/// <div style="background: linear-gradient(in oklch to right, rgb(255 0 0), rgb(255 255 0) 20%, 40%, rgb(0 128 0));"></div>
+/// </div>
+#[cfg_attr(feature = "pyffi", pyclass)]
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    colors: Vec<Color>,
+    positions: Vec<Float>,
+    segments: Vec<Segment>,
+}
+
+impl Gradient {
+    /// Create a new gradient from the colors and positions.
+    fn create(
+        colors: Vec<Color>,
+        positions: Vec<Float>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<Self, GradientError> {
+        if colors.is_empty() {
+            return Err(GradientError::NoColors);
+        } else if colors.len() != positions.len() {
+            return Err(GradientError::MismatchedPositions);
+        } else if positions.iter().any(|p| !p.is_finite()) {
+            return Err(GradientError::InvalidPosition);
+        }
+
+        let mut positions = positions;
+        for index in 1..positions.len() {
+            positions[index] = positions[index].max(positions[index - 1]);
+        }
+
+        let segments = colors
+            .windows(2)
+            .map(|pair| Segment {
+                space,
+                strategy,
+                easing: Easing::Linear(),
+                interpolator: Interpolator::new(&pair[0], &pair[1], space, strategy),
+            })
+            .collect();
+
+        Ok(Self {
+            colors,
+            positions,
+            segments,
+        })
+    }
+
+    /// Determine the evenly spaced positions for the given number of colors.
+    fn even_positions(count: usize) -> Vec<Float> {
+        if count <= 1 {
+            return vec![0.0; count];
+        }
+        let steps = (count - 1) as Float;
+        (0..count).map(|index| index as Float / steps).collect()
+    }
+
+    /// Ensure that the segment index is valid.
+    fn check_segment(&self, index: usize) -> Result<(), OutOfBoundsError> {
+        if index < self.segments.len() {
+            Ok(())
+        } else {
+            Err(OutOfBoundsError::new(
+                index,
+                0..=self.segments.len().saturating_sub(1),
+            ))
+        }
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Gradient {
+    /// Create a new gradient with evenly spaced stops from 0 to 1.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(
+        colors: Vec<Color>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<Self, GradientError> {
+        let positions = Self::even_positions(colors.len());
+        Self::create(colors, positions, space, strategy)
+    }
+
+    /// Create a new gradient with evenly spaced stops from 0 to 1.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(
+        colors: Vec<Color>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<Self, GradientError> {
+        let positions = Self::even_positions(colors.len());
+        Self::create(colors, positions, space, strategy)
+    }
+
+    /// Create a new gradient with stops at the given positions.
+    ///
+    /// There must be as many positions as colors and all positions must be
+    /// finite.
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn with_positions(
+        colors: Vec<Color>,
+        positions: Vec<Float>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<Self, GradientError> {
+        Self::create(colors, positions, space, strategy)
+    }
+
+    /// Create a new gradient with stops at the given positions.
+    ///
+    /// There must be as many positions as colors and all positions must be
+    /// finite.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn with_positions(
+        colors: Vec<Color>,
+        positions: Vec<Float>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<Self, GradientError> {
+        Self::create(colors, positions, space, strategy)
+    }
+
+    /// Get the number of color stops.
+    pub fn stop_count(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Get the position of the first stop.
+    pub fn start(&self) -> Float {
+        self.positions[0]
+    }
+
+    /// Get the position of the last stop.
+    pub fn end(&self) -> Float {
+        self.positions[self.positions.len() - 1]
+    }
+
+    /// Set the color space and hue interpolation strategy for the segment
+    /// between the stops with the given index and the next index.
+    pub fn set_interpolation(
+        &mut self,
+        index: usize,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Result<(), OutOfBoundsError> {
+        self.check_segment(index)?;
+        let segment = &mut self.segments[index];
+        segment.space = space;
+        segment.strategy = strategy;
+        segment.interpolator = Interpolator::new(
+            &self.colors[index],
+            &self.colors[index + 1],
+            space,
+            strategy,
+        );
+        Ok(())
+    }
+
+    /// Set the easing function for the segment between the stops with the
+    /// given index and the next index.
+    pub fn set_easing(&mut self, index: usize, easing: Easing) -> Result<(), OutOfBoundsError> {
+        self.check_segment(index)?;
+        self.segments[index].easing = easing;
+        Ok(())
+    }
+
+    /// Compute the color at the given position.
+    ///
+    /// The returned color is in the color space of the segment containing the
+    /// position. A position before the first stop or after the last stop
+    /// returns the first or last stop's color, respectively.
+    pub fn at(&self, position: Float) -> Color {
+        if self.segments.is_empty() || position.is_nan() || position <= self.start() {
+            let color = &self.colors[0];
+            return match self.segments.first() {
+                Some(segment) => color.to(segment.space),
+                None => color.clone(),
+            };
+        }
+
+        // Pick the last segment that starts at or before the position.
+        let index = self.positions[1..self.positions.len() - 1].partition_point(|p| *p <= position);
+        let segment = &self.segments[index];
+        let start = self.positions[index];
+        let end = self.positions[index + 1];
+
+        let progress = if end <= start {
+            1.0
+        } else {
+            (position - start) / (end - start)
+        };
+        segment.interpolator.at(segment.easing.apply(progress))
+    }
+
+    /// Sample the given number of evenly spaced colors.
+    ///
+    /// The first sample is at the first stop and the last sample at the last
+    /// stop. A single sample is at the first stop.
+    pub fn sample(&self, count: usize) -> GradientIterator {
+        GradientIterator {
+            gradient: self.clone(),
+            index: 0,
+            count,
+        }
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// An iterator over evenly spaced samples of a gradient.
+///
+/// This iterator is returned by [`Gradient::sample`] and is both fused and
+/// exact.
+#[cfg_attr(feature = "pyffi", pyclass)]
+#[derive(Debug)]
+pub struct GradientIterator {
+    gradient: Gradient,
+    index: usize,
+    count: usize,
+}
+
+impl Iterator for GradientIterator {
+    type Item = Color;
+
+    /// Access the next sample.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+
+        let fraction = if self.count == 1 {
+            0.0
+        } else {
+            self.index as Float / (self.count - 1) as Float
+        };
+        self.index += 1;
+
+        let start = self.gradient.start();
+        let end = self.gradient.end();
+        Some(self.gradient.at((end - start).mul_add(fraction, start)))
+    }
+
+    /// Get the number of remaining samples.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl std::iter::FusedIterator for GradientIterator {}
+impl std::iter::ExactSizeIterator for GradientIterator {}
+
+#[cfg(feature = "pyffi")]
+#[pymethods]
+impl GradientIterator {
+    /// Access the next sample. <span class=python-only></span>
+    pub fn __next__(&mut self) -> Option<Color> {
+        self.next()
+    }
+
+    /// Access this iterator. <span class=python-only></span>
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+}
+
+// ====================================================================================================================
+// Spline
+// ====================================================================================================================
+
+/// A choice of interpolating splines.
+///
+/// Both splines pass through every color. A Catmull-Rom spline only considers
+/// the two neighboring colors for each segment and hence is local: Changing
+/// one color only changes the four nearest segments. A natural cubic spline
+/// has continuous second derivatives and hence is smoother, but changing one
+/// color changes all segments.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SplineKind {
+    /// A uniform Catmull-Rom spline.
+    CatmullRom,
+    /// A natural cubic spline.
+    Natural,
+}
+
+/// Compute the second derivatives of the natural cubic spline through the
+/// values.
+///
+/// This function solves the tridiagonal system of equations with the Thomas
+/// algorithm, fixing the second derivatives at both ends at zero.
+fn natural_derivatives(values: &[Float]) -> Vec<Float> {
+    let count = values.len();
+    let mut derivatives = vec![0.0; count];
+    if count < 3 {
+        return derivatives;
+    }
+
+    let mut upper = vec![0.0; count];
+    let mut rhs = vec![0.0; count];
+    for index in 1..count - 1 {
+        let value = 6.0 * (values[index - 1] - 2.0 * values[index] + values[index + 1]);
+        let pivot = 4.0 - upper[index - 1];
+        upper[index] = 1.0 / pivot;
+        rhs[index] = (value - rhs[index - 1]) / pivot;
+    }
+    for index in (1..count - 1).rev() {
+        derivatives[index] = rhs[index] - upper[index] * derivatives[index + 1];
+    }
+
+    derivatives
+}
+
+/// A smooth spline through a sequence of colors.
+///
+/// Unlike [`Gradient`], which interpolates linearly between color stops and
+/// hence has visible kinks at the stops, a spline curves smoothly through all
+/// colors. Like CSS Color 4's interpolation, it carries forward missing
+/// components, uses the hue interpolation strategy for every pair of
+/// consecutive colors, and interpolates translucent colors with premultiplied
+/// alpha.
+///
+/// By default, the colors are evenly spaced along the spline. Since the
+/// perceptual differences between consecutive colors usually differ, the
+/// spline's perceptual speed is uneven. [`Spline::with_arc_length`]
+/// re-parameterizes the spline by the cumulative ΔE OK along the curve, so
+/// that evenly spaced fractions result in perceptually even steps, as
+/// desirable for colormaps.
+///
+/// # Examples
+///
+/// ```
+/// # use prettypretty::{Color, ColorSpace, DeltaE, GradientError, HueInterpolation};
+/// # use prettypretty::{OkVersion, Spline, SplineKind};
+/// let spline = Spline::new(
+///     vec![
+///         Color::oklch(0.3, 0.1, 280.0),
+///         Color::oklch(0.6, 0.15, 180.0),
+///         Color::oklch(0.9, 0.15, 100.0),
+///     ],
+///     ColorSpace::Oklch,
+///     HueInterpolation::Shorter,
+///     SplineKind::CatmullRom,
+/// )?;
+/// assert_eq!(spline.at(0.5), Color::oklch(0.6, 0.15, 180.0));
+///
+/// let uniform = spline.with_arc_length();
+/// let metric = DeltaE::Ok(OkVersion::Original);
+/// let colors = uniform.sample(9);
+/// let steps = colors
+///     .windows(2)
+///     .map(|pair| pair[0].distance(&pair[1], metric))
+///     .collect::<Vec<_>>();
+/// for step in &steps {
+///     assert!((step - steps[0]).abs() < 0.001);
+/// }
+/// # Ok::<(), GradientError>(())
+/// ```
+/// <div class=color-swatch>
+/// <div style="background-color: oklch(0.3 0.1 280);"></div>
+/// <div style="background-color: oklch(0.6 0.15 180);"></div>
+/// <div style="background-color: oklch(0.9 0.15 100);"></div>
+/// </div>
+#[cfg_attr(feature = "pyffi", pyclass(frozen))]
+#[derive(Clone, Debug)]
+pub struct Spline {
+    space: ColorSpace,
+    kind: SplineKind,
+    points: Vec<[Float; 4]>,
+    derivatives: Vec<[Float; 4]>,
+    arc_length: Option<Vec<Float>>,
+}
+
+impl Spline {
+    /// The number of samples per segment for measuring arc length.
+    const ARC_SAMPLES: usize = 64;
+
+    /// Create a new spline.
+    fn create(
+        colors: Vec<Color>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+        kind: SplineKind,
+    ) -> Result<Self, GradientError> {
+        if colors.is_empty() {
+            return Err(GradientError::NoColors);
+        }
+
+        let coordinates = colors
+            .iter()
+            .map(|color| (color.space(), *color.as_ref()))
+            .collect::<Vec<_>>();
+        let coordinates = prepare_to_interpolate_sequence(&coordinates, space, strategy);
+
+        // Fill in missing alpha just like coordinates.
+        let mut alphas = colors.iter().map(Color::alpha).collect::<Vec<_>>();
+        for index in 1..alphas.len() {
+            if alphas[index].is_nan() {
+                alphas[index] = alphas[index - 1];
+            }
+        }
+        for index in (0..alphas.len() - 1).rev() {
+            if alphas[index].is_nan() {
+                alphas[index] = alphas[index + 1];
+            }
+        }
+
+        let points: Vec<[Float; 4]> = coordinates
+            .iter()
+            .zip(alphas.iter())
+            .map(|(coordinates, alpha)| {
+                let [c1, c2, c3] = premultiply(space, coordinates, *alpha);
+                [c1, c2, c3, *alpha]
+            })
+            .collect();
+
+        let derivatives = match kind {
+            SplineKind::CatmullRom => Vec::new(),
+            SplineKind::Natural => {
+                let columns: [Vec<Float>; 4] = std::array::from_fn(|index| {
+                    let values = points.iter().map(|p| p[index]).collect::<Vec<_>>();
+                    natural_derivatives(&values)
+                });
+                (0..points.len())
+                    .map(|position| std::array::from_fn(|index| columns[index][position]))
+                    .collect()
+            }
+        };
+
+        Ok(Self {
+            space,
+            kind,
+            points,
+            derivatives,
+            arc_length: None,
+        })
+    }
+
+    /// Evaluate the spline at the given parameter, which ranges from zero to
+    /// the number of colors minus one.
+    fn evaluate(&self, parameter: Float) -> Color {
+        let count = self.points.len();
+        let point = if count == 1 {
+            self.points[0]
+        } else {
+            let segment = (parameter.floor().max(0.0) as usize).min(count - 2);
+            let t = (parameter - segment as Float).clamp(0.0, 1.0);
+            let p1 = &self.points[segment];
+            let p2 = &self.points[segment + 1];
+
+            match self.kind {
+                SplineKind::CatmullRom => {
+                    // Extrapolate the missing neighbors at both ends.
+                    let p0 = if segment == 0 {
+                        std::array::from_fn(|index| 2.0 * p1[index] - p2[index])
+                    } else {
+                        self.points[segment - 1]
+                    };
+                    let p3 = if segment + 2 == count {
+                        std::array::from_fn(|index| 2.0 * p2[index] - p1[index])
+                    } else {
+                        self.points[segment + 2]
+                    };
+
+                    let t2 = t * t;
+                    let t3 = t2 * t;
+                    std::array::from_fn(|index| {
+                        let [a, b, c, d] = [p0[index], p1[index], p2[index], p3[index]];
+                        0.5 * (2.0 * b
+                            + (c - a) * t
+                            + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2
+                            + (3.0 * b - a - 3.0 * c + d) * t3)
+                    })
+                }
+                SplineKind::Natural => {
+                    let m1 = &self.derivatives[segment];
+                    let m2 = &self.derivatives[segment + 1];
+                    let s = 1.0 - t;
+                    std::array::from_fn(|index| {
+                        s * p1[index]
+                            + t * p2[index]
+                            + ((s * s * s - s) * m1[index] + (t * t * t - t) * m2[index]) / 6.0
+                    })
+                }
+            }
+        };
+
+        let [c1, c2, c3, alpha] = point;
+        let alpha = if alpha.is_nan() {
+            alpha
+        } else {
+            alpha.clamp(0.0, 1.0)
+        };
+        Color::new(self.space, unpremultiply(self.space, &[c1, c2, c3], alpha)).with_alpha(alpha)
+    }
+
+    /// Map the fraction of arc length to the spline's parameter.
+    fn to_parameter(&self, fraction: Float) -> Float {
+        let segments = (self.points.len() - 1) as Float;
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        let Some(lengths) = &self.arc_length else {
+            return fraction * segments;
+        };
+
+        let total = lengths[lengths.len() - 1];
+        if total <= 0.0 {
+            return fraction * segments;
+        }
+
+        let target = fraction * total;
+        let index = lengths.partition_point(|length| *length < target);
+        if index == 0 {
+            return 0.0;
+        } else if index == lengths.len() {
+            return segments;
+        }
+
+        let [l1, l2] = [lengths[index - 1], lengths[index]];
+        let t = if l2 <= l1 {
+            0.0
+        } else {
+            (target - l1) / (l2 - l1)
+        };
+        (index as Float - 1.0 + t) / Self::ARC_SAMPLES as Float
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Spline {
+    /// Create a new spline through the colors.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(
+        colors: Vec<Color>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+        kind: SplineKind,
+    ) -> Result<Self, GradientError> {
+        Self::create(colors, space, strategy, kind)
+    }
+
+    /// Create a new spline through the colors.
+    #[cfg(not(feature = "pyffi"))]
+    pub fn new(
+        colors: Vec<Color>,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+        kind: SplineKind,
+    ) -> Result<Self, GradientError> {
+        Self::create(colors, space, strategy, kind)
+    }
+
+    /// Get the interpolation color space.
+    pub fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    /// Get the kind of spline.
+    pub fn kind(&self) -> SplineKind {
+        self.kind
+    }
+
+    /// Determine whether this spline is parameterized by arc length.
+    pub fn is_arc_length(&self) -> bool {
+        self.arc_length.is_some()
+    }
+
+    /// Re-parameterize this spline by perceptual arc length.
+    ///
+    /// This method measures the spline's length as the cumulative ΔE OK in
+    /// Oklab between closely spaced samples and returns a new spline that maps
+    /// fractions to the color at the same fraction of that length.
+    pub fn with_arc_length(&self) -> Self {
+        let samples = (self.points.len() - 1) * Self::ARC_SAMPLES;
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut total = 0.0;
+        let mut previous = *self.evaluate(0.0).to(ColorSpace::Oklab).as_ref();
+        lengths.push(total);
+
+        for index in 1..=samples {
+            let parameter = index as Float / Self::ARC_SAMPLES as Float;
+            let current = *self.evaluate(parameter).to(ColorSpace::Oklab).as_ref();
+            let distance = delta_e_ok(&previous, &current);
+            if distance.is_finite() {
+                total += distance;
+            }
+            lengths.push(total);
+            previous = current;
+        }
+
+        Self {
+            arc_length: Some(lengths),
+            ..self.clone()
+        }
+    }
+
+    /// Compute the color at the given fraction.
+    ///
+    /// The fraction is clamped to `0..=1`. Without arc length
+    /// parameterization, the colors are at evenly spaced fractions.
+    pub fn at(&self, fraction: Float) -> Color {
+        self.evaluate(self.to_parameter(fraction))
+    }
+
+    /// Sample the given number of evenly spaced colors.
+    ///
+    /// The first sample is the first color, the last sample the last color. A
+    /// single sample is the first color.
+    pub fn sample(&self, count: usize) -> Vec<Color> {
+        if count == 1 {
+            return vec![self.at(0.0)];
+        }
+
+        let steps = count.saturating_sub(1) as Float;
+        (0..count)
+            .map(|index| self.at(index as Float / steps))
+            .collect()
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// ====================================================================================================================
+
+#[cfg(test)]
+mod test {
+    use super::{Easing, Gradient, Spline, SplineKind};
+    use crate::{Color, ColorSpace, Float, GradientError, HueInterpolation};
+
+    #[test]
+    fn test_easing() {
+        for easing in [
+            Easing::Linear(),
+            Easing::Ease(),
+            Easing::EaseIn(),
+            Easing::EaseOut(),
+            Easing::EaseInOut(),
+            Easing::Midpoint { hint: 0.7 },
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+
+            let mut previous = 0.0;
+            for step in 1..=20 {
+                let value = easing.apply(step as f64 / 20.0);
+                assert!(previous <= value, "{:?}", easing);
+                previous = value;
+            }
+        }
+
+        // ease-in-out is symmetric.
+        let ease = Easing::EaseInOut();
+        assert!((ease.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!((ease.apply(0.3) + ease.apply(0.7) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gradient() -> Result<(), GradientError> {
+        assert_eq!(
+            Gradient::new(vec![], ColorSpace::Oklab, HueInterpolation::Shorter).unwrap_err(),
+            GradientError::NoColors
+        );
+
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        let blue = Color::srgb(0.0, 0.0, 1.0);
+        let white = Color::srgb(1.0, 1.0, 1.0);
+
+        // Out-of-order positions are fixed up, resulting in a hard stop.
+        let gradient = Gradient::with_positions(
+            vec![red.clone(), blue.clone(), white.clone()],
+            vec![0.0, 0.5, 0.25],
+            ColorSpace::Srgb,
+            HueInterpolation::Shorter,
+        )?;
+        assert_eq!(gradient.end(), 0.5);
+        assert_eq!(gradient.at(-1.0), red);
+        assert_eq!(gradient.at(0.25), Color::srgb(0.5, 0.0, 0.5));
+        assert_eq!(gradient.at(0.5), white);
+        assert_eq!(gradient.at(2.0), white);
+
+        // Translucent colors interpolate with premultiplied alpha.
+        let gradient = Gradient::new(
+            vec![red.clone(), blue.with_alpha(0.0)],
+            ColorSpace::Srgb,
+            HueInterpolation::Shorter,
+        )?;
+        let samples = gradient.sample(3).collect::<Vec<_>>();
+        assert_eq!(samples[1], red.with_alpha(0.5));
+        assert!(gradient.sample(0).next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spline() -> Result<(), GradientError> {
+        let colors = vec![
+            Color::oklch(0.5, 0.1, 350.0),
+            Color::oklch(0.6, Float::NAN, 0.0),
+            Color::oklch(0.7, 0.1, 20.0),
+            Color::oklch(0.8, 0.1, 40.0).with_alpha(0.5),
+        ];
+
+        for kind in [SplineKind::CatmullRom, SplineKind::Natural] {
+            let spline = Spline::new(
+                colors.clone(),
+                ColorSpace::Oklch,
+                HueInterpolation::Shorter,
+                kind,
+            )?;
+
+            // The spline passes through all colors, with the missing chroma
+            // carried forward and the hue unwrapped.
+            let samples = spline.sample(4);
+            assert_eq!(samples[0], colors[0]);
+            assert_eq!(samples[1], Color::oklch(0.6, 0.1, 360.0));
+            assert_eq!(samples[2], colors[2]);
+            assert_eq!(samples[3], colors[3]);
+
+            // The hue takes the shorter arc between 350 and 20.
+            let [_, _, h] = *spline.at(0.25).as_ref();
+            assert!(350.0 < h && h < 380.0, "{:?} {}", kind, h);
+
+            // Arc length parameterization preserves the ends.
+            let uniform = spline.with_arc_length();
+            assert!(uniform.is_arc_length());
+            assert_eq!(uniform.at(0.0), colors[0]);
+            assert_eq!(uniform.at(1.0), colors[3]);
+        }
+
+        let single = Spline::new(
+            vec![Color::srgb(1.0, 0.0, 0.0)],
+            ColorSpace::Oklab,
+            HueInterpolation::Shorter,
+            SplineKind::Natural,
+        )?
+        .with_arc_length();
+        assert_eq!(
+            single.at(0.5),
+            Color::srgb(1.0, 0.0, 0.0).to(ColorSpace::Oklab)
+        );
+
+        Ok(())
+    }
+}
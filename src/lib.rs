@@ -402,8 +402,8 @@
 //! assert_eq!(Color::from(gray), Color::from_24bit(188, 188, 188));
 //!
 //! let green = TerminalColor::from(71);
-//! assert!(matches!(green, TerminalColor::Rgb6(_)));
-//! if let TerminalColor::Rgb6(also_green) = green {
+//! assert!(matches!(green, TerminalColor::Rgb6 { .. }));
+//! if let TerminalColor::Rgb6 { color: also_green } = green {
 //!     assert_eq!(also_green[0], 1);
 //!     assert_eq!(also_green[1], 3);
 //!     assert_eq!(also_green[2], 1);
@@ -576,13 +576,21 @@ pub type Bits = u32;
 mod collection;
 mod core;
 mod error;
+mod gradient;
 mod object;
 mod term_color;
 
-pub use collection::{Sampler, Theme, ThemeEntry, ThemeEntryIterator, DEFAULT_THEME};
-pub use core::{ColorFormatError, ColorSpace, HueInterpolation};
-pub use error::OutOfBoundsError;
-pub use object::{Color, Interpolator, OkVersion};
+pub use collection::{
+    CategoricalPalette, Sampler, Theme, ThemeEntry, ThemeEntryIterator, TonalPalette, DEFAULT_THEME,
+};
+pub use core::{
+    Cam16, ColorFormat, ColorFormatError, ColorFormatErrorKind, ColorSpace, ColorVisionDeficiency,
+    CvdMethod, GamutBoundary, GamutMapping, HueInterpolation, RgbSpace, Surround, TransferCurve,
+    TransferFunction, ViewingConditions,
+};
+pub use error::{GradientError, OutOfBoundsError, RgbSpaceError};
+pub use gradient::{Easing, Gradient, GradientIterator, Spline, SplineKind};
+pub use object::{Adjustment, Color, DeltaE, Harmony, Interpolator, OkVersion};
 pub use term_color::{
     AnsiColor, EmbeddedRgb, Fidelity, GrayGradient, Layer, TerminalColor, TrueColor,
 };
@@ -595,21 +603,44 @@ use pyo3::prelude::*;
 #[cfg(feature = "pyffi")]
 #[pymodule]
 pub fn color(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Adjustment>()?;
     m.add_class::<AnsiColor>()?;
+    m.add_class::<Cam16>()?;
+    m.add_class::<CategoricalPalette>()?;
     m.add_class::<Color>()?;
+    m.add_class::<ColorFormat>()?;
+    m.add_class::<ColorFormatError>()?;
+    m.add_class::<ColorFormatErrorKind>()?;
     m.add_class::<ColorSpace>()?;
+    m.add_class::<ColorVisionDeficiency>()?;
+    m.add_class::<CvdMethod>()?;
     m.add_class::<Sampler>()?;
+    m.add_class::<DeltaE>()?;
+    m.add_class::<Easing>()?;
     m.add_class::<EmbeddedRgb>()?;
     m.add_class::<Fidelity>()?;
+    m.add_class::<GamutBoundary>()?;
+    m.add_class::<GamutMapping>()?;
+    m.add_class::<Gradient>()?;
+    m.add_class::<GradientIterator>()?;
     m.add_class::<GrayGradient>()?;
+    m.add_class::<Harmony>()?;
     m.add_class::<HueInterpolation>()?;
     m.add_class::<Interpolator>()?;
     m.add_class::<Layer>()?;
     m.add_class::<OkVersion>()?;
+    m.add_class::<RgbSpace>()?;
+    m.add_class::<Spline>()?;
+    m.add_class::<SplineKind>()?;
+    m.add_class::<Surround>()?;
     m.add_class::<TerminalColor>()?;
     m.add_class::<Theme>()?;
     m.add_class::<ThemeEntry>()?;
     m.add_class::<ThemeEntryIterator>()?;
+    m.add_class::<TonalPalette>()?;
+    m.add_class::<TransferCurve>()?;
+    m.add_class::<TransferFunction>()?;
     m.add_class::<TrueColor>()?;
+    m.add_class::<ViewingConditions>()?;
     Ok(())
 }
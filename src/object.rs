@@ -1,20 +1,31 @@
+// PyO3 wraps the result of a fallible method by converting its error into a
+// PyErr, which is a no-op for methods that already return a PyResult.
+#![cfg_attr(feature = "pyffi", allow(clippy::useless_conversion))]
+
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 
 use crate::core::{
-    clip, convert, delta_e_ok, format, from_24bit, in_gamut, interpolate, normalize, parse,
-    prepare_to_interpolate, scale_lightness, to_24bit, to_contrast, to_contrast_luminance_p3,
-    to_contrast_luminance_srgb, to_eq_bits, to_gamut, ColorSpace, HueInterpolation,
+    alpha_to_eq_bits, clip, convert, daltonize, delta_e_2000, delta_e_94, delta_e_cmc,
+    delta_e_hyab, delta_e_itp, delta_e_ok, delta_e_z, format, format_as, from_24bit, from_x11_name,
+    in_gamut, interpolate, mix, normalize, parse, parse_with, premultiply,
+    prepare_alpha_interpolation, prepare_to_interpolate, scale_lightness, simulate_cvd, to_24bit,
+    to_contrast, to_contrast_luminance_p3, to_contrast_luminance_srgb, to_css_name, to_eq_bits,
+    to_gamut, to_gamut_hct, to_gamut_with, to_x11_name, unpremultiply, Cam16, ColorFormat,
+    ColorSpace, ColorVisionDeficiency, CvdMethod, GamutMapping, HueInterpolation, RgbSpace,
+    ViewingConditions,
 };
 
-#[cfg(feature = "pyffi")]
 use crate::core::ColorFormatError;
 
+use std::sync::Arc;
+
 use crate::Float;
 
 /// A high-resolution color object.
 ///
-/// Every color object has a [color space](ColorSpace) and three coordinates.
+/// Every color object has a [color space](ColorSpace), three coordinates, and
+/// an alpha.
 #[doc = include_str!("style.html")]
 ///
 /// # Color Coordinates
@@ -35,13 +46,25 @@ use crate::Float;
 /// component](https://www.w3.org/TR/css-color-4/#missing), i.e., a component
 /// intentionally set to not-a-number, notably for interpolation.
 ///
+/// ## Alpha
+///
+/// A color's alpha determines its opacity and ranges from 0.0 for fully
+/// transparent to 1.0 for fully opaque colors, which also is the default. Just
+/// like coordinates, the alpha may be not-a-number, i.e., missing. Conversion
+/// between color spaces, gamut mapping, and color adjustments all preserve the
+/// alpha. Since terminals cannot display translucent colors,
+/// [`Color::over`] composites a translucent color over an opaque background
+/// and [`Sampler`](crate::Sampler) does so automatically with the current
+/// theme's background color.
+///
 /// ## Normalization
 ///
 /// While coordinates may be not-a-number, that representation of powerless or
 /// missing components can easily render any computation on colors useless. For
 /// that reason, this class automatically normalizes colors with
 /// [`Color::normalize`] if necessary. Normalization replaces not-a-numbers with
-/// zero and also ensures that lightness and chroma have meaningful quantities.
+/// zero and also ensures that lightness, chroma, and alpha have meaningful
+/// quantities.
 ///
 /// ## Equality Testing and Hashing
 ///
@@ -55,7 +78,8 @@ use crate::Float;
 ///   * To preserve not-a-number semantics for hues, also zero out chroma for
 ///     not-a-number hues in Oklch;
 ///   * To preserve rotation semantics for hues, remove all full rotations;
-///   * To prepare for rounding, scale down hues to unit range;
+///   * To prepare for rounding, scale down hues to unit range and CIELAB's
+///     lightness, a/b, and chroma by 100;
 ///   * To allow for floating point error, multiply by 1e5/1e14 and then round,
 ///     which drops the least significant digit;
 ///   * To make zeros comparable, replace negative zero with positive zero (but
@@ -63,17 +87,31 @@ use crate::Float;
 ///   * To convince Rust that coordinates are comparable, convert to bits.
 ///
 /// While rounding isn't strictly necessary for correctness, it makes for a more
-/// robust comparison without meaningfully reducing precision.
+/// robust comparison without meaningfully reducing precision. Equality testing
+/// and hashing treat the alpha the same way, after clamping it to `0..=1`.
 ///
 /// ## Coordinate Access
 ///
 /// Both Rust and Python code can access individual coordinates by indexing a
 /// color object with integers `0..2`.
+///
+/// ## User-Defined RGB Color Spaces
+///
+/// A color in a user-defined [`RgbSpace`] uses XYZ as its color space and
+/// coordinates but also remembers the RGB color space. [`Color::in_gamut`],
+/// [`Color::clip`], [`Color::to_gamut`], [`Color::to_gamut_with`],
+/// [`Color::to_gamut_hct`], and the contrast methods operate on that color
+/// space's gamut. Methods that return a color in the same color space retain
+/// the RGB color space, whereas [`Color::to`] and other methods that return a
+/// color in a different color space drop it. Equality testing and hashing
+/// ignore the RGB color space.
 #[cfg_attr(feature = "pyffi", pyclass(eq, sequence))]
 #[derive(Clone, Debug)]
 pub struct Color {
     space: ColorSpace,
     coordinates: [Float; 3],
+    alpha: Float,
+    rgb_space: Option<Arc<RgbSpace>>,
 }
 
 #[cfg_attr(feature = "pyffi", pymethods)]
@@ -85,6 +123,9 @@ impl Color {
 
     /// Instantiate a new color with the given color space and coordinates.
     ///
+    /// The new color is fully opaque, i.e., has an alpha of 1.0. Use
+    /// [`Color::with_alpha`] to change the alpha.
+    ///
     /// ```
     /// # use prettypretty::{Color, ColorSpace};
     /// let pink = Color::new(ColorSpace::Oklch, [0.7, 0.22, 3.0]);
@@ -97,11 +138,19 @@ impl Color {
     #[new]
     #[inline]
     pub const fn new(space: ColorSpace, coordinates: [Float; 3]) -> Self {
-        Self { space, coordinates }
+        Self {
+            space,
+            coordinates,
+            alpha: 1.0,
+            rgb_space: None,
+        }
     }
 
     /// Instantiate a new color with the given color space and coordinates.
     ///
+    /// The new color is fully opaque, i.e., has an alpha of 1.0. Use
+    /// [`Color::with_alpha`] to change the alpha.
+    ///
     /// ```
     /// # use prettypretty::{Color, ColorSpace};
     /// let pink = Color::new(ColorSpace::Oklch, [0.7, 0.22, 3.0]);
@@ -113,7 +162,77 @@ impl Color {
     #[cfg(not(feature = "pyffi"))]
     #[inline]
     pub const fn new(space: ColorSpace, coordinates: [Float; 3]) -> Self {
-        Self { space, coordinates }
+        Self {
+            space,
+            coordinates,
+            alpha: 1.0,
+            rgb_space: None,
+        }
+    }
+
+    /// Instantiate a new color from coordinates in a user-defined RGB color
+    /// space.
+    ///
+    /// Since user-defined RGB color spaces are not part of [`ColorSpace`],
+    /// this method returns an XYZ color that remembers the RGB color space.
+    /// Gamut testing, gamut mapping, and contrast use the latter's gamut.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, RgbSpace, RgbSpaceError, TransferFunction};
+    /// let rec709 = RgbSpace::new(
+    ///     [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+    ///     [0.3127, 0.3290],
+    ///     TransferFunction::REC709,
+    /// )?;
+    /// let color = Color::from_rgb_space(&rec709, [0.5, 0.5, 0.5]);
+    /// assert_eq!(color.space(), ColorSpace::Xyz);
+    /// assert!(color.rgb_space().is_some());
+    /// assert_eq!(color.to_hex_format(), "#8b8b8b");
+    ///
+    /// let too_bright = Color::from_rgb_space(&rec709, [1.2, 0.5, 0.5]);
+    /// assert!(!too_bright.in_gamut());
+    /// assert!(too_bright.to_gamut().in_gamut());
+    /// # Ok::<(), RgbSpaceError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #8b8b8b;"></div>
+    /// </div>
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn from_rgb_space(space: &RgbSpace, coordinates: [Float; 3]) -> Self {
+        Self::in_rgb_space(Arc::new(space.clone()), coordinates)
+    }
+
+    /// Instantiate a new color from coordinates in a user-defined RGB color
+    /// space.
+    ///
+    /// Since user-defined RGB color spaces are not part of [`ColorSpace`],
+    /// this method returns an XYZ color that remembers the RGB color space.
+    /// Gamut testing, gamut mapping, and contrast use the latter's gamut.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, RgbSpace, RgbSpaceError, TransferFunction};
+    /// let rec709 = RgbSpace::new(
+    ///     [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+    ///     [0.3127, 0.3290],
+    ///     TransferFunction::REC709,
+    /// )?;
+    /// let color = Color::from_rgb_space(&rec709, [0.5, 0.5, 0.5]);
+    /// assert_eq!(color.space(), ColorSpace::Xyz);
+    /// assert!(color.rgb_space().is_some());
+    /// assert_eq!(color.to_hex_format(), "#8b8b8b");
+    ///
+    /// let too_bright = Color::from_rgb_space(&rec709, [1.2, 0.5, 0.5]);
+    /// assert!(!too_bright.in_gamut());
+    /// assert!(too_bright.to_gamut().in_gamut());
+    /// # Ok::<(), RgbSpaceError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #8b8b8b;"></div>
+    /// </div>
+    #[cfg(not(feature = "pyffi"))]
+    pub fn from_rgb_space(space: &RgbSpace, coordinates: [Float; 3]) -> Self {
+        Self::in_rgb_space(Arc::new(space.clone()), coordinates)
     }
 
     /// Parse a color from its string representation. <span class=python-only></span>
@@ -254,6 +373,30 @@ impl Color {
         Self::new(ColorSpace::Oklch, [lr, c, h])
     }
 
+    /// Instantiate a new HCT color with the given hue h, chroma C, and tone T
+    /// coordinates.
+    ///
+    /// # Examples
+    ///
+    /// Not all combinations of hue, chroma, and tone are in gamut for sRGB.
+    /// [`Color::to_gamut_hct`] reduces chroma while preserving hue and tone.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let too_blue = Color::hct(282.8, 120, 32.3).to(ColorSpace::Srgb);
+    /// assert!(!too_blue.in_gamut());
+    ///
+    /// let blue = too_blue.to_gamut_hct();
+    /// assert!(blue.in_gamut());
+    /// let [_, _, tone] = *blue.to(ColorSpace::Hct).as_ref();
+    /// assert!((tone - 32.3).abs() < 1e-6);
+    /// ```
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn hct(h: Float, c: Float, t: Float) -> Self {
+        Self::new(ColorSpace::Hct, [h, c, t])
+    }
+
     /// Instantiate a new sRGB color from its 24-bit representation.
     ///
     /// This function returns a new sRGB color with the given red, green, and
@@ -293,6 +436,73 @@ impl Color {
         Self::new(ColorSpace::Srgb, from_24bit(r, g, b))
     }
 
+    /// Instantiate a new sRGB color from its X11 color name.
+    ///
+    /// This function looks up the name in the X Window System's color name
+    /// database, i.e., `rgb.txt`, ignoring ASCII case and white space. While
+    /// [`Color as FromStr`](struct.Color.html#impl-FromStr-for-Color) also
+    /// recognizes X11 color names, it gives precedence to CSS named colors,
+    /// which differ for `gray`, `green`, `maroon`, and `purple`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let x11_green = Color::from_x11_name("Green").unwrap();
+    /// assert_eq!(x11_green, Color::from_24bit(0, 255, 0));
+    ///
+    /// let css_green = Color::from_str("Green")?;
+    /// assert_eq!(css_green, Color::from_24bit(0, 128, 0));
+    ///
+    /// let slate = Color::from_x11_name("dark slate gray").unwrap();
+    /// assert_eq!(slate, Color::from_24bit(47, 79, 79));
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #00ff00;"></div>
+    /// <div style="background-color: #008000;"></div>
+    /// <div style="background-color: #2f4f4f;"></div>
+    /// </div>
+    #[cfg(feature = "pyffi")]
+    #[staticmethod]
+    pub fn from_x11_name(name: &str) -> Option<Self> {
+        from_x11_name(name).map(|[r, g, b]| Self::new(ColorSpace::Srgb, from_24bit(r, g, b)))
+    }
+
+    /// Instantiate a new sRGB color from its X11 color name.
+    ///
+    /// This function looks up the name in the X Window System's color name
+    /// database, i.e., `rgb.txt`, ignoring ASCII case and white space. While
+    /// [`Color as FromStr`](struct.Color.html#impl-FromStr-for-Color) also
+    /// recognizes X11 color names, it gives precedence to CSS named colors,
+    /// which differ for `gray`, `green`, `maroon`, and `purple`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let x11_green = Color::from_x11_name("Green").unwrap();
+    /// assert_eq!(x11_green, Color::from_24bit(0, 255, 0));
+    ///
+    /// let css_green = Color::from_str("Green")?;
+    /// assert_eq!(css_green, Color::from_24bit(0, 128, 0));
+    ///
+    /// let slate = Color::from_x11_name("dark slate gray").unwrap();
+    /// assert_eq!(slate, Color::from_24bit(47, 79, 79));
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #00ff00;"></div>
+    /// <div style="background-color: #008000;"></div>
+    /// <div style="background-color: #2f4f4f;"></div>
+    /// </div>
+    #[cfg(not(feature = "pyffi"))]
+    pub fn from_x11_name(name: &str) -> Option<Self> {
+        from_x11_name(name).map(|[r, g, b]| Self::new(ColorSpace::Srgb, from_24bit(r, g, b)))
+    }
+
     /// Convert this color to 24-bit RGB representation.
     ///
     /// This method converts the color to a gamut-mapped sRGB color before
@@ -308,7 +518,8 @@ impl Color {
     ///
     /// Like [`Color::to_24bit`], this method converts the color to a
     /// gamut-mapped sRGB color before formatting its coordinates in hashed
-    /// hexadecimal notation.
+    /// hexadecimal notation. If the color is not fully opaque, this method
+    /// also formats the alpha, producing the eight-digit `#123abc80` form.
     ///
     /// # Examples
     ///
@@ -334,14 +545,134 @@ impl Color {
     #[inline]
     pub fn to_hex_format(&self) -> String {
         let [r, g, b] = self.to_24bit();
-        format!("#{:02x}{:02x}{:02x}", r, g, b)
+        if self.is_opaque() {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            let alpha = (self.normalize().alpha * 255.0).round() as u8;
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, alpha)
+        }
+    }
+
+    /// Format this color in the given format.
+    ///
+    /// Except for [`ColorFormat::Css`], all formats serialize sRGB colors and
+    /// hence this method converts the color to sRGB and maps it into gamut
+    /// first. The same applies to HSL and HWB colors in CSS format. With the
+    /// exception of [`ColorFormat::X`], which has no alpha, all formats
+    /// include the alpha of translucent colors.
+    ///
+    /// Instead of calling this method, Rust code can also use format specs:
+    /// `{}` formats in CSS, `{:#}` in legacy `rgb()`, `{:x}` and `{:X}` in
+    /// hashed hexadecimal, and `{:#x}` and `{:#X}` in the X Windows format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormat};
+    /// let pink = Color::p3(1, 0.2, 1);
+    /// assert_eq!(pink.to_format(ColorFormat::Hex), "#ff41fb");
+    /// assert_eq!(pink.to_format(ColorFormat::Rgb), "rgb(255, 65, 251)");
+    /// assert_eq!(pink.to_format(ColorFormat::Hsl), "hsl(301.172, 100%, 62.836%)");
+    /// assert_eq!(pink.to_format(ColorFormat::X), "rgb:ffff/41b7/fc48");
+    /// assert_eq!(pink.to_format(ColorFormat::Css), "color(display-p3 1 0.2 1)");
+    ///
+    /// assert_eq!(format!("{:x}", pink), "#ff41fb");
+    /// assert_eq!(format!("{:#X}", pink), "rgb:FFFF/41B7/FC48");
+    /// assert_eq!(format!("{:#}", pink.with_alpha(0.5)), "rgba(255, 65, 251, 0.5)");
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(display-p3 1 0.2 1);"></div>
+    /// <div style="background-color: #ff41fb;"></div>
+    /// <div style="background-color: rgba(255, 65, 251, 0.5);"></div>
+    /// </div>
+    pub fn to_format(&self, format: ColorFormat) -> String {
+        struct Formatted<'a>(&'a Color, ColorFormat);
+
+        impl std::fmt::Display for Formatted<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                format_as(self.1, self.0.space, &self.0.coordinates, self.0.alpha, f)
+            }
+        }
+
+        Formatted(self, format).to_string()
+    }
+
+    /// Determine the CSS name for this color.
+    ///
+    /// This method returns the name of the CSS named color that is exactly
+    /// this color, `transparent` for transparent black, and `None` otherwise.
+    /// If several names denote the same color, e.g., `aqua` and `cyan`, this
+    /// method returns the alphabetically first name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let purple = Color::from_str("rgb(102 51 153)")?;
+    /// assert_eq!(purple.css_name(), Some("rebeccapurple"));
+    /// assert_eq!(purple.to(ColorSpace::Oklch).css_name(), Some("rebeccapurple"));
+    ///
+    /// let almost = Color::from_str("rgb(102 51 154)")?;
+    /// assert_eq!(almost.css_name(), None);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: rebeccapurple;"></div>
+    /// <div style="background-color: rgb(102 51 154);"></div>
+    /// </div>
+    pub fn css_name(&self) -> Option<&'static str> {
+        let color = self.normalize();
+        if color.alpha == 1.0 {
+            to_css_name(color.space, &color.coordinates)
+        } else if color.alpha == 0.0
+            && to_24bit(color.space, &color.to(ColorSpace::Srgb).coordinates) == [0, 0, 0]
+        {
+            Some("transparent")
+        } else {
+            None
+        }
+    }
+
+    /// Determine the X11 name for this color.
+    ///
+    /// This method returns the name from the X Window System's color name
+    /// database that is exactly this opaque color and `None` otherwise. If
+    /// several names denote the same color, e.g., `gray` and `grey`, this
+    /// method returns the alphabetically first name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::Color;
+    /// let gray = Color::from_24bit(127, 127, 127);
+    /// assert_eq!(gray.x11_name(), Some("gray50"));
+    /// assert_eq!(gray.css_name(), None);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #7f7f7f;"></div>
+    /// </div>
+    pub fn x11_name(&self) -> Option<&'static str> {
+        let color = self.normalize();
+        if color.alpha == 1.0 {
+            to_x11_name(color.space, &color.coordinates)
+        } else {
+            None
+        }
     }
 
     /// Determine whether this color is the default color, i.e., is the origin
     /// of the XYZ color space.
+    ///
+    /// Since black in a user-defined RGB color space also is the origin of
+    /// XYZ, this method additionally requires that the color does not have a
+    /// user-defined RGB color space.
     #[inline]
     pub fn is_default(&self) -> bool {
-        self.space == ColorSpace::Xyz && self.coordinates == [0.0, 0.0, 0.0]
+        self.space == ColorSpace::Xyz
+            && self.coordinates == [0.0, 0.0, 0.0]
+            && self.alpha == 1.0
+            && self.rgb_space.is_none()
     }
 
     /// Access the color space.
@@ -359,6 +690,107 @@ impl Color {
         self.space
     }
 
+    /// Access the alpha.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let translucent = Color::from_str("#ff000080")?;
+    /// assert!((translucent.alpha() - 0.5019607843137255).abs() < 1e-15);
+    /// assert_eq!(Color::srgb(1, 0, 0).alpha(), 1.0);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #ff000080;"></div>
+    /// <div style="background-color: #ff0000;"></div>
+    /// </div>
+    #[inline]
+    pub fn alpha(&self) -> Float {
+        self.alpha
+    }
+
+    /// Access the user-defined RGB color space.
+    ///
+    /// This method returns `None` for colors that were not created with
+    /// [`Color::from_rgb_space`] or [`Color::to_rgb_space`] or that have since
+    /// been converted to another color space.
+    pub fn rgb_space(&self) -> Option<RgbSpace> {
+        self.rgb_space.as_deref().cloned()
+    }
+
+    /// Create a new color with the same color space and coordinates as this
+    /// color but the given alpha.
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn with_alpha(&self, alpha: Float) -> Self {
+        Self {
+            space: self.space,
+            coordinates: self.coordinates,
+            alpha,
+            rgb_space: self.rgb_space.clone(),
+        }
+    }
+
+    /// Determine whether this color is fully opaque, i.e., has an alpha of 1.0.
+    ///
+    /// This method treats an alpha greater than 1.0 as fully opaque and a
+    /// not-a-number alpha as fully transparent.
+    #[inline]
+    pub fn is_opaque(&self) -> bool {
+        self.alpha >= 1.0
+    }
+
+    /// Composite this color over the given background color.
+    ///
+    /// This method implements the [source-over
+    /// operator](https://www.w3.org/TR/compositing-1/#porterduffcompositingoperators_srcover)
+    /// in sRGB, which is how browsers and terminals composite colors. It
+    /// normalizes both colors and returns an sRGB color. If the background is
+    /// fully opaque, as terminal backgrounds are, so is the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let red = Color::from_str("color(srgb 1 0 0 / 0.5)")?;
+    /// let white = Color::srgb(1, 1, 1);
+    /// let pink = red.over(&white);
+    /// assert_eq!(pink, Color::srgb(1, 0.5, 0.5));
+    /// assert!(pink.is_opaque());
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 1 0 0 / 0.5);"></div>
+    /// <div style="background-color: #fff;"></div>
+    /// <div style="background-color: color(srgb 1 0.5 0.5);"></div>
+    /// </div>
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn over(&self, background: &Self) -> Self {
+        let source = self.to(ColorSpace::Srgb).normalize();
+        let backdrop = background.to(ColorSpace::Srgb).normalize();
+        if source.alpha == 1.0 {
+            return source;
+        }
+
+        let alpha = source.alpha + backdrop.alpha * (1.0 - source.alpha);
+        if alpha == 0.0 {
+            return Self::new(ColorSpace::Srgb, [0.0, 0.0, 0.0]).with_alpha(0.0);
+        }
+
+        let [r1, g1, b1] = source.coordinates;
+        let [r2, g2, b2] = backdrop.coordinates;
+        let blend = |c1: Float, c2: Float| {
+            (c1 * source.alpha + c2 * backdrop.alpha * (1.0 - source.alpha)) / alpha
+        };
+
+        Self::new(
+            ColorSpace::Srgb,
+            [blend(r1, r2), blend(g1, g2), blend(b1, b2)],
+        )
+        .with_alpha(alpha)
+    }
+
     /// Access the coordinates. <span class=python-only></span>
     ///
     /// This method provides access to this color's coordinates as a single
@@ -375,7 +807,8 @@ impl Color {
     /// This function replaces not-a-number coordinates with zero. For semantic
     /// coherence, if the hue in Oklch/Oklrch is not-a-number, it also replaces
     /// chroma with zero. Furthermore, it clamps (revised) lightness to `0..=1`
-    /// and chroma to `0..`.
+    /// and chroma to `0..`. Finally, it replaces a not-a-number alpha with zero
+    /// and clamps the alpha to `0..=1`.
     ///
     /// Many methods automatically normalize colors. A statement to that effect
     /// is included in their documentation. Methods that do *not* normalize
@@ -383,12 +816,20 @@ impl Color {
     ///  [`Color::in_gamut`], and [`Color::is_default`].
     #[inline]
     pub fn normalize(&self) -> Self {
-        Self::new(self.space, normalize(self.space, &self.coordinates))
+        let alpha = if self.alpha.is_nan() {
+            0.0
+        } else {
+            self.alpha.clamp(0.0, 1.0)
+        };
+
+        self.with_coordinates(normalize(self.space, &self.coordinates))
+            .with_alpha(alpha)
     }
 
     /// Convert this color to the target color space.
     ///
-    /// This method normalizes the color before conversion.
+    /// This method normalizes the color's coordinates before conversion. It
+    /// preserves the alpha as is.
     ///
     /// # Approach
     ///
@@ -412,7 +853,14 @@ impl Color {
     /// Oklab converting to Oklrab and Oklch, which in turn both convert to
     /// Oklrch—requires 4 single-hop and 4 double-hop conversion functions in
     /// addition to the 2 single-hop, 4 double-hop, and 2 triple-hop functions
-    /// for converting from and to XYZ.
+    /// for converting from and to XYZ. Similarly, the branch of XYZ D50, Lab,
+    /// and Lch—with XYZ D50 converting to Lab, which in turn converts to
+    /// Lch—requires 2 single-hop and 1 double-hop conversion functions in
+    /// each direction, in addition to the Bradford chromatic adaptation
+    /// between XYZ D65 and D50. Finally, HSL, HSV, and HWB are leaves hanging
+    /// off sRGB, Okhsv and Okhsl are leaves hanging off Oklab, and CAM16-UCS
+    /// is a leaf hanging off XYZ. They hence require 2 single-hop conversion
+    /// functions each.
     ///
     /// With those conversion functions in place, routing through the conversion
     /// graph is a straightforward linear case analysis that first matches pairs
@@ -420,21 +868,51 @@ impl Color {
     /// the source color space, and finally matches on the target color space.
     /// Conveniently, a match during the first step also eliminates the need for
     /// the second and third match. See the source code for the full details.
+    ///
+    /// # Examples
+    ///
+    /// The example code converts a color specified in CSS's `lab()` notation,
+    /// which uses the D50 standard illuminant, to sRGB, which uses D65.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError, ColorSpace};
+    /// # use std::str::FromStr;
+    /// let teal = Color::from_str("lab(52 -32 -12)")?;
+    /// assert_eq!(teal.space(), ColorSpace::Lab);
+    ///
+    /// let also_teal = teal.to(ColorSpace::Srgb);
+    /// assert_eq!(also_teal.to_hex_format(), "#008b8f");
+    /// assert_eq!(also_teal.to(ColorSpace::Lab), teal);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: lab(52 -32 -12);"></div>
+    /// <div style="background-color: #008b8f;"></div>
+    /// </div>
     #[inline]
     #[must_use = "method returns a new color and does not mutate original value"]
     pub fn to(&self, target: ColorSpace) -> Self {
-        Self::new(target, convert(self.space, target, &self.coordinates))
+        Self::new(target, convert(self.space, target, &self.coordinates)).with_alpha(self.alpha)
     }
 
     /// Determine whether this color is in-gamut for its color space.
     ///
+    /// Since HSL, HSV, HWB, Okhsv, and Okhsl model sRGB's gamut, a color in
+    /// one of these color spaces is in-gamut exactly if its sRGB equivalent
+    /// is. For Okhsv and Okhsl, that implies a saturation in `0..=1`.
+    ///
     /// ```
     /// # use prettypretty::{Color, ColorSpace};
     /// let red = Color::srgb(1.0, 0.0, 0.0);
     /// assert!(red.in_gamut());
     ///
+    /// let also_red = red.to(ColorSpace::Okhsl);
+    /// assert!((also_red[1] - 1.0).abs() < 0.001);
+    ///
     /// let green = Color::p3(0.0, 1.0, 0.0);
     /// assert!(!green.to(ColorSpace::Srgb).in_gamut());
+    /// assert!(!green.to(ColorSpace::Hsl).in_gamut());
+    /// assert!(green.to(ColorSpace::Okhsl)[1] > 1.0);
     /// ```
     /// <div class=color-swatch>
     /// <div style="background-color: color(srgb 1 0 0);"></div>
@@ -442,7 +920,10 @@ impl Color {
     /// </div>
     #[inline]
     pub fn in_gamut(&self) -> bool {
-        in_gamut(self.space, &self.coordinates)
+        match &self.rgb_space {
+            Some(space) => space.in_gamut(space.from_xyz(self.coordinates)),
+            None => in_gamut(self.space, &self.coordinates),
+        }
     }
 
     /// Clip this color to the gamut of its color space.
@@ -468,7 +949,12 @@ impl Color {
     #[inline]
     #[must_use = "method returns a new color and does not mutate original value"]
     pub fn clip(&self) -> Self {
-        Self::new(self.space, clip(self.space, &self.coordinates))
+        match &self.rgb_space {
+            Some(space) => {
+                self.with_rgb_space_coordinates(space.clip(space.from_xyz(self.coordinates)))
+            }
+            None => self.with_coordinates(clip(self.space, &self.coordinates)),
+        }
     }
 
     /// Map this color into the gamut of its color space.
@@ -495,6 +981,8 @@ impl Color {
     /// simple as calculating Euclidian distance, i.e., the square root of the
     /// coordinate differences squared and summed.
     ///
+    /// Use [`Color::to_gamut_with`] to choose a different algorithm.
+    ///
     /// # Examples
     ///
     /// Display P3's yellow secondary is out of gamut in sRGB. Gamut mapping the
@@ -517,14 +1005,147 @@ impl Color {
     #[inline]
     #[must_use = "method returns a new color and does not mutate original value"]
     pub fn to_gamut(&self) -> Self {
-        Self::new(self.space, to_gamut(self.space, &self.coordinates))
+        match &self.rgb_space {
+            Some(space) => {
+                self.with_rgb_space_coordinates(space.to_gamut(self.to_rgb_coordinates(space)))
+            }
+            None => self.with_coordinates(to_gamut(self.space, &self.coordinates)),
+        }
+    }
+
+    /// Map this color into the gamut of its color space with the given
+    /// algorithm.
+    ///
+    /// This method normalizes the color before gamut-mapping. With
+    /// [`GamutMapping::CSS`], it is equivalent to [`Color::to_gamut`]. See
+    /// [`GamutMapping`] for a description of the algorithms.
+    ///
+    /// # Examples
+    ///
+    /// Different algorithms produce visibly different results for a saturated
+    /// accent color such as Display P3's green primary. With a large JND, the
+    /// CSS algorithm degenerates to clipping, which shifts the hue towards
+    /// yellow. Ray tracing and chroma compression retain lightness and hue,
+    /// whereas hue-preserving clipping trades some lightness for chroma.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, GamutMapping};
+    /// let too_green = Color::p3(0, 1, 0).to(ColorSpace::Srgb);
+    ///
+    /// let css = too_green.to_gamut_with(GamutMapping::CSS);
+    /// assert_eq!(css, too_green.to_gamut());
+    /// assert_eq!(css.to_hex_format(), "#00fb29");
+    ///
+    /// let loose = too_green.to_gamut_with(GamutMapping::Css { jnd: 0.1 });
+    /// assert_eq!(loose.to_hex_format(), "#00ff00");
+    ///
+    /// let traced = too_green.to_gamut_with(GamutMapping::Raytrace());
+    /// assert_eq!(traced.to_hex_format(), "#00f74d");
+    ///
+    /// let compressed = too_green.to_gamut_with(GamutMapping::ChromaCompression());
+    /// assert_eq!(compressed.to_hex_format(), "#01f74f");
+    ///
+    /// let clipped = too_green.to_gamut_with(GamutMapping::HuePreservingClip());
+    /// assert_eq!(clipped.to_hex_format(), "#00f94f");
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(display-p3 0 1 0);"></div>
+    /// <div style="background-color: #00fb29;"></div>
+    /// <div style="background-color: #00ff00;"></div>
+    /// <div style="background-color: #00f74d;"></div>
+    /// <div style="background-color: #01f74f;"></div>
+    /// <div style="background-color: #00f94f;"></div>
+    /// </div>
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn to_gamut_with(&self, mapping: GamutMapping) -> Self {
+        match &self.rgb_space {
+            Some(space) => self.with_rgb_space_coordinates(
+                space.to_gamut_with(mapping, self.to_rgb_coordinates(space)),
+            ),
+            None => self.with_coordinates(to_gamut_with(mapping, self.space, &self.coordinates)),
+        }
+    }
+
+    /// Map this color into the gamut of its color space while preserving HCT
+    /// hue and tone.
+    ///
+    /// This method normalizes the color before gamut mapping. Unlike
+    /// [`Color::to_gamut`], which may adjust lightness when reducing chroma,
+    /// this method performs a binary search over HCT chroma only. Since the
+    /// resulting color has the same tone, i.e., CIELAB lightness L*, as the
+    /// original color, it also has the same contrast against other colors.
+    /// This method is the solver used by [`TonalPalette`](crate::TonalPalette).
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn to_gamut_hct(&self) -> Self {
+        match &self.rgb_space {
+            Some(space) => {
+                self.with_rgb_space_coordinates(space.to_gamut_hct(self.to_rgb_coordinates(space)))
+            }
+            None => self.with_coordinates(to_gamut_hct(self.space, &self.coordinates)),
+        }
     }
 
-    /// Compute the Euclidian distance between the two colors in Oklab.
+    /// Convert this color to the user-defined RGB color space.
     ///
-    /// This method computes the color difference *Delta E OK*, which is the
-    /// Euclidian distance in the Oklab color space, using either original or
-    /// revised version.
+    /// This method normalizes the color before conversion. Since user-defined
+    /// RGB color spaces are not part of [`ColorSpace`], it returns an XYZ
+    /// color that remembers the RGB color space. Use
+    /// [`Color::rgb_space_coordinates`] to access the coordinates in that
+    /// color space.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, RgbSpace, RgbSpaceError, TransferFunction};
+    /// let bt601 = RgbSpace::new(
+    ///     [[0.630, 0.340], [0.310, 0.595], [0.155, 0.070]],
+    ///     [0.3127, 0.3290],
+    ///     TransferFunction::REC709,
+    /// )?;
+    /// let green = Color::srgb(0, 1, 0).to_rgb_space(&bt601);
+    /// assert!(!green.in_gamut());
+    ///
+    /// let also_green = green.to_gamut();
+    /// assert!(also_green.in_gamut());
+    /// assert!(also_green.to(ColorSpace::Srgb).in_gamut());
+    /// assert_eq!(also_green.to_hex_format(), "#3ffb00");
+    /// # Ok::<(), RgbSpaceError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 0 1 0);"></div>
+    /// <div style="background-color: #3ffb00;"></div>
+    /// </div>
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn to_rgb_space(&self, space: &RgbSpace) -> Self {
+        let space = Arc::new(space.clone());
+        let coordinates = self.to_rgb_coordinates(&space);
+        Self::in_rgb_space(space, coordinates).with_alpha(self.alpha)
+    }
+
+    /// Access the coordinates in this color's user-defined RGB color space.
+    ///
+    /// This method returns `None` if the color does not have a user-defined
+    /// RGB color space. It normalizes the color before conversion.
+    pub fn rgb_space_coordinates(&self) -> Option<[Float; 3]> {
+        self.rgb_space
+            .as_ref()
+            .map(|space| self.to_rgb_coordinates(space))
+    }
+
+    /// Determine the CAM16 correlates of this color under the given viewing
+    /// conditions.
+    ///
+    /// This method normalizes the color before conversion. See
+    /// [`ViewingConditions`] for an example.
+    pub fn to_cam16(&self, conditions: &ViewingConditions) -> Cam16 {
+        conditions.to_cam16(self.to(ColorSpace::Xyz).coordinates)
+    }
+
+    /// Compute the color difference between the two colors.
+    ///
+    /// This method computes the difference with the given metric. For
+    /// [`DeltaE::Ok`], that is *Delta E OK*, the Euclidian distance in the
+    /// Oklab color space, using either original or revised version.
     ///
     /// # Examples
     ///
@@ -532,15 +1153,20 @@ impl Color {
     /// with lightness L(honeydew) = 0.94 and L(cantaloupe) = 0.87. Since the
     /// revised lightness Lr corrects the original's dark bias, we'd expect
     /// light colors to be more spread out in Oklrab. That is indeed the case.
+    /// CIEDE2000, which is scaled so that a difference of 1.0 roughly
+    /// corresponds to a just noticeable difference, deems them fairly distinct.
     /// ```
-    /// # use prettypretty::{Color, ColorSpace, OkVersion, ColorFormatError};
+    /// # use prettypretty::{Color, ColorSpace, DeltaE, OkVersion, ColorFormatError};
     /// # use std::str::FromStr;
     /// let honeydew = Color::from_str("#d4fb79")?;
     /// let cantaloupe = Color::from_str("#ffd479")?;
-    /// let d1 = honeydew.distance(&cantaloupe, OkVersion::Original);
-    /// let d2 = honeydew.distance(&cantaloupe, OkVersion::Revised);
+    /// let d1 = honeydew.distance(&cantaloupe, DeltaE::Ok(OkVersion::Original));
+    /// let d2 = honeydew.distance(&cantaloupe, DeltaE::Ok(OkVersion::Revised));
     /// assert!((d1 - 0.11174969799958659).abs() < f64::EPSILON);
     /// assert!((d2 - 0.11498895250174994).abs() < f64::EPSILON);
+    ///
+    /// let d3 = honeydew.distance(&cantaloupe, DeltaE::Ciede2000());
+    /// assert!((d3 - 21.5918).abs() < 1e-4);
     /// # Ok::<(), ColorFormatError>(())
     /// ```
     /// <div class=color-swatch>
@@ -548,31 +1174,87 @@ impl Color {
     /// <div style="background-color: #ffd479;"></div>
     /// </div>
     #[inline]
-    pub fn distance(&self, other: &Self, version: OkVersion) -> f64 {
-        delta_e_ok(
-            &self.to(version.cartesian_space()).coordinates,
-            &other.to(version.cartesian_space()).coordinates,
-        )
+    pub fn distance(&self, other: &Self, metric: DeltaE) -> f64 {
+        let space = metric.space();
+        metric.compute(&self.to(space).coordinates, &other.to(space).coordinates)
     }
 
-    /// Interpolate the two colors.
+    /// Compute the color difference ΔE ITP between the two colors.
     ///
-    /// This method creates a new interpolator for this and the given color.
-    /// [`Interpolator::at`] generates the actual, interpolated colors. It
-    /// normalizes both colors.
+    /// This method computes the color difference defined by ITU-R BT.2124,
+    /// which is based on the ICtCp color space and designed for HDR content.
+    /// It is scaled so that a difference of 1.0 roughly corresponds to a just
+    /// noticeable difference. This method is equivalent to
+    /// [`Color::distance`] with [`DeltaE::Itp`].
     ///
     /// # Examples
     ///
-    /// As illustrated below, [`Color::interpolate`] takes care of the mechanics
-    /// of interpolation. However, the resulting color may not be displayable
-    /// and hence require further processing, such as gamut mapping.
-    ///
     /// ```
-    /// # use prettypretty::{Color, ColorSpace, HueInterpolation};
-    /// let red = Color::srgb(0.8, 0.0, 0.0);
-    /// let yellow = Color::from_24bit(0xff, 0xca, 0);
-    /// let orange = red
-    ///     .interpolate(&yellow, ColorSpace::Oklch, HueInterpolation::Shorter)
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let honeydew = Color::from_str("#d4fb79")?;
+    /// let cantaloupe = Color::from_str("#ffd479")?;
+    /// let d = honeydew.delta_e_itp(&cantaloupe);
+    /// assert!((d - 39.97996413541971).abs() < 1e-9);
+    /// assert_eq!(honeydew.delta_e_itp(&honeydew), 0.0);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #d4fb79;"></div>
+    /// <div style="background-color: #ffd479;"></div>
+    /// </div>
+    #[inline]
+    pub fn delta_e_itp(&self, other: &Self) -> f64 {
+        self.distance(other, DeltaE::Itp())
+    }
+
+    /// Compute the color difference ΔEz between the two colors.
+    ///
+    /// This method computes the color difference defined alongside the Jzazbz
+    /// color space, using its polar form JzCzhz.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// # use std::str::FromStr;
+    /// let honeydew = Color::from_str("#d4fb79")?;
+    /// let cantaloupe = Color::from_str("#ffd479")?;
+    /// let d = honeydew.delta_e_z(&cantaloupe);
+    /// assert!((d - 0.046931281435425236).abs() < 1e-9);
+    /// assert_eq!(honeydew.delta_e_z(&honeydew), 0.0);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #d4fb79;"></div>
+    /// <div style="background-color: #ffd479;"></div>
+    /// </div>
+    #[inline]
+    pub fn delta_e_z(&self, other: &Self) -> f64 {
+        delta_e_z(
+            &self.to(ColorSpace::Jzczhz).coordinates,
+            &other.to(ColorSpace::Jzczhz).coordinates,
+        )
+    }
+
+    /// Interpolate the two colors.
+    ///
+    /// This method creates a new interpolator for this and the given color.
+    /// [`Interpolator::at`] generates the actual, interpolated colors. It
+    /// normalizes both colors.
+    ///
+    /// # Examples
+    ///
+    /// As illustrated below, [`Color::interpolate`] takes care of the mechanics
+    /// of interpolation. However, the resulting color may not be displayable
+    /// and hence require further processing, such as gamut mapping.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, HueInterpolation};
+    /// let red = Color::srgb(0.8, 0.0, 0.0);
+    /// let yellow = Color::from_24bit(0xff, 0xca, 0);
+    /// let orange = red
+    ///     .interpolate(&yellow, ColorSpace::Oklch, HueInterpolation::Shorter)
     ///     .at(0.5);
     /// assert_eq!(orange, Color::oklch(0.6960475282872609, 0.196904718808239, 59.33737836604695));
     /// assert!(!orange.to(ColorSpace::Rec2020).in_gamut());
@@ -654,6 +1336,76 @@ impl Color {
         Interpolator::new(self, color, interpolation_space, interpolation_strategy)
     }
 
+    /// Mix this color with the other color.
+    ///
+    /// This method implements the semantics of CSS Color 5's
+    /// [`color-mix()`](https://www.w3.org/TR/css-color-5/#color-mix). The
+    /// weights are fractions between zero and one, corresponding to the
+    /// percentages of `color-mix()`. If both weights are missing, they default
+    /// to one half. If one is missing, it defaults to one minus the other. If
+    /// the weights do not add up to one, they are normalized. If their sum is
+    /// less than one, it also becomes the multiplier for the mixed color's
+    /// alpha. This method returns `None` if a weight is out of range or both
+    /// weights are zero. Otherwise, it returns the mixed color in the
+    /// interpolation color space.
+    ///
+    /// # Examples
+    ///
+    /// The example code mixes 30% red with 70% blue along the longer hue arc,
+    /// i.e., `color-mix(in oklch longer hue, red 30%, blue)`. It then mixes
+    /// 20% black with 20% white, which results in gray with 40% alpha.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, HueInterpolation};
+    /// # use std::str::FromStr;
+    /// let red = Color::from_str("red")?;
+    /// let blue = Color::from_str("blue")?;
+    /// let mix = red
+    ///     .mix(Some(0.3), &blue, None, ColorSpace::Oklch, HueInterpolation::Longer)
+    ///     .unwrap();
+    /// assert_eq!(mix, Color::from_str("color-mix(in oklch longer hue, red 30%, blue)")?);
+    ///
+    /// let black = Color::srgb(0, 0, 0);
+    /// let white = Color::srgb(1, 1, 1);
+    /// let gray = black
+    ///     .mix(Some(0.2), &white, Some(0.2), ColorSpace::Srgb, HueInterpolation::Shorter)
+    ///     .unwrap();
+    /// assert_eq!(gray, Color::srgb(0.5, 0.5, 0.5).with_alpha(0.4));
+    ///
+    /// assert_eq!(
+    ///     black.mix(Some(0.0), &white, Some(0.0), ColorSpace::Srgb, HueInterpolation::Shorter),
+    ///     None
+    /// );
+    /// # Ok::<(), prettypretty::ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: red;"></div>
+    /// <div style="background-color: color-mix(in oklch longer hue, red 30%, blue);"></div>
+    /// <div style="background-color: blue;"></div>
+    /// <div style="background-color: color(srgb 0.5 0.5 0.5 / 0.4);"></div>
+    /// </div>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(signature = (weight, other, other_weight, interpolation_space, interpolation_strategy))]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn mix(
+        &self,
+        weight: Option<Float>,
+        other: &Self,
+        other_weight: Option<Float>,
+        interpolation_space: ColorSpace,
+        interpolation_strategy: HueInterpolation,
+    ) -> Option<Self> {
+        let (coordinates, alpha) = mix(
+            (self.space, &self.coordinates, self.alpha),
+            weight,
+            (other.space, &other.coordinates, other.alpha),
+            other_weight,
+            interpolation_space,
+            interpolation_strategy,
+        )?;
+        Some(Self::new(interpolation_space, coordinates).with_alpha(alpha))
+    }
+
     /// Lighten this color by the given factor in Oklrch.
     ///
     /// This method normalizes this color, converts it to Oklrch, multiplies its
@@ -690,6 +1442,7 @@ impl Color {
             ColorSpace::Oklrch,
             scale_lightness(self.space, &self.coordinates, factor),
         )
+        .with_alpha(self.alpha)
     }
 
     /// Darken this color by the given factor.
@@ -703,6 +1456,293 @@ impl Color {
             ColorSpace::Oklrch,
             scale_lightness(self.space, &self.coordinates, factor.recip()),
         )
+        .with_alpha(self.alpha)
+    }
+
+    /// Adjust this color.
+    ///
+    /// This method applies the adjustment in Oklrch, as described for
+    /// [`Adjustment`]. Without a target color space, it returns the Oklrch
+    /// color, which may be out of gamut for the color spaces of interest.
+    /// With a target color space, it converts the result to that color space
+    /// and gamut-maps it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Adjustment, Color, ColorSpace};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let srgb = Some(ColorSpace::Srgb);
+    ///
+    /// let purple = teal.adjust(Adjustment::RotateHue { degrees: 120.0 }, srgb);
+    /// assert_eq!(purple.to_hex_format(), "#845e95");
+    ///
+    /// let muted = teal.adjust(Adjustment::Desaturate { amount: 0.05 }, srgb);
+    /// assert_eq!(muted.to_hex_format(), "#527877");
+    ///
+    /// let gray = teal.adjust(Adjustment::Grayscale(), srgb);
+    /// assert_eq!(gray.to_hex_format(), "#6f6f6f");
+    ///
+    /// let inverted = teal.adjust(Adjustment::Invert(), None);
+    /// assert_eq!(inverted.space(), ColorSpace::Oklrch);
+    /// assert_eq!(inverted.to(ColorSpace::Srgb).to_gamut().to_hex_format(), "#23908f");
+    ///
+    /// let tint = teal.adjust(Adjustment::Tint { fraction: 0.5 }, srgb);
+    /// assert_eq!(tint.to_hex_format(), "#93bfbe");
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #008080;"></div>
+    /// <div style="background-color: #845e95;"></div>
+    /// <div style="background-color: #527877;"></div>
+    /// <div style="background-color: #6f6f6f;"></div>
+    /// <div style="background-color: #23908f;"></div>
+    /// <div style="background-color: #93bfbe;"></div>
+    /// </div>
+    #[cfg(feature = "pyffi")]
+    #[pyo3(signature = (adjustment, target=None))]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn adjust(&self, adjustment: Adjustment, target: Option<ColorSpace>) -> Self {
+        let color = Self::new(
+            ColorSpace::Oklrch,
+            adjustment.apply(self.space, &self.coordinates),
+        )
+        .with_alpha(self.alpha);
+
+        match target {
+            Some(space) => color.to(space).to_gamut(),
+            None => color,
+        }
+    }
+
+    /// Determine the colors forming the harmony with this color.
+    ///
+    /// This method rotates the hue of this color in Oklrch, leaving lightness
+    /// and chroma unchanged, and gamut-maps the results into the target color
+    /// space. The first color is this color, also gamut-mapped into the target
+    /// color space, followed by the colors added by the harmony in the order
+    /// described for [`Harmony`]. Since achromatic colors have no hue, all
+    /// colors are the same for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, Harmony};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let colors = teal.harmony(Harmony::Triadic, ColorSpace::Srgb);
+    /// let hex: Vec<_> = colors.iter().map(|c| c.to_hex_format()).collect();
+    /// assert_eq!(hex, ["#008080", "#845e95", "#8f672a"]);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #008080;"></div>
+    /// <div style="background-color: #845e95;"></div>
+    /// <div style="background-color: #8f672a;"></div>
+    /// </div>
+    pub fn harmony(&self, harmony: Harmony, target: ColorSpace) -> Vec<Self> {
+        let [lr, c, h] = *self.to(ColorSpace::Oklrch).as_ref();
+
+        std::iter::once(0.0)
+            .chain(harmony.rotations().iter().copied())
+            .map(|rotation| {
+                Self::new(
+                    ColorSpace::Oklrch,
+                    [lr, c, (h + rotation).rem_euclid(360.0)],
+                )
+                .to(target)
+                .to_gamut()
+                .with_alpha(self.alpha)
+            })
+            .collect()
+    }
+
+    /// Determine the given number of tints of this color.
+    ///
+    /// Tints are mixtures with white. This method interpolates between this
+    /// color and white in Oklrab, which preserves Oklrch hue and yields
+    /// perceptually even steps. It returns the tints in order of increasing
+    /// lightness, excluding this color and white, gamut-mapped into the
+    /// target color space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let tints = teal.tints(3, ColorSpace::Srgb);
+    /// let hex: Vec<_> = tints.iter().map(|c| c.to_hex_format()).collect();
+    /// assert_eq!(hex, ["#5b9f9f", "#93bfbe", "#c9dfde"]);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #008080;"></div>
+    /// <div style="background-color: #5b9f9f;"></div>
+    /// <div style="background-color: #93bfbe;"></div>
+    /// <div style="background-color: #c9dfde;"></div>
+    /// </div>
+    pub fn tints(&self, count: usize, target: ColorSpace) -> Vec<Self> {
+        self.ramp([1.0, 0.0, 0.0], count, target)
+    }
+
+    /// Determine the given number of shades of this color.
+    ///
+    /// Shades are mixtures with black. Other than that, this method works just
+    /// like [`Color::tints`]. It returns the shades in order of decreasing
+    /// lightness, excluding this color and black, gamut-mapped into the target
+    /// color space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let shades = teal.shades(3, ColorSpace::Srgb);
+    /// let hex: Vec<_> = shades.iter().map(|c| c.to_hex_format()).collect();
+    /// assert_eq!(hex, ["#125e5e", "#133e3e", "#0d1e1e"]);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #008080;"></div>
+    /// <div style="background-color: #125e5e;"></div>
+    /// <div style="background-color: #133e3e;"></div>
+    /// <div style="background-color: #0d1e1e;"></div>
+    /// </div>
+    pub fn shades(&self, count: usize, target: ColorSpace) -> Vec<Self> {
+        self.ramp([0.0, 0.0, 0.0], count, target)
+    }
+
+    /// Determine the design-token scale for this color.
+    ///
+    /// This method returns ten colors for the steps 50, 100, 200, ..., 900 of
+    /// a design-token scale, i.e., [`Color::SCALE`]. The colors have this
+    /// color's Oklrch hue as well as the step's lightness, which decreases
+    /// from 0.97 for step 50 to 0.28 for step 900. Their chroma is this
+    /// color's chroma scaled by the step's relative chroma. They are
+    /// gamut-mapped into the target color space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let scale = teal.scale(ColorSpace::Srgb);
+    /// assert_eq!(scale.len(), 10);
+    /// assert_eq!(scale[0].to_hex_format(), "#f0f9f8");
+    /// assert_eq!(scale[5].to_hex_format(), "#40a5a5");
+    /// assert_eq!(scale[9].to_hex_format(), "#004c4c");
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #f0f9f8;"></div>
+    /// <div style="background-color: #40a5a5;"></div>
+    /// <div style="background-color: #004c4c;"></div>
+    /// </div>
+    pub fn scale(&self, target: ColorSpace) -> Vec<Self> {
+        let [_, c, h] = *self.to(ColorSpace::Oklrch).as_ref();
+
+        Self::SCALE
+            .iter()
+            .map(|(_, lr, relative_chroma)| {
+                Self::new(ColorSpace::Oklrch, [*lr, c * relative_chroma, h])
+                    .to(target)
+                    .to_gamut()
+                    .with_alpha(self.alpha)
+            })
+            .collect()
+    }
+
+    /// Simulate how a person with the color vision deficiency perceives this
+    /// color.
+    ///
+    /// The severity ranges from 0, i.e., normal color vision, to 1, i.e.,
+    /// dichromacy, and is clamped to that range. This method gamut-maps this
+    /// color to sRGB before simulating the deficiency and returns an in-gamut
+    /// sRGB color. See [`CvdMethod`] for a description of the methods.
+    ///
+    /// # Examples
+    ///
+    /// For a person with deuteranopia, red and green look very much alike:
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorVisionDeficiency, CvdMethod, DeltaE, OkVersion};
+    /// let red = Color::srgb(0.8, 0.1, 0.1);
+    /// let green = Color::srgb(0.3, 0.6, 0.1);
+    /// let metric = DeltaE::Ok(OkVersion::Revised);
+    /// let distance = red.distance(&green, metric);
+    ///
+    /// let red2 = red.simulate_cvd(ColorVisionDeficiency::Deuteranopia, CvdMethod::Machado, 1.0);
+    /// let green2 = green.simulate_cvd(ColorVisionDeficiency::Deuteranopia, CvdMethod::Machado, 1.0);
+    /// assert_eq!(red2.to_hex_format(), "#83750a");
+    /// assert_eq!(green2.to_hex_format(), "#958529");
+    /// assert!(red2.distance(&green2, metric) < distance / 3.0);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 0.8 0.1 0.1);"></div>
+    /// <div style="background-color: color(srgb 0.3 0.6 0.1);"></div>
+    /// <div style="background-color: #83750a;"></div>
+    /// <div style="background-color: #958529;"></div>
+    /// </div>
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn simulate_cvd(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Self {
+        Self::new(
+            ColorSpace::Srgb,
+            simulate_cvd(deficiency, method, severity, self.space, &self.coordinates),
+        )
+        .with_alpha(self.alpha)
+    }
+
+    /// Daltonize this color for a person with the color vision deficiency.
+    ///
+    /// Daltonization shifts the color information lost to the deficiency into
+    /// channels that are still perceived, which often restores some of the
+    /// contrast between colors. The severity and method determine the
+    /// simulation used for computing the lost information, with the same
+    /// meaning as for [`Color::simulate_cvd`]. This method returns an in-gamut
+    /// sRGB color.
+    ///
+    /// # Examples
+    ///
+    /// Daltonizing red and green makes them more distinguishable again for a
+    /// person with deuteranopia:
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorVisionDeficiency, CvdMethod, DeltaE, OkVersion};
+    /// let deficiency = ColorVisionDeficiency::Deuteranopia;
+    /// let method = CvdMethod::Machado;
+    /// let metric = DeltaE::Ok(OkVersion::Revised);
+    /// let red = Color::srgb(0.8, 0.1, 0.1);
+    /// let green = Color::srgb(0.3, 0.6, 0.1);
+    /// let before = red.simulate_cvd(deficiency, method, 1.0)
+    ///     .distance(&green.simulate_cvd(deficiency, method, 1.0), metric);
+    ///
+    /// let red2 = red.daltonize(deficiency, method, 1.0);
+    /// let green2 = green.daltonize(deficiency, method, 1.0);
+    /// assert_eq!(red2.to_hex_format(), "#cc5c90");
+    /// assert_eq!(green2.to_hex_format(), "#4d8700");
+    /// let after = red2.simulate_cvd(deficiency, method, 1.0)
+    ///     .distance(&green2.simulate_cvd(deficiency, method, 1.0), metric);
+    /// assert!(after > before);
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 0.8 0.1 0.1);"></div>
+    /// <div style="background-color: color(srgb 0.3 0.6 0.1);"></div>
+    /// <div style="background-color: #cc5c90;"></div>
+    /// <div style="background-color: #4d8700;"></div>
+    /// </div>
+    #[inline]
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn daltonize(
+        &self,
+        deficiency: ColorVisionDeficiency,
+        method: CvdMethod,
+        severity: Float,
+    ) -> Self {
+        Self::new(
+            ColorSpace::Srgb,
+            daltonize(deficiency, method, severity, self.space, &self.coordinates),
+        )
+        .with_alpha(self.alpha)
     }
 
     /// Determine the perceptual contrast of text against a solidly colored
@@ -713,7 +1753,24 @@ impl Color {
     /// algorithm that is surprisingly similar to the [Accessible Perceptual
     /// Contrast Algorithm](https://github.com/Myndex/apca-w3), version
     /// 0.0.98G-4g. This method normalizes both colors.
+    ///
+    /// If either color has a user-defined RGB color space, this method
+    /// computes contrast luminance for gamut-mapped coordinates in that color
+    /// space, preferring the text's color space if both colors have one.
+    ///
+    /// Since APCA's luminance-based model has no parameters for the viewing
+    /// conditions, this method does not accept [`ViewingConditions`] and
+    /// instead assumes APCA's reference conditions. For matching colors under
+    /// a dark surround, see
+    /// [`Sampler::with_conditions`](crate::Sampler::with_conditions).
     pub fn contrast_against(&self, background: &Self) -> f64 {
+        if let Some(space) = self.rgb_space.as_ref().or(background.rgb_space.as_ref()) {
+            return to_contrast(
+                self.to_rgb_space_contrast_luminance(space),
+                background.to_rgb_space_contrast_luminance(space),
+            );
+        }
+
         let fg = self.to(ColorSpace::Srgb);
         let bg = background.to(ColorSpace::Srgb);
 
@@ -756,7 +1813,9 @@ impl Color {
     /// </div>
     pub fn use_black_text(&self) -> bool {
         let background = self.to(ColorSpace::Srgb);
-        let luminance = if background.in_gamut() {
+        let luminance = if let Some(space) = &self.rgb_space {
+            self.to_rgb_space_contrast_luminance(space)
+        } else if background.in_gamut() {
             to_contrast_luminance_srgb(&background.coordinates)
         } else {
             to_contrast_luminance_p3(&self.to(ColorSpace::DisplayP3).coordinates)
@@ -787,7 +1846,9 @@ impl Color {
     /// </div>
     pub fn use_black_background(&self) -> bool {
         let text = self.to(ColorSpace::Srgb);
-        let luminance = if text.in_gamut() {
+        let luminance = if let Some(space) = &self.rgb_space {
+            self.to_rgb_space_contrast_luminance(space)
+        } else if text.in_gamut() {
             to_contrast_luminance_srgb(&text.coordinates)
         } else {
             to_contrast_luminance_p3(&self.to(ColorSpace::DisplayP3).coordinates)
@@ -818,6 +1879,35 @@ impl Color {
         format!("{}", self)
     }
 
+    /// Format this color according to the format spec. <span
+    /// class=python-only></span>
+    ///
+    /// The format spec may be empty for CSS, `#` for legacy `rgb()`, `x` or
+    /// `X` for hashed hexadecimal, `#x` or `#X` for the X Windows format, or a
+    /// precision such as `.3` for CSS. This method is available from Python
+    /// only.
+    #[cfg(feature = "pyffi")]
+    pub fn __format__(&self, spec: &str) -> PyResult<String> {
+        match spec {
+            "" => Ok(format!("{}", self)),
+            "#" => Ok(format!("{:#}", self)),
+            "x" => Ok(format!("{:x}", self)),
+            "X" => Ok(format!("{:X}", self)),
+            "#x" => Ok(format!("{:#x}", self)),
+            "#X" => Ok(format!("{:#X}", self)),
+            _ => spec
+                .strip_prefix('.')
+                .and_then(|p| p.parse::<usize>().ok())
+                .map(|p| format!("{:.*}", p, self))
+                .ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "invalid format spec \"{}\" for color",
+                        spec
+                    ))
+                }),
+        }
+    }
+
     /// Get this color's length, which is 3. <span class=python-only></span>
     ///
     /// This method is available from Python only.
@@ -965,18 +2055,292 @@ impl Color {
     pub fn oklrch(lr: impl Into<Float>, c: impl Into<Float>, h: impl Into<Float>) -> Self {
         Self::new(ColorSpace::Oklrch, [lr.into(), c.into(), h.into()])
     }
+
+    /// Instantiate a new HCT color with the given hue h, chroma C, and tone T
+    /// coordinates.
+    ///
+    /// # Examples
+    ///
+    /// Not all combinations of hue, chroma, and tone are in gamut for sRGB.
+    /// [`Color::to_gamut_hct`] reduces chroma while preserving hue and tone.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace};
+    /// let too_blue = Color::hct(282.8, 120, 32.3).to(ColorSpace::Srgb);
+    /// assert!(!too_blue.in_gamut());
+    ///
+    /// let blue = too_blue.to_gamut_hct();
+    /// assert!(blue.in_gamut());
+    /// let [_, _, tone] = *blue.to(ColorSpace::Hct).as_ref();
+    /// assert!((tone - 32.3).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn hct(h: impl Into<Float>, c: impl Into<Float>, t: impl Into<Float>) -> Self {
+        Self::new(ColorSpace::Hct, [h.into(), c.into(), t.into()])
+    }
+
+    /// Mix this color with the other color.
+    ///
+    /// This method implements the semantics of CSS Color 5's
+    /// [`color-mix()`](https://www.w3.org/TR/css-color-5/#color-mix). The
+    /// weights are fractions between zero and one, corresponding to the
+    /// percentages of `color-mix()`. If both weights are missing, they default
+    /// to one half. If one is missing, it defaults to one minus the other. If
+    /// the weights do not add up to one, they are normalized. If their sum is
+    /// less than one, it also becomes the multiplier for the mixed color's
+    /// alpha. This method returns `None` if a weight is out of range or both
+    /// weights are zero. Otherwise, it returns the mixed color in the
+    /// interpolation color space.
+    ///
+    /// # Examples
+    ///
+    /// The example code mixes 30% red with 70% blue along the longer hue arc,
+    /// i.e., `color-mix(in oklch longer hue, red 30%, blue)`. It then mixes
+    /// 20% black with 20% white, which results in gray with 40% alpha.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, HueInterpolation};
+    /// # use std::str::FromStr;
+    /// let red = Color::from_str("red")?;
+    /// let blue = Color::from_str("blue")?;
+    /// let mix = red
+    ///     .mix(Some(0.3), &blue, None, ColorSpace::Oklch, HueInterpolation::Longer)
+    ///     .unwrap();
+    /// assert_eq!(mix, Color::from_str("color-mix(in oklch longer hue, red 30%, blue)")?);
+    ///
+    /// let black = Color::srgb(0, 0, 0);
+    /// let white = Color::srgb(1, 1, 1);
+    /// let gray = black
+    ///     .mix(Some(0.2), &white, Some(0.2), ColorSpace::Srgb, HueInterpolation::Shorter)
+    ///     .unwrap();
+    /// assert_eq!(gray, Color::srgb(0.5, 0.5, 0.5).with_alpha(0.4));
+    ///
+    /// assert_eq!(
+    ///     black.mix(Some(0.0), &white, Some(0.0), ColorSpace::Srgb, HueInterpolation::Shorter),
+    ///     None
+    /// );
+    /// # Ok::<(), prettypretty::ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: red;"></div>
+    /// <div style="background-color: color-mix(in oklch longer hue, red 30%, blue);"></div>
+    /// <div style="background-color: blue;"></div>
+    /// <div style="background-color: color(srgb 0.5 0.5 0.5 / 0.4);"></div>
+    /// </div>
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn mix(
+        &self,
+        weight: Option<Float>,
+        other: &Self,
+        other_weight: Option<Float>,
+        interpolation_space: ColorSpace,
+        interpolation_strategy: HueInterpolation,
+    ) -> Option<Self> {
+        let (coordinates, alpha) = mix(
+            (self.space, &self.coordinates, self.alpha),
+            weight,
+            (other.space, &other.coordinates, other.alpha),
+            other_weight,
+            interpolation_space,
+            interpolation_strategy,
+        )?;
+        Some(Self::new(interpolation_space, coordinates).with_alpha(alpha))
+    }
+
+    /// Adjust this color.
+    ///
+    /// This method applies the adjustment in Oklrch, as described for
+    /// [`Adjustment`]. Without a target color space, it returns the Oklrch
+    /// color, which may be out of gamut for the color spaces of interest.
+    /// With a target color space, it converts the result to that color space
+    /// and gamut-maps it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Adjustment, Color, ColorSpace};
+    /// let teal = Color::from_24bit(0x00, 0x80, 0x80);
+    /// let srgb = Some(ColorSpace::Srgb);
+    ///
+    /// let purple = teal.adjust(Adjustment::RotateHue { degrees: 120.0 }, srgb);
+    /// assert_eq!(purple.to_hex_format(), "#845e95");
+    ///
+    /// let muted = teal.adjust(Adjustment::Desaturate { amount: 0.05 }, srgb);
+    /// assert_eq!(muted.to_hex_format(), "#527877");
+    ///
+    /// let gray = teal.adjust(Adjustment::Grayscale(), srgb);
+    /// assert_eq!(gray.to_hex_format(), "#6f6f6f");
+    ///
+    /// let inverted = teal.adjust(Adjustment::Invert(), None);
+    /// assert_eq!(inverted.space(), ColorSpace::Oklrch);
+    /// assert_eq!(inverted.to(ColorSpace::Srgb).to_gamut().to_hex_format(), "#23908f");
+    ///
+    /// let tint = teal.adjust(Adjustment::Tint { fraction: 0.5 }, srgb);
+    /// assert_eq!(tint.to_hex_format(), "#93bfbe");
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #008080;"></div>
+    /// <div style="background-color: #845e95;"></div>
+    /// <div style="background-color: #527877;"></div>
+    /// <div style="background-color: #6f6f6f;"></div>
+    /// <div style="background-color: #23908f;"></div>
+    /// <div style="background-color: #93bfbe;"></div>
+    /// </div>
+    #[must_use = "method returns a new color and does not mutate original value"]
+    pub fn adjust(&self, adjustment: Adjustment, target: Option<ColorSpace>) -> Self {
+        let color = Self::new(
+            ColorSpace::Oklrch,
+            adjustment.apply(self.space, &self.coordinates),
+        )
+        .with_alpha(self.alpha);
+
+        match target {
+            Some(space) => color.to(space).to_gamut(),
+            None => color,
+        }
+    }
 }
 
 // Use separate block, so that methods are not exposed to Python.
 // Do not use cfg(), so that methods are documented.
 impl Color {
+    /// Create a new, opaque color from the coordinates in the user-defined RGB
+    /// color space.
+    fn in_rgb_space(space: Arc<RgbSpace>, coordinates: [Float; 3]) -> Self {
+        Self {
+            space: ColorSpace::Xyz,
+            coordinates: space.to_xyz(coordinates),
+            alpha: 1.0,
+            rgb_space: Some(space),
+        }
+    }
+
+    /// Create a new color with the same color space, user-defined RGB color
+    /// space, and alpha as this color but the given coordinates.
+    fn with_coordinates(&self, coordinates: [Float; 3]) -> Self {
+        Self {
+            space: self.space,
+            coordinates,
+            alpha: self.alpha,
+            rgb_space: self.rgb_space.clone(),
+        }
+    }
+
+    /// Create a new color with the same user-defined RGB color space and
+    /// alpha as this color but the given coordinates in that color space.
+    ///
+    /// This method must only be invoked on colors with a user-defined RGB
+    /// color space.
+    fn with_rgb_space_coordinates(&self, coordinates: [Float; 3]) -> Self {
+        let space = self.rgb_space.as_ref().expect("color has RGB color space");
+        self.with_coordinates(space.to_xyz(coordinates))
+    }
+
+    /// Convert this color to normalized coordinates in the user-defined RGB
+    /// color space.
+    fn to_rgb_coordinates(&self, space: &RgbSpace) -> [Float; 3] {
+        let xyz = convert(
+            self.space,
+            ColorSpace::Xyz,
+            &normalize(self.space, &self.coordinates),
+        );
+        space.from_xyz(xyz)
+    }
+
+    /// Determine the contrast luminance of this color after gamut-mapping it
+    /// in the user-defined RGB color space.
+    fn to_rgb_space_contrast_luminance(&self, space: &RgbSpace) -> Float {
+        space.to_contrast_luminance(space.to_gamut(self.to_rgb_coordinates(space)))
+    }
+    /// Parse a color from its string representation while resolving
+    /// references. <span class=rust-only></span>
+    ///
+    /// This method accepts the same string representations as [`Color as
+    /// FromStr`](struct.Color.html#impl-FromStr-for-Color). But it also
+    /// invokes the resolver on strings that are neither hashed, X Windows, nor
+    /// named colors. That includes the origin colors of relative colors and
+    /// the colors of `color-mix()`, which makes it possible to define colors
+    /// in terms of other colors. The resolver receives the trimmed, lowercase
+    /// reference. [`Theme::parse`](crate::Theme::parse) uses this method to
+    /// resolve `ansi.*` references to its entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError};
+    /// let brand = Color::from_24bit(0xc0, 0x30, 0x60);
+    /// let resolve = |s: &str| (s == "brand").then(|| brand.clone());
+    ///
+    /// let muted = Color::parse_with(
+    ///     "oklch(from brand l calc(c * 0.5) h)", resolve)?;
+    /// assert_eq!(muted.to_hex_format(), "#9c5868");
+    ///
+    /// let accent = Color::parse_with(
+    ///     "oklch(from Brand l c calc(h + 180))", resolve)?;
+    /// assert_eq!(accent.to_hex_format(), "#00857a");
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: #c03060;"></div>
+    /// <div style="background-color: #9c5868;"></div>
+    /// <div style="background-color: #00857a;"></div>
+    /// </div>
+    pub fn parse_with<F>(s: &str, resolve: F) -> Result<Self, ColorFormatError>
+    where
+        F: Fn(&str) -> Option<Color>,
+    {
+        parse_with(s, &|reference| {
+            resolve(reference).map(|color| (color.space, color.coordinates, color.alpha))
+        })
+        .map(|(space, coordinates, alpha)| Self::new(space, coordinates).with_alpha(alpha))
+    }
+
+    /// The steps, Oklrch lightness, and relative chroma of design-token
+    /// scales. <span class=rust-only></span>
+    ///
+    /// [`Color::scale`] uses these values for the steps 50, 100, 200, ..., 900.
+    /// The relative chroma tapers off towards the lightest and darkest steps,
+    /// which keeps light backgrounds and dark text from looking garish.
+    pub const SCALE: [(u16, Float, Float); 10] = [
+        (50, 0.97, 0.1),
+        (100, 0.93, 0.25),
+        (200, 0.87, 0.45),
+        (300, 0.79, 0.7),
+        (400, 0.7, 0.9),
+        (500, 0.61, 1.0),
+        (600, 0.52, 1.0),
+        (700, 0.44, 0.95),
+        (800, 0.36, 0.85),
+        (900, 0.28, 0.75),
+    ];
+
+    /// Interpolate in Oklrab between this color and the given Oklrab end
+    /// coordinates, excluding both end points.
+    fn ramp(&self, end: [Float; 3], count: usize, target: ColorSpace) -> Vec<Self> {
+        let [l1, a1, b1] = *self.to(ColorSpace::Oklrab).as_ref();
+        let [l2, a2, b2] = end;
+
+        (1..=count)
+            .map(|step| {
+                let t = step as Float / (count + 1) as Float;
+                Self::new(
+                    ColorSpace::Oklrab,
+                    [l1 + (l2 - l1) * t, a1 + (a2 - a1) * t, b1 + (b2 - b1) * t],
+                )
+                .to(target)
+                .to_gamut()
+                .with_alpha(self.alpha)
+            })
+            .collect()
+    }
+
     /// Find the index position of the candidate color closest to this color.
     /// <span class=rust-only></span>
     ///
-    /// This method delegates to [`Color::find_closest`] using the Delta E
-    /// metric for Oklab/Oklrab, which is the Euclidian distance.
+    /// This method delegates to [`Color::find_closest`] using the given color
+    /// difference metric. Despite its name, the metric need not be ΔE OK.
     ///
-    /// Since this method converts every color to either Oklab or Oklrab, it
+    /// Since this method converts every color to the metric's color space, it
     /// also normalizes every color before use.
     ///
     /// Because it is generic, this method is available in Rust only. A
@@ -986,18 +2350,18 @@ impl Color {
     /// # Examples
     ///
     /// ```
-    /// # use prettypretty::{Color, ColorSpace, OkVersion};
+    /// # use prettypretty::{Color, ColorSpace, DeltaE, OkVersion};
     /// let colors = [
     ///     &Color::from_24bit(0xc4, 0x13, 0x31),
     ///     &Color::from_24bit(0, 0x80, 0x25),
     ///     &Color::from_24bit(0x30, 0x78, 0xea),
     /// ];
     /// let rose = Color::srgb(1, 0.5, 0.5);
-    /// let closest = rose.find_closest_ok(colors, OkVersion::Revised);
+    /// let closest = rose.find_closest_ok(colors, OkVersion::Revised.into());
     /// assert_eq!(closest, Some(0));
     ///
     /// let green = Color::srgb(0.5, 1, 0.6);
-    /// let closest = green.find_closest_ok(colors, OkVersion::Revised);
+    /// let closest = green.find_closest_ok(colors, DeltaE::HyAb());
     /// assert_eq!(closest, Some(1))
     /// ```
     /// <div class=color-swatch>
@@ -1008,11 +2372,11 @@ impl Color {
     /// <div style="background-color: color(srgb 0.5 1 0.6);"></div>
     /// </div>
     #[inline]
-    pub fn find_closest_ok<'c, C>(&self, candidates: C, version: OkVersion) -> Option<usize>
+    pub fn find_closest_ok<'c, C>(&self, candidates: C, metric: DeltaE) -> Option<usize>
     where
         C: IntoIterator<Item = &'c Self>,
     {
-        self.find_closest(candidates, version.cartesian_space(), delta_e_ok)
+        self.find_closest(candidates, metric.space(), |c1, c2| metric.compute(c1, c2))
     }
 
     /// Find the index position of the candidate color closest to this color.
@@ -1097,22 +2461,62 @@ impl std::str::FromStr for Color {
     /// The *hashed notation* has three or six hexadecimal digits, e.g., `#123` or
     /// #`cafe00`. Note that the three digit version is a short form of the six
     /// digit version with every digit repeated. In other words, the red
-    /// coordinate in `#123` is not 0x1/0xf but 0x11/0xff.
+    /// coordinate in `#123` is not 0x1/0xf but 0x11/0xff. The hashed notation
+    /// may also have four or eight hexadecimal digits, with the last one or
+    /// two digits specifying the alpha. Finally, following XParseColor, it may
+    /// have nine or twelve hexadecimal digits, e.g., `#fff000fff`, with every
+    /// coordinate scaled like the XParseColor notation.
     ///
     /// The *XParseColor notation* has between one and four hexadecimal digits
     /// per coordinate, e.g., `rgb:1/00/cafe`. Here, every coordinate is scaled,
-    /// i.e., the red coordinate in the example is 0x1/0xf.
-    ///
-    /// This method also recognizes a subset of the *CSS color syntax*. In
-    /// particular, it recognizes the `color()`, `oklab()`, and `oklch` CSS
-    /// functions. For `color()`, the color space right after the opening
-    /// parenthesis may be `srgb`, `linear-srgb`, `display-p3`,
-    /// `--linear-display-p3`, `rec2020`, `--linear-rec2020`, `--oklrab`,
-    /// `--oklrch`, or `xyz`. As indicated by the leading double-dashes, the
-    /// linear versions of Display P3 and Rec. 2020 as well as OkLrab and Oklrch
-    /// are not included in [CSS 4 Color](https://www.w3.org/TR/css-color-4/).
-    /// Coordinates must be space-separated and unitless (i.e., no `%` or
-    /// `deg`).
+    /// i.e., the red coordinate in the example is 0x1/0xf. This method also
+    /// recognizes the floating point notations of the X Color Management
+    /// System, i.e., `rgbi:` for sRGB, `CIEXYZ:` for XYZ, `CIEuvY:` and
+    /// `CIExyY:` for chromaticity coordinates plus luminance, which are
+    /// converted to XYZ, and `CIELab:` for CIELAB, e.g.,
+    /// `CIExyY:0.3127/0.329/1`.
+    ///
+    /// This method also recognizes the *CSS color syntax* of [CSS Color
+    /// 4](https://www.w3.org/TR/css-color-4/), i.e., the `rgb()`, `rgba()`,
+    /// `hsl()`, `hsla()`, `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()`, and
+    /// `color()` functions. Coordinates may be numbers, percentages, or `none`,
+    /// hues may also be angles in `deg`, `grad`, `rad`, or `turn`, and the
+    /// coordinates may be followed by a slash and the alpha, e.g., `oklch(70%
+    /// 0.1 120deg / 50%)`. Coordinates and alpha may also be `calc()`, `min()`,
+    /// `max()`, or `clamp()` expressions over numbers, percentages, and angles,
+    /// e.g., `rgb(calc(255 / 2) 0 0)`. The `rgb()`, `rgba()`, `hsl()`, and
+    /// `hsla()` functions also accept the legacy, comma-separated syntax
+    /// without expressions, e.g., `rgba(255, 127, 0, 0.5)`. For `color()`, the
+    /// color space right after the opening parenthesis may be `srgb`,
+    /// `srgb-linear` or `linear-srgb`, `display-p3`, `--linear-display-p3`,
+    /// `a98-rgb`, `--linear-a98-rgb`, `rec2020`, `--linear-rec2020`,
+    /// `rec2100-pq`, `rec2100-hlg`, `prophoto-rgb`, `--linear-prophoto-rgb`,
+    /// `--hsv`, `--oklrab`, `--oklrch`, `--okhsv`, `--okhsl`, `--cam16-ucs`,
+    /// `--hct`, `--ictcp`, `--jzazbz`, `--jzczhz`, `xyz`, `xyz-d65`, or
+    /// `xyz-d50`. As indicated by the leading double-dashes, several of these
+    /// color spaces are not included in CSS Color 4.
+    ///
+    /// This method further recognizes the `color-mix()` function of [CSS Color
+    /// 5](https://www.w3.org/TR/css-color-5/#color-mix), e.g.,
+    /// `color-mix(in oklch longer hue, red 30%, blue)`, with the same
+    /// semantics as [`Color::mix`]. Besides the color spaces of `color()`, the
+    /// interpolation color space may be `hsl`, `hwb`, `lab`, `lch`, `oklab`,
+    /// or `oklch`.
+    ///
+    /// All CSS functions also accept the *relative color syntax* of CSS Color
+    /// 5, e.g., `oklch(from teal l c calc(h + 180))`. The origin color after
+    /// `from` may be any color this method recognizes. It is converted to the
+    /// function's color space, whose coordinates are then available as
+    /// channel keywords, e.g., `l`, `c`, and `h` for Oklch, besides `alpha`.
+    /// Each coordinate may be a number, percentage, angle for hues, `none`,
+    /// channel keyword, or `calc()` expression with `+`, `-`, `*`, `/`,
+    /// `min()`, `max()`, and `clamp()`. To resolve origin colors by name, use
+    /// [`Color::parse_with`].
+    ///
+    /// Finally, this method recognizes the CSS named colors, e.g.,
+    /// `rebeccapurple`, the `transparent` keyword, and the X11 color names,
+    /// e.g., `dark slate gray`, ignoring white space for the latter. Where the
+    /// two sets of names overlap, the CSS named colors take precedence.
     ///
     /// By implementing the `FromStr` trait, `str::parse` works just the same
     /// for parsing color formats—that is, as long as type inference can
@@ -1137,15 +2541,35 @@ impl std::str::FromStr for Color {
     ///
     /// let rose: Color = str::parse("rgb:ffff/dada/cccc")?;
     /// assert_eq!(rose, Color::srgb(1, 0.8549019607843137, 0.8));
+    ///
+    /// let teal = Color::from_str("color(prophoto-rgb 0.3 0.45 0.5)")?;
+    /// assert_eq!(teal.space(), ColorSpace::ProPhotoRgb);
+    /// assert_eq!(format!("{}", teal), "color(prophoto-rgb 0.3 0.45 0.5)");
+    ///
+    /// let white = Color::from_str("CIExyY:0.3127/0.329/1")?;
+    /// assert_eq!(white.space(), ColorSpace::Xyz);
+    /// assert_eq!(white.to_hex_format(), "#ffffff");
+    ///
+    /// let salmon = Color::from_str("rgba(250, 128, 114, 0.5)")?;
+    /// assert_eq!(salmon.alpha(), 0.5);
+    /// assert_eq!(salmon.to_hex_format(), "#fa807280");
+    ///
+    /// let cyan = Color::from_str("oklch(from red l c calc(h + 180))")?;
+    /// assert_eq!(cyan.space(), ColorSpace::Oklch);
+    /// assert_eq!(cyan.to_hex_format(), "#009cb2");
     /// # Ok::<(), ColorFormatError>(())
     /// ```
     /// <div class=color-swatch>
     /// <div style="background-color: #011480;"></div>
     /// <div style="background-color: #ffdacc;"></div>
+    /// <div style="background-color: color(prophoto-rgb 0.3 0.45 0.5);"></div>
+    /// <div style="background-color: #ffffff;"></div>
+    /// <div style="background-color: rgba(250, 128, 114, 0.5);"></div>
+    /// <div style="background-color: oklch(from red l c calc(h + 180));"></div>
     /// </div>
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse(s).map(|(space, coordinates)| Self::new(space, coordinates))
+        parse(s).map(|(space, coordinates, alpha)| Self::new(space, coordinates).with_alpha(alpha))
     }
 }
 
@@ -1227,6 +2651,7 @@ impl std::hash::Hash for Color {
         n1.hash(state);
         n2.hash(state);
         n3.hash(state);
+        alpha_to_eq_bits(self.alpha).hash(state);
     }
 }
 
@@ -1245,7 +2670,8 @@ impl PartialEq for Color {
     ///   * To preserve not-a-number semantics for hues, also zero out chroma for
     ///     not-a-number hues in Oklch;
     ///   * To preserve rotation semantics for hues, remove all full rotations;
-    ///   * To prepare for rounding, scale down hues to unit range;
+    ///   * To prepare for rounding, scale down hues to unit range and
+    ///     CIELAB's lightness, a/b, and chroma by 100;
     ///   * To allow for floating point error, multiply by 1e5/1e14 (depending
     ///     on `Float`'s type) and then round to drop least significant digit;
     ///   * To make zeros comparable, replace negative zero with positive zero
@@ -1255,7 +2681,9 @@ impl PartialEq for Color {
     ///
     /// While rounding isn't strictly necessary for correctness, it makes for a
     /// more robust comparison without meaningfully reducing precision, at least
-    /// for the default representation using `f64`.
+    /// for the default representation using `f64`. Both methods also compare
+    /// alpha values after replacing not-a-number with zero, clamping to
+    /// `0..=1`, and rounding.
     ///
     /// # Examples
     ///
@@ -1277,7 +2705,9 @@ impl PartialEq for Color {
     /// <div style="background-color: oklch(0.5 0.1 305);"></div>
     /// </div>
     fn eq(&self, other: &Self) -> bool {
-        if self.space != other.space {
+        if self.space != other.space
+            || alpha_to_eq_bits(self.alpha) != alpha_to_eq_bits(other.alpha)
+        {
             return false;
         } else if self.coordinates == other.coordinates {
             return true;
@@ -1295,11 +2725,13 @@ impl std::fmt::Display for Color {
     /// Format this color.
     ///
     /// This method formats the color in CSS format using either a `color()`,
-    /// `oklab()`, or `oklch()` CSS function and three space-separated
-    /// coordinates. It respects the formatter's precision, defaulting to 5
-    /// digits past the decimal. Since degrees for Oklch/Oklrch are up to two
-    /// orders of magnitude larger than other coordinates, this method uses a
-    /// precision smaller by 2 for degrees.
+    /// `oklab()`, `oklch()`, `lab()`, or `lch()` CSS function and three
+    /// space-separated coordinates. If the color is not fully opaque, the
+    /// coordinates are followed by a slash and the alpha. It respects the
+    /// formatter's precision, defaulting to 5 digits past the decimal. Since
+    /// degrees for polar color spaces are up to two orders of magnitude larger
+    /// than other coordinates, this method uses a precision smaller by 2 for
+    /// degrees.
     ///
     /// # Examples
     ///
@@ -1343,9 +2775,76 @@ impl std::fmt::Display for Color {
     /// <div class=color-swatch>
     /// <div style="background-color: oklch(0.665 0 none);"></div>
     /// </div>
+    /// <br>
+    ///
+    /// Finally, a translucent color also includes its alpha.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorFormatError, ColorSpace::*};
+    /// # use std::str::FromStr;
+    /// let haze = Color::oklch(0.8, 0.05, 250).with_alpha(0.4);
+    /// assert_eq!(format!("{}", haze), "oklch(0.8 0.05 250 / 0.4)");
+    /// assert_eq!(Color::from_str("oklch(0.8 0.05 250 / 0.4)")?, haze);
+    /// # Ok::<(), ColorFormatError>(())
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: oklch(0.8 0.05 250 / 0.4);"></div>
+    /// </div>
+    /// <br>
+    ///
+    /// The alternate form `{:#}` formats the color in legacy `rgb()` format
+    /// instead, just like [`Color::to_format`] with [`ColorFormat::Rgb`].
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        format(self.space, &self.coordinates, f)
+        if f.alternate() {
+            format_as(
+                ColorFormat::Rgb,
+                self.space,
+                &self.coordinates,
+                self.alpha,
+                f,
+            )
+        } else {
+            format(self.space, &self.coordinates, self.alpha, f)
+        }
+    }
+}
+
+impl std::fmt::LowerHex for Color {
+    /// Format this color in hexadecimal.
+    ///
+    /// The normal form `{:x}` formats the color in hashed hexadecimal format,
+    /// just like [`Color::to_format`] with [`ColorFormat::Hex`], whereas the
+    /// alternate form `{:#x}` formats the color in X Windows format, just like
+    /// [`Color::to_format`] with [`ColorFormat::X`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = if f.alternate() {
+            ColorFormat::X
+        } else {
+            ColorFormat::Hex
+        };
+        format_as(format, self.space, &self.coordinates, self.alpha, f)
+    }
+}
+
+impl std::fmt::UpperHex for Color {
+    /// Format this color in hexadecimal with uppercase digits.
+    ///
+    /// This method produces the same formats as
+    /// [`LowerHex`](struct.Color.html#impl-LowerHex-for-Color), only with
+    /// uppercase hexadecimal digits.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = if f.alternate() {
+            format!("{:#x}", self)
+        } else {
+            format!("{:x}", self)
+        };
+
+        // Only uppercase the digits, not the X Windows prefix.
+        match s.strip_prefix("rgb:") {
+            Some(digits) => write!(f, "rgb:{}", digits.to_ascii_uppercase()),
+            None => f.write_str(&s.to_ascii_uppercase()),
+        }
     }
 }
 
@@ -1384,6 +2883,238 @@ impl OkVersion {
 
 // ====================================================================================================================
 
+/// A choice of color harmonies.
+///
+/// A color harmony combines colors whose hues are evenly spaced on the color
+/// wheel. [`Color::harmony`] rotates the hue in Oklrch, which has the same hue
+/// as Oklch.
+///
+///   * **Complementary** adds the opposite hue, i.e., rotated by 180°.
+///   * **Analogous** adds the neighboring hues, i.e., rotated by 30° and -30°.
+///   * **Triadic** adds the hues rotated by 120° and 240°.
+///   * **Tetradic** adds the hues rotated by 90°, 180°, and 270°.
+#[cfg_attr(feature = "pyffi", pyclass(eq, eq_int, frozen, hash))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Harmony {
+    Complementary,
+    Analogous,
+    Triadic,
+    Tetradic,
+}
+
+impl Harmony {
+    /// Get the hue rotations in degrees for the colors added by this harmony.
+    const fn rotations(&self) -> &'static [Float] {
+        match *self {
+            Self::Complementary => &[180.0],
+            Self::Analogous => &[30.0, -30.0],
+            Self::Triadic => &[120.0, 240.0],
+            Self::Tetradic => &[90.0, 180.0, 270.0],
+        }
+    }
+}
+
+// ====================================================================================================================
+
+/// A choice of color adjustments.
+///
+/// [`Color::adjust`] and [`Theme::adjust`](crate::Theme::adjust) apply an
+/// adjustment in Oklrch, which has the same chroma and hue as Oklch but a
+/// more accurate lightness.
+///
+///   * **RotateHue** rotates the hue by the given degrees.
+///   * **Saturate** and **Desaturate** increase and decrease chroma by the
+///     given absolute amount, with chroma never dropping below zero.
+///   * **ScaleChroma** multiplies chroma by the given factor.
+///   * **SetLightness** and **SetChroma** replace lightness and chroma,
+///     respectively.
+///   * **Grayscale** drops chroma to zero while preserving lightness, i.e., it
+///     is a perceptual grayscale conversion.
+///   * **Invert** flips lightness, i.e., replaces Lr with 1 - Lr, while
+///     preserving chroma and hue.
+///   * **Tint** and **Shade** mix the color with white and black,
+///     respectively, by interpolating in Oklrab with the given fraction of
+///     white or black.
+#[cfg_attr(feature = "pyffi", pyclass(eq, frozen))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Adjustment {
+    RotateHue { degrees: Float },
+    Saturate { amount: Float },
+    Desaturate { amount: Float },
+    ScaleChroma { factor: Float },
+    SetLightness { lightness: Float },
+    SetChroma { chroma: Float },
+    Grayscale(),
+    Invert(),
+    Tint { fraction: Float },
+    Shade { fraction: Float },
+}
+
+impl Adjustment {
+    /// Apply this adjustment to the coordinates, returning Oklrch
+    /// coordinates.
+    pub(crate) fn apply(&self, space: ColorSpace, coordinates: &[Float; 3]) -> [Float; 3] {
+        let [lr, c, h] = convert(space, ColorSpace::Oklrch, coordinates);
+
+        match *self {
+            Self::RotateHue { degrees } => [lr, c, (h + degrees).rem_euclid(360.0)],
+            Self::Saturate { amount } => [lr, (c + amount).max(0.0), h],
+            Self::Desaturate { amount } => [lr, (c - amount).max(0.0), h],
+            Self::ScaleChroma { factor } => [lr, (c * factor).max(0.0), h],
+            Self::SetLightness { lightness } => [lightness, c, h],
+            Self::SetChroma { chroma } => [lr, chroma.max(0.0), h],
+            Self::Grayscale() => [lr, 0.0, h],
+            Self::Invert() => [1.0 - lr, c, h],
+            Self::Tint { fraction } => Self::mix(space, coordinates, 1.0, fraction),
+            Self::Shade { fraction } => Self::mix(space, coordinates, 0.0, fraction),
+        }
+    }
+
+    /// Mix the coordinates with the achromatic color of the given lightness.
+    fn mix(
+        space: ColorSpace,
+        coordinates: &[Float; 3],
+        lightness: Float,
+        fraction: Float,
+    ) -> [Float; 3] {
+        let [lr, a, b] = convert(space, ColorSpace::Oklrab, coordinates);
+        let keep = 1.0 - fraction;
+        convert(
+            ColorSpace::Oklrab,
+            ColorSpace::Oklrch,
+            &[lr * keep + lightness * fraction, a * keep, b * keep],
+        )
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Adjustment {
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// ====================================================================================================================
+
+/// A choice of color difference metrics.
+///
+/// [`Color::distance`] and [`Color::find_closest_ok`] use a color difference
+/// metric to compare colors.
+///
+/// # Ok
+///
+/// *ΔE OK* is the Euclidian distance in Oklab or Oklrab, depending on the
+/// version. It is cheap to compute and the metric used for downsampling by
+/// [`Sampler`](crate::Sampler).
+///
+/// # Ciede2000
+///
+/// *CIEDE2000* is the CIE's current color difference metric. It corrects
+/// CIELAB's perceptual non-uniformity through weighting functions for
+/// lightness, chroma, and hue as well as a term for the interaction between
+/// chroma and hue differences in the blue region. Since it was fitted to
+/// small color differences, it is the most accurate metric for deciding
+/// whether two colors appear the same.
+///
+/// # Cie94
+///
+/// *CIE94* is CIEDE2000's predecessor and weighs chroma and hue differences
+/// by the reference color's chroma. It uses the weighting factors for graphic
+/// arts. It is not symmetric and uses the first color as reference.
+///
+/// # Cmc
+///
+/// *CMC l:c* is the British Colour Measurement Committee's metric, with
+/// separate weights for lightness and chroma. [`DeltaE::CMC`] uses the weights
+/// 2:1 for acceptability. It is not symmetric and uses the first color as
+/// reference.
+///
+/// # HyAb
+///
+/// *HyAB* adds the absolute lightness difference to the Euclidian distance in
+/// CIELAB's a*b* plane. It is more accurate than Euclidian metrics for large
+/// color differences, such as those arising when downsampling to the 16 ANSI
+/// colors.
+///
+/// # Itp
+///
+/// *ΔE ITP* is the Euclidian distance in ICtCp as defined by ITU-R BT.2124
+/// and designed for high dynamic range content.
+///
+/// CIEDE2000, CIE94, CMC l:c, and HyAB all use CIELAB with the D50 white
+/// point.
+#[cfg_attr(feature = "pyffi", pyclass(eq, frozen))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DeltaE {
+    Ok(OkVersion),
+    Ciede2000(),
+    Cie94(),
+    Cmc { lightness: Float, chroma: Float },
+    HyAb(),
+    Itp(),
+}
+
+impl DeltaE {
+    /// CMC l:c with the weights 2:1 for acceptability.
+    pub const CMC: Self = Self::Cmc {
+        lightness: 2.0,
+        chroma: 1.0,
+    };
+
+    /// Compute the color difference between coordinates in this metric's color
+    /// space.
+    pub(crate) fn compute(&self, coordinates1: &[Float; 3], coordinates2: &[Float; 3]) -> Float {
+        match *self {
+            Self::Ok(_) => delta_e_ok(coordinates1, coordinates2),
+            Self::Ciede2000() => delta_e_2000(coordinates1, coordinates2),
+            Self::Cie94() => delta_e_94(coordinates1, coordinates2),
+            Self::Cmc { lightness, chroma } => {
+                delta_e_cmc(lightness, chroma, coordinates1, coordinates2)
+            }
+            Self::HyAb() => delta_e_hyab(coordinates1, coordinates2),
+            Self::Itp() => delta_e_itp(coordinates1, coordinates2),
+        }
+    }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl DeltaE {
+    /// CMC l:c with the weights 2:1 for acceptability. <span
+    /// class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    #[classattr]
+    #[pyo3(name = "CMC")]
+    pub fn cmc() -> Self {
+        Self::CMC
+    }
+
+    /// Determine the color space this metric operates in.
+    pub const fn space(&self) -> ColorSpace {
+        match *self {
+            Self::Ok(version) => version.cartesian_space(),
+            Self::Ciede2000() | Self::Cie94() | Self::Cmc { .. } | Self::HyAb() => ColorSpace::Lab,
+            Self::Itp() => ColorSpace::Ictcp,
+        }
+    }
+
+    /// Get a debug representation. <span class=python-only></span>
+    #[cfg(feature = "pyffi")]
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl From<OkVersion> for DeltaE {
+    /// Convert the Oklab version to its ΔE OK metric.
+    fn from(value: OkVersion) -> Self {
+        Self::Ok(value)
+    }
+}
+
+// ====================================================================================================================
+
 /// A color interpolator.
 ///
 /// An interpolator performs linear interpolation between the coordinates of two
@@ -1397,6 +3128,9 @@ impl OkVersion {
 /// interpolations for the its two source colors and thus potentially amortize
 /// the cost of preparation.
 ///
+/// As required by CSS Color 4, an interpolator also interpolates the alpha and
+/// uses premultiplied coordinates when interpolating translucent colors.
+///
 /// See [`Color::interpolate`] for detailed examples.
 #[cfg_attr(feature = "pyffi", pyclass)]
 #[derive(Clone, Debug)]
@@ -1404,16 +3138,13 @@ pub struct Interpolator {
     space: ColorSpace,
     coordinates1: [Float; 3],
     coordinates2: [Float; 3],
+    alpha1: Float,
+    alpha2: Float,
 }
 
-#[cfg_attr(feature = "pyffi", pymethods)]
 impl Interpolator {
-    /// Create a new color interpolator.
-    ///
-    /// See [`Color::interpolate`] for detailed examples.
-    #[cfg(feature = "pyffi")]
-    #[new]
-    pub fn new(
+    /// Prepare the two colors for interpolation.
+    fn create(
         color1: &Color,
         color2: &Color,
         space: ColorSpace,
@@ -1427,13 +3158,33 @@ impl Interpolator {
             space,
             strategy,
         );
+        let [alpha1, alpha2] = prepare_alpha_interpolation(color1.alpha, color2.alpha);
 
         Self {
             space,
-            coordinates1,
-            coordinates2,
+            coordinates1: premultiply(space, &coordinates1, alpha1),
+            coordinates2: premultiply(space, &coordinates2, alpha2),
+            alpha1,
+            alpha2,
         }
     }
+}
+
+#[cfg_attr(feature = "pyffi", pymethods)]
+impl Interpolator {
+    /// Create a new color interpolator.
+    ///
+    /// See [`Color::interpolate`] for detailed examples.
+    #[cfg(feature = "pyffi")]
+    #[new]
+    pub fn new(
+        color1: &Color,
+        color2: &Color,
+        space: ColorSpace,
+        strategy: HueInterpolation,
+    ) -> Self {
+        Self::create(color1, color2, space, strategy)
+    }
 
     /// Create a new color interpolator.
     ///
@@ -1446,28 +3197,143 @@ impl Interpolator {
         space: ColorSpace,
         strategy: HueInterpolation,
     ) -> Self {
-        let (coordinates1, coordinates2) = prepare_to_interpolate(
-            color1.space,
-            &color1.coordinates,
-            color2.space,
-            &color2.coordinates,
-            space,
-            strategy,
-        );
-
-        Self {
-            space,
-            coordinates1,
-            coordinates2,
-        }
+        Self::create(color1, color2, space, strategy)
     }
 
     /// Compute the interpolated color for the given fraction.
     ///
-    /// See [`Color::interpolate`] for detailed examples.
+    /// See [`Color::interpolate`] for detailed examples. When interpolating
+    /// translucent colors, the interpolated color's coordinates are weighted
+    /// by alpha. Hence, interpolating with a fully transparent color does not
+    /// change the coordinates, only the alpha.
+    ///
+    /// ```
+    /// # use prettypretty::{Color, ColorSpace, HueInterpolation};
+    /// let red = Color::srgb(1, 0, 0);
+    /// let clear = Color::srgb(0, 0, 1).with_alpha(0.0);
+    /// let faded = red
+    ///     .interpolate(&clear, ColorSpace::Srgb, HueInterpolation::Shorter)
+    ///     .at(0.5);
+    /// assert_eq!(faded, Color::srgb(1, 0, 0).with_alpha(0.5));
+    /// ```
+    /// <div class=color-swatch>
+    /// <div style="background-color: color(srgb 1 0 0);"></div>
+    /// <div style="background-color: color(srgb 1 0 0 / 0.5);"></div>
+    /// </div>
     #[inline]
     pub fn at(&self, fraction: f64) -> Color {
-        let [c1, c2, c3] = interpolate(fraction, &self.coordinates1, &self.coordinates2);
-        Color::new(self.space, [c1, c2, c3])
+        let alpha = self.alpha1 + fraction * (self.alpha2 - self.alpha1);
+        let coordinates = interpolate(fraction, &self.coordinates1, &self.coordinates2);
+        Color::new(self.space, unpremultiply(self.space, &coordinates, alpha)).with_alpha(alpha)
+    }
+}
+
+// ====================================================================================================================
+
+#[cfg(test)]
+mod test {
+    use crate::{Adjustment, Color, ColorSpace, Float, Harmony, HueInterpolation};
+
+    #[test]
+    fn test_mix() {
+        let black = Color::srgb(0.0, 0.0, 0.0);
+        let white = Color::srgb(1.0, 1.0, 1.0);
+        let mix = |w1, c1: &Color, w2, c2: &Color| {
+            c1.mix(w1, c2, w2, ColorSpace::Srgb, HueInterpolation::Shorter)
+        };
+
+        // Percentages summing to 0% or outside 0%..=100% yield no color.
+        assert_eq!(mix(Some(0.0), &black, Some(0.0), &white), None);
+        assert_eq!(mix(Some(0.0), &black, None, &white), Some(white.clone()));
+        assert_eq!(mix(Some(1.2), &black, None, &white), None);
+        assert_eq!(mix(Some(-0.2), &black, Some(0.5), &white), None);
+
+        // Percentages summing to more than 100% are normalized.
+        let gray = mix(Some(0.8), &black, Some(0.6), &white).unwrap();
+        assert_eq!(gray, Color::srgb(0.6 / 1.4, 0.6 / 1.4, 0.6 / 1.4));
+        assert_eq!(gray.alpha(), 1.0);
+
+        // Percentages summing to less than 100% also multiply the alpha.
+        let gray = mix(Some(0.2), &black, Some(0.2), &white).unwrap();
+        assert_eq!(gray, Color::srgb(0.5, 0.5, 0.5).with_alpha(0.4));
+
+        let translucent_black = black.with_alpha(0.5);
+        let translucent_white = white.with_alpha(0.5);
+        let gray = mix(
+            Some(0.25),
+            &translucent_black,
+            Some(0.25),
+            &translucent_white,
+        )
+        .unwrap();
+        assert_eq!(gray, Color::srgb(0.5, 0.5, 0.5).with_alpha(0.25));
+    }
+
+    #[test]
+    fn test_harmony_and_tints() {
+        // Achromatic colors have a missing hue, which rotation preserves.
+        let gray = Color::srgb(0.5, 0.5, 0.5);
+        let oklch_gray = Color::new(ColorSpace::Oklch, [0.5, 0.0, Float::NAN]);
+        for color in [&gray, &oklch_gray] {
+            let [lr, _, _] = *color.to(ColorSpace::Oklrch).as_ref();
+            for harmony in [
+                Harmony::Complementary,
+                Harmony::Analogous,
+                Harmony::Triadic,
+                Harmony::Tetradic,
+            ] {
+                let colors = color.harmony(harmony, ColorSpace::Srgb);
+                assert_eq!(colors.len(), harmony.rotations().len() + 1);
+                for other in colors {
+                    assert!(other.in_gamut());
+                    let [other_lr, other_c, _] = *other.to(ColorSpace::Oklrch).as_ref();
+                    assert!((other_lr - lr).abs() < 1e-9);
+                    assert!(other_c < 1e-9);
+                }
+            }
+
+            // Tints of achromatic colors are grays with increasing lightness.
+            let tints = color.tints(3, ColorSpace::Srgb);
+            assert_eq!(tints.len(), 3);
+            let mut previous = lr;
+            for tint in tints {
+                let [r, g, b] = *tint.as_ref();
+                assert!(r.is_finite() && (r - g).abs() < 1e-9 && (g - b).abs() < 1e-9);
+                let [tint_lr, _, _] = *tint.to(ColorSpace::Oklrch).as_ref();
+                assert!(previous < tint_lr && tint_lr < 1.0);
+                previous = tint_lr;
+            }
+
+            assert!(color.tints(0, ColorSpace::Srgb).is_empty());
+            assert!(color.shades(0, ColorSpace::Srgb).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_adjust() {
+        let teal = Color::from_24bit(0x00, 0x80, 0x80).with_alpha(0.5);
+        let [_, c, h] = *teal.to(ColorSpace::Oklrch).as_ref();
+
+        // Without target, the result is in Oklrch and preserves alpha.
+        let rotated = teal.adjust(Adjustment::RotateHue { degrees: 90.0 }, None);
+        assert_eq!(rotated.space(), ColorSpace::Oklrch);
+        assert_eq!(rotated.alpha(), 0.5);
+        assert!((rotated[2] - (h + 90.0)).abs() < 1e-9);
+
+        // SetLightness outside 0..=1 is not clamped in Oklrch, but gamut
+        // mapping to sRGB yields white and black, respectively.
+        for (lightness, hex) in [(1.5, "#ffffff80"), (-0.5, "#00000080")] {
+            let adjustment = Adjustment::SetLightness { lightness };
+
+            let color = teal.adjust(adjustment, None);
+            assert_eq!(color.space(), ColorSpace::Oklrch);
+            assert_eq!(color.alpha(), 0.5);
+            assert_eq!(color[0], lightness);
+            assert!((color[1] - c).abs() < 1e-9);
+
+            let color = teal.adjust(adjustment, Some(ColorSpace::Srgb));
+            assert_eq!(color.space(), ColorSpace::Srgb);
+            assert_eq!(color.to_hex_format(), hex);
+        }
     }
 }
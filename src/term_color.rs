@@ -1,3 +1,7 @@
+// PyO3 wraps the result of a fallible method by converting its error into a
+// PyErr, which is a no-op for methods that already return a PyResult.
+#![cfg_attr(feature = "pyffi", allow(clippy::useless_conversion))]
+
 #[cfg(feature = "pyffi")]
 use pyo3::prelude::*;
 